@@ -0,0 +1,121 @@
+//! Times parsing and each part of every day separately, against the sample inputs from the puzzle text,
+//! the real inputs, and a generated input of about the same size as a real one. Run with `cargo bench -p aoc2023`, optionally filtered like `-- day03/part1`.
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use aoch::{AoCDay, daystr};
+use aocinput::gen::Generate;
+use aocinput::normalize::normalize;
+use aoc2023::Parts;
+
+const P1: Option<u8> = Some(1);
+const P2: Option<u8> = Some(2);
+const BOTH: Option<u8> = None;
+
+/// Benchmarks one day over a list of named inputs, each with the parts that it's valid for.
+///
+/// Some samples only make sense for one part, so they are only measured against that part.
+fn bench_day<D: AoCDay + Copy>(c: &mut Criterion, day: D, inputs: &[(&str, &str, Option<u8>)]) {
+	let mut group = c.benchmark_group(format!("day{:02}", day.day()));
+	for &(name, input, parts) in inputs {
+		// inputs may be missing from a checkout without them
+		if input.trim().is_empty() { continue; }
+		// normalised up front like the runner does, so that only the parser is timed
+		let normalized = normalize(input).text;
+		let input = &*normalized;
+		let parts = Parts::from(parts);
+		group.throughput(Throughput::Bytes(input.len() as u64));
+
+		group.bench_with_input(BenchmarkId::new("parse", name), input, |b, i| {
+			b.iter(|| day.parse(i))
+		});
+		if parts.part1 {
+			group.bench_with_input(BenchmarkId::new("part1", name), input, |b, i| {
+				b.iter_batched_ref(|| day.parse(i), |data| day.part1(data), BatchSize::SmallInput)
+			});
+		}
+		if parts.part2 {
+			group.bench_with_input(BenchmarkId::new("part2", name), input, |b, i| {
+				b.iter_batched_ref(|| day.parse(i), |data| day.part2(data), BatchSize::SmallInput)
+			});
+		}
+	}
+	group.finish();
+}
+
+/// A generated input with the default parameters
+fn generated<D: Generate>(day: D) -> String {
+	day.generate(0, &D::Params::default()).input
+}
+
+fn days(c: &mut Criterion) {
+	bench_day(c, day01::Day01, &[
+		("sample1", day01::TEST_INPUT_P1, P1),
+		("sample2", day01::TEST_INPUT_P2, P2),
+		("input", daystr!("01"), BOTH),
+		("generated", &generated(day01::Day01), BOTH),
+	]);
+	bench_day(c, day02::Day02, &[
+		("sample", day02::TEST_INPUT, BOTH),
+		("input", daystr!("02"), BOTH),
+		("generated", &generated(day02::Day02), BOTH),
+	]);
+	bench_day(c, day03::Day03, &[
+		("sample", day03::TEST_INPUT, BOTH),
+		("input", daystr!("03"), BOTH),
+		("generated", &generated(day03::Day03), BOTH),
+	]);
+	bench_day(c, day04::Day04, &[
+		("sample", day04::TEST_INPUT, BOTH),
+		("input", daystr!("04"), BOTH),
+		("generated", &generated(day04::Day04), BOTH),
+	]);
+	bench_day(c, day05::Day05, &[
+		("sample", day05::TEST_INPUT, BOTH),
+		("input", daystr!("05"), BOTH),
+		("generated", &generated(day05::Day05), BOTH),
+	]);
+	bench_day(c, day06::Day06, &[
+		("sample", day06::TEST_INPUT, BOTH),
+		("input", daystr!("06"), BOTH),
+		("generated", &generated(day06::Day06), BOTH),
+	]);
+	bench_day(c, day07::Day07, &[
+		("sample", day07::TEST_INPUT, BOTH),
+		("input", daystr!("07"), BOTH),
+		("generated", &generated(day07::Day07), BOTH),
+	]);
+	bench_day(c, day08::Day08, &[
+		("sample_rl", day08::TEST_INPUT_RL, P1),
+		("sample_llr", day08::TEST_INPUT_LLR, P1),
+		("sample_parallel", day08::TEST_INPUT_LR_PAR, P2),
+		("input", daystr!("08"), BOTH),
+		("generated", &generated(day08::Day08), BOTH),
+	]);
+	bench_day(c, day09::Day09, &[
+		("sample", day09::TEST_INPUT, BOTH),
+		("sample3", day09::TEST_INPUT_3, P2),
+		("input", daystr!("09"), BOTH),
+		("generated", &generated(day09::Day09), BOTH),
+	]);
+	bench_day(c, day10::Day10, &[
+		("sample_simple", day10::TEST_INPUT_P1_SIMPLE, P1),
+		("sample_complex", day10::TEST_INPUT_P1_COMPLEX, P1),
+		("sample_enclosed", day10::TEST_INPUT_P2_SIMPLE, P2),
+		("sample_large", day10::TEST_INPUT_P2_LARGE, P2),
+		("sample_trash", day10::TEST_INPUT_P2_WITHTRASH, P2),
+		("input", daystr!("10"), BOTH),
+		("generated", &generated(day10::Day10), BOTH),
+	]);
+	bench_day(c, day11::Day11, &[
+		("sample", day11::TEST_INPUT, BOTH),
+		("input", daystr!("11"), BOTH),
+		("generated", &generated(day11::Day11), BOTH),
+	]);
+	bench_day(c, day12::Day12, &[
+		("sample", day12::TEST_INPUT, BOTH),
+		("input", daystr!("12"), BOTH),
+		("generated", &generated(day12::Day12), BOTH),
+	]);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+use std::net::Ipv4Addr;
+use std::process::ExitCode;
+use std::time::Duration;
+use clap::Parser;
+use aoc2023::server::{Config, Server};
+use aoc2023::trace;
+
+/// Solves puzzle inputs POSTed to `/day/{n}/part/{p}` on localhost, answering with JSON records like
+/// `aoc2023 --format json` prints.
+#[derive(Debug, Parser)]
+#[command(name = "server")]
+struct Args {
+	/// The port to listen on, or 0 for any free one
+	#[arg(short, long, default_value_t = 2023)]
+	port: u16,
+
+	/// The largest input accepted, in bytes
+	#[arg(long, default_value_t = Config::default().max_body)]
+	max_body: usize,
+
+	/// How many seconds a request can take to arrive
+	#[arg(long, default_value_t = Config::default().read_timeout.as_secs())]
+	read_timeout: u64,
+
+	/// How many seconds a request can take to be solved
+	#[arg(long, default_value_t = Config::default().timeout.as_secs())]
+	timeout: u64,
+
+	/// How many inputs can be solved at once, counting ones that timed out but are still running. Defaults to
+	/// the number of cores.
+	#[arg(long, default_value_t = Config::default().max_solves)]
+	max_solves: usize,
+}
+
+fn main() -> ExitCode {
+	let args = Args::parse();
+	// each request is logged to stderr at the info level, filtered by RUST_LOG
+	trace::init(None);
+
+	let config = Config {
+		max_body: args.max_body,
+		read_timeout: Duration::from_secs(args.read_timeout),
+		timeout: Duration::from_secs(args.timeout),
+		max_solves: args.max_solves,
+	};
+	let server = match Server::bind((Ipv4Addr::LOCALHOST, args.port), config, days::registry()) {
+		Ok(server) => server,
+		Err(e) => {
+			eprintln!("unable to listen on port {}: {}", args.port, e);
+			return ExitCode::FAILURE;
+		},
+	};
+	eprintln!("listening on http://{}", server.addr());
+	server.run();
+	ExitCode::SUCCESS
+}
@@ -0,0 +1,343 @@
+//! A local history of how long each run took, kept as one JSON object per line in `history.jsonl` at the root
+//! of the workspace, so that tuning a solver can be checked against the runs from before it.
+//!
+//! Runs are grouped by the git revision they were built from (with a `-dirty` suffix for uncommitted changes) and
+//! their build profile. Comparing two revisions needs a few runs of each, as a single run is too noisy to tell
+//! anything from.
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use aocinput::answers::EMBEDDED;
+use crate::DayReport;
+use crate::json::{nanos, profile};
+
+#[derive(Debug, thiserror::Error)]
+pub enum HistoryError {
+	#[error("unable to access {0}: {1}")]
+	Io(PathBuf, std::io::Error),
+	#[error("{0}:{1} is not a valid run: {2}")]
+	Json(PathBuf, usize, serde_json::Error),
+}
+
+/// The path of `history.jsonl` at the root of the workspace.
+pub fn workspace_path() -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("history.jsonl")
+}
+
+/// The git revision of the workspace, as given by `git describe --always --dirty`, or `unknown` if git can't
+/// tell.
+pub fn revision() -> String {
+	Command::new("git")
+		.args(["describe", "--always", "--dirty"])
+		.current_dir(env!("CARGO_MANIFEST_DIR"))
+		.output().ok()
+		.filter(|out| out.status.success())
+		.and_then(|out| String::from_utf8(out.stdout).ok())
+		.map(|rev| rev.trim().to_owned())
+		.filter(|rev| !rev.is_empty())
+		.unwrap_or_else(|| "unknown".to_owned())
+}
+
+/// How long each phase of a day took on a single input. Parts that weren't solved have no time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+	pub day: u8,
+	pub input: String,
+	pub parse_ns: u64,
+	pub part1_ns: Option<u64>,
+	pub part2_ns: Option<u64>,
+}
+
+/// The timings of every input solved in a single run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+	/// Seconds since the unix epoch
+	pub timestamp: u64,
+	pub revision: String,
+	pub profile: String,
+	pub timings: Vec<Timing>,
+}
+
+impl Run {
+	pub fn new(reports: &[DayReport], revision: String) -> Run {
+		let timings = reports.iter()
+			.map(|r| Timing {
+				day: r.day,
+				input: r.input.clone(),
+				parse_ns: nanos(r.parse),
+				part1_ns: r.part1.as_ref().map(|p| nanos(p.elapsed)),
+				part2_ns: r.part2.as_ref().map(|p| nanos(p.elapsed)),
+			})
+			.collect();
+		let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+		Run { timestamp, revision, profile: profile().to_owned(), timings }
+	}
+
+	/// Each timed phase, as `(day, input, phase, nanoseconds)`
+	fn phases(&self) -> impl Iterator<Item = (u8, &str, Phase, u64)> + '_ {
+		self.timings.iter().flat_map(|t| {
+			[(Phase::Parse, Some(t.parse_ns)), (Phase::Part1, t.part1_ns), (Phase::Part2, t.part2_ns)].into_iter()
+				.filter_map(move |(phase, ns)| Some((t.day, t.input.as_str(), phase, ns?)))
+		})
+	}
+}
+
+/// Adds a run to the end of the history at `path`, creating it if it doesn't exist yet.
+pub fn append(path: &Path, run: &Run) -> Result<(), HistoryError> {
+	let line = serde_json::to_string(run).expect("runs serialize");
+	OpenOptions::new().create(true).append(true).open(path)
+		.and_then(|mut file| writeln!(file, "{}", line))
+		.map_err(|e| HistoryError::Io(path.to_owned(), e))
+}
+
+/// Every run in the history at `path`, oldest first. A file that doesn't exist yet has no runs.
+pub fn load(path: &Path) -> Result<Vec<Run>, HistoryError> {
+	let raw = match std::fs::read_to_string(path) {
+		Ok(raw) => raw,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+		Err(e) => return Err(HistoryError::Io(path.to_owned(), e)),
+	};
+	raw.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty())
+		.map(|(i, line)| serde_json::from_str(line).map_err(|e| HistoryError::Json(path.to_owned(), i+1, e)))
+		.collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+	Parse,
+	Part1,
+	Part2,
+}
+
+impl fmt::Display for Phase {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Phase::Parse => "parse",
+			Phase::Part1 => "part 1",
+			Phase::Part2 => "part 2",
+		})
+	}
+}
+
+/// Whether a phase got slower, by more than the threshold and by more than the noise between runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+	Slower,
+	Faster,
+	Unchanged,
+	/// There weren't at least two runs of both revisions to tell the noise from
+	TooFewRuns,
+}
+
+/// The one-sided 95% critical values of Student's t distribution, for 1 to 30 degrees of freedom
+const T_CRITICAL: [f64; 30] = [
+	6.314, 2.920, 2.353, 2.132, 2.015, 1.943, 1.895, 1.860, 1.833, 1.812,
+	1.796, 1.782, 1.771, 1.761, 1.753, 1.746, 1.740, 1.734, 1.729, 1.725,
+	1.721, 1.717, 1.714, 1.711, 1.708, 1.706, 1.703, 1.701, 1.699, 1.697,
+];
+
+fn mean(samples: &[f64]) -> f64 {
+	samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+fn variance(samples: &[f64]) -> f64 {
+	let m = mean(samples);
+	samples.iter().map(|s| (s - m).powi(2)).sum::<f64>() / (samples.len() - 1) as f64
+}
+
+/// Whether the means of two samples differ by more than chance, using Welch's t-test at 95% confidence in the
+/// direction that `b` differs from `a`. Needs at least two of each.
+fn significant(a: &[f64], b: &[f64]) -> bool {
+	let (na, nb) = (a.len() as f64, b.len() as f64);
+	let (va, vb) = (variance(a) / na, variance(b) / nb);
+	let diff = (mean(b) - mean(a)).abs();
+	if va + vb == 0.0 {
+		// every run took exactly as long as the others, so any difference at all is real
+		return diff > 0.0;
+	}
+	let t = diff / (va + vb).sqrt();
+	// the Welch-Satterthwaite approximation, rounded down to stay on the cautious side
+	let df = (va + vb).powi(2) / (va.powi(2) / (na - 1.0) + vb.powi(2) / (nb - 1.0));
+	let critical = T_CRITICAL.get((df.floor() as usize).max(1) - 1).copied().unwrap_or(1.645);
+	t > critical
+}
+
+/// How a single phase of a day's input compares between the two revisions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+	pub day: u8,
+	pub input: String,
+	pub phase: Phase,
+	/// The mean time of each revision's runs, in nanoseconds
+	pub baseline_ns: f64,
+	pub candidate_ns: f64,
+	pub verdict: Verdict,
+}
+
+/// Every phase timed by both the latest revision and a baseline revision before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+	pub profile: String,
+	/// Each revision, with how many runs it had
+	pub baseline: (String, usize),
+	pub candidate: (String, usize),
+	pub changes: Vec<Change>,
+}
+
+impl Comparison {
+	/// Compares the runs of the latest revision in `runs` against those of `baseline`, or of the last revision
+	/// run before it if there's no baseline given. Only runs with the latest run's build profile are compared,
+	/// and a phase has to be `threshold` percent slower or faster to count.
+	pub fn new(runs: &[Run], baseline: Option<&str>, threshold: f64) -> Result<Comparison, String> {
+		let latest = runs.last().ok_or("no runs have been recorded yet")?;
+		let runs = runs.iter().filter(|r| r.profile == latest.profile).collect_vec();
+		let candidate = latest.revision.as_str();
+		let baseline = match baseline {
+			Some(baseline) => baseline,
+			None => runs.iter().rev()
+				.map(|r| r.revision.as_str())
+				.find(|rev| *rev != candidate)
+				.ok_or_else(|| format!("no {} runs of a revision before {} to compare against", latest.profile, candidate))?,
+		};
+
+		let samples = |revision: &str| {
+			let of = runs.iter().filter(|r| r.revision == revision).collect_vec();
+			let phases = of.iter()
+				.flat_map(|r| r.phases())
+				.map(|(day, input, phase, ns)| ((day, input.to_owned(), phase), ns as f64))
+				.into_group_map();
+			(of.len(), phases)
+		};
+		let (baseline_runs, before) = samples(baseline);
+		let (candidate_runs, after) = samples(candidate);
+		if baseline_runs == 0 {
+			return Err(format!("no {} runs of {} have been recorded", latest.profile, baseline));
+		}
+
+		let changes = after.into_iter()
+			.filter_map(|(key, after)| Some((before.get(&key)?, after, key)))
+			.map(|(before, after, (day, input, phase))| {
+				let (baseline_ns, candidate_ns) = (mean(before), mean(&after));
+				let change = (candidate_ns - baseline_ns) / baseline_ns * 100.0;
+				let verdict = match () {
+					_ if before.len() < 2 || after.len() < 2 => Verdict::TooFewRuns,
+					_ if change.abs() < threshold || !significant(before, &after) => Verdict::Unchanged,
+					_ if change > 0.0 => Verdict::Slower,
+					_ => Verdict::Faster,
+				};
+				Change { day, input, phase, baseline_ns, candidate_ns, verdict }
+			})
+			.sorted_by(|a, b| (a.day, &a.input, a.phase).cmp(&(b.day, &b.input, b.phase)))
+			.collect();
+
+		Ok(Comparison {
+			profile: latest.profile.clone(),
+			baseline: (baseline.to_owned(), baseline_runs),
+			candidate: (candidate.to_owned(), candidate_runs),
+			changes,
+		})
+	}
+
+	pub fn slowdowns(&self) -> impl Iterator<Item = &Change> {
+		self.changes.iter().filter(|c| c.verdict == Verdict::Slower)
+	}
+}
+
+fn time(ns: f64) -> String {
+	format!("{:.1?}", std::time::Duration::from_nanos(ns as u64))
+}
+
+/// Only the phases that changed are listed, with a count of each verdict at the bottom.
+impl fmt::Display for Comparison {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "Comparing {} ({} runs) against {} ({} runs), {} build",
+			self.candidate.0, self.candidate.1, self.baseline.0, self.baseline.1, self.profile)?;
+
+		let mut counts = [0; 4];
+		for c in &self.changes {
+			let (i, label) = match c.verdict {
+				Verdict::Slower => (0, "SLOWER"),
+				Verdict::Faster => (1, "faster"),
+				Verdict::Unchanged => { counts[2] += 1; continue; },
+				Verdict::TooFewRuns => { counts[3] += 1; continue; },
+			};
+			counts[i] += 1;
+			let input = match c.input.as_str() {
+				EMBEDDED => String::new(),
+				input => format!(" ({})", input),
+			};
+			let change = (c.candidate_ns - c.baseline_ns) / c.baseline_ns * 100.0;
+			writeln!(f, "{:<6} day {}{} {}: {} -> {} ({:+.1}%)",
+				label, c.day, input, c.phase, time(c.baseline_ns), time(c.candidate_ns), change)?;
+		}
+
+		write!(f, "{} slower, {} faster, {} unchanged", counts[0], counts[1], counts[2])?;
+		if counts[3] > 0 {
+			write!(f, ", {} with too few runs to tell", counts[3])?;
+		}
+		writeln!(f)
+	}
+}
+
+#[test]
+fn welch() {
+	let base = [100.0, 102.0, 98.0, 101.0, 99.0];
+	assert!(!significant(&base, &[101.0, 99.0, 103.0, 97.0, 100.0]));
+	assert!(significant(&base, &[120.0, 122.0, 118.0, 121.0, 119.0]));
+	assert!(significant(&[5.0, 5.0], &[6.0, 6.0]));
+	assert!(!significant(&[5.0, 5.0], &[5.0, 5.0]));
+}
+
+#[test]
+fn compare() {
+	let run = |revision: &str, profile: &str, part1: u64| Run {
+		timestamp: 0,
+		revision: revision.into(),
+		profile: profile.into(),
+		timings: vec![Timing { day: 5, input: EMBEDDED.into(), parse_ns: 1000, part1_ns: Some(part1), part2_ns: None }],
+	};
+	let runs = [
+		run("abc", "release", 1000),
+		run("abc", "release", 1010),
+		run("abc", "release", 990),
+		run("abc", "debug", 9000),
+		run("def", "release", 1500),
+		run("def", "release", 1490),
+		run("def", "release", 1510),
+	];
+
+	let comparison = Comparison::new(&runs, None, 5.0).unwrap();
+	assert_eq!((comparison.baseline, comparison.candidate), (("abc".into(), 3), ("def".into(), 3)));
+	assert_eq!(comparison.changes.iter().map(|c| (c.phase, c.verdict)).collect_vec(), [
+		(Phase::Parse, Verdict::Unchanged),
+		(Phase::Part1, Verdict::Slower),
+	]);
+
+	// a single run of a revision can't be told apart from noise
+	let comparison = Comparison::new(&runs[..5], None, 5.0).unwrap();
+	assert_eq!(comparison.slowdowns().count(), 0);
+	assert_eq!(comparison.changes[1].verdict, Verdict::TooFewRuns);
+
+	assert!(Comparison::new(&runs[..3], None, 5.0).is_err());
+	assert!(Comparison::new(&runs, Some("xyz"), 5.0).is_err());
+}
+
+#[test]
+fn round_trip() {
+	let path = std::env::temp_dir().join(format!("aoc2023-history-{}.jsonl", std::process::id()));
+	let _ = std::fs::remove_file(&path);
+	assert_eq!(load(&path).unwrap(), []);
+
+	let run = Run { timestamp: 1, revision: "abc".into(), profile: "debug".into(), timings: Vec::new() };
+	append(&path, &run).unwrap();
+	append(&path, &run).unwrap();
+	assert_eq!(load(&path).unwrap(), [run.clone(), run]);
+	std::fs::remove_file(&path).unwrap();
+}
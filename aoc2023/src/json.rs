@@ -0,0 +1,131 @@
+//! Machine readable output of a run, with one record for each requested part of each solved input.
+use std::time::Duration;
+use serde::Serialize;
+use crate::{Allocations, DayReport, Parts};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+	Ok,
+	Error,
+}
+
+/// A single part of a day, solved against a single input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record<'r> {
+	pub day: u8,
+	pub part: u8,
+	pub input: &'r str,
+	pub status: Status,
+	/// How the answer displays, which for a grid is over several lines
+	pub answer: Option<String>,
+	/// Time spent parsing the input, which is shared by both parts
+	pub parse_ns: u64,
+	pub solve_ns: Option<u64>,
+	pub error: Option<&'r str>,
+	/// Only present when built with the `memory` feature
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub parse_allocations: Option<Allocations>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub solve_allocations: Option<Allocations>,
+}
+
+/// Everything from a run, along with how the runner was built.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Run<'r> {
+	pub version: &'static str,
+	pub profile: &'static str,
+	pub records: Vec<Record<'r>>,
+	pub total_ns: u64,
+}
+
+pub(crate) fn nanos(d: Duration) -> u64 {
+	d.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// The build profile the runner was built with
+pub(crate) fn profile() -> &'static str {
+	if cfg!(debug_assertions) { "debug" } else { "release" }
+}
+
+impl<'r> Run<'r> {
+	pub fn new(reports: &'r [DayReport], parts: Parts) -> Run<'r> {
+		let records = reports.iter()
+			.flat_map(|r| {
+				[(1, parts.part1, &r.part1), (2, parts.part2, &r.part2)].into_iter()
+					.filter(|(_, requested, _)| *requested)
+					.map(move |(part, _, solved)| {
+						let error = r.failure(part);
+						let status = if error.is_some() { Status::Error } else { Status::Ok };
+						Record {
+							day: r.day,
+							part,
+							input: &r.input,
+							status,
+							answer: solved.as_ref().map(|p| p.answer.to_string()),
+							parse_ns: nanos(r.parse),
+							solve_ns: solved.as_ref().map(|p| nanos(p.elapsed)),
+							error,
+							parse_allocations: r.parse_allocations,
+							solve_allocations: solved.as_ref().and_then(|p| p.allocations),
+						}
+					})
+			})
+			.collect();
+
+		Run {
+			version: env!("CARGO_PKG_VERSION"),
+			profile: profile(),
+			records,
+			total_ns: nanos(reports.iter().map(DayReport::total).sum()),
+		}
+	}
+}
+
+#[test]
+fn records() {
+	use crate::PartReport;
+
+	let reports = [
+		DayReport {
+			day: 1, input: "input".into(), parse: Duration::from_nanos(5), parse_allocations: None,
+			part1: Some(PartReport { answer: 42usize.into(), elapsed: Duration::from_nanos(10), allocations: None }),
+			part2: None,
+			error: None, part1_error: None, part2_error: None,
+		},
+		DayReport {
+			day: 2, input: "alice".into(), parse: Duration::from_nanos(3), parse_allocations: None, part1: None, part2: None,
+			error: Some("bad".into()), part1_error: None, part2_error: None,
+		},
+	];
+	let run = Run::new(&reports, Parts::from(Some(1)));
+	assert_eq!(run.total_ns, 18);
+	assert_eq!(
+		serde_json::to_string(&run.records).unwrap(),
+		concat!(
+			r#"[{"day":1,"part":1,"input":"input","status":"ok","answer":"42","parse_ns":5,"solve_ns":10,"error":null},"#,
+			r#"{"day":2,"part":1,"input":"alice","status":"error","answer":null,"parse_ns":3,"solve_ns":null,"error":"bad"}]"#,
+		),
+	);
+}
+
+#[test]
+fn one_part_failed() {
+	use crate::PartReport;
+
+	// only part 2 overflowed, so part 1's record is unaffected
+	let reports = [DayReport {
+		day: 6, input: "input".into(), parse: Duration::from_nanos(5), parse_allocations: None,
+		part1: Some(PartReport { answer: 288usize.into(), elapsed: Duration::from_nanos(10), allocations: None }),
+		part2: None,
+		error: None, part1_error: None, part2_error: Some("overflow".into()),
+	}];
+	let run = Run::new(&reports, Parts::BOTH);
+	assert_eq!(
+		serde_json::to_string(&run.records).unwrap(),
+		concat!(
+			r#"[{"day":6,"part":1,"input":"input","status":"ok","answer":"288","parse_ns":5,"solve_ns":10,"error":null},"#,
+			r#"{"day":6,"part":2,"input":"input","status":"error","answer":null,"parse_ns":5,"solve_ns":null,"error":"overflow"}]"#,
+		),
+	);
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+use aocinput::{Animation, Answer, Explanation, Input};
+use days::{DayError, DynDay};
+
+pub mod select;
+pub mod report;
+pub mod verify;
+pub mod json;
+pub mod trace;
+pub mod history;
+pub mod memory;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+pub use select::{DaySelection, Parts};
+pub use report::Table;
+pub use verify::Verification;
+pub use memory::Allocations;
+
+/// The answer and wall-clock time of a single solved part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+	/// The day's answer, read back from how it displays
+	pub answer: Answer,
+	pub elapsed: Duration,
+	/// What solving the part allocated, when built with the `memory` feature
+	pub allocations: Option<Allocations>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+	pub day: u8,
+	/// The name of the input that was solved
+	pub input: String,
+	pub parse: Duration,
+	/// What parsing allocated, when built with the `memory` feature
+	pub parse_allocations: Option<Allocations>,
+	pub part1: Option<PartReport>,
+	pub part2: Option<PartReport>,
+	/// Set when the input could not be parsed, in which case no parts were run
+	pub error: Option<String>,
+	/// Set when a part failed to be solved, in which case that part has no report
+	pub part1_error: Option<String>,
+	pub part2_error: Option<String>,
+}
+
+impl DayReport {
+	pub fn total(&self) -> Duration {
+		self.parse
+			+ self.part1.as_ref().map(|p| p.elapsed).unwrap_or_default()
+			+ self.part2.as_ref().map(|p| p.elapsed).unwrap_or_default()
+	}
+	/// Why a part has no report, if it failed: either the input couldn't be parsed, or the part itself failed
+	pub fn failure(&self, part: u8) -> Option<&str> {
+		let failed = if part == 1 { &self.part1_error } else { &self.part2_error };
+		self.error.as_deref().or(failed.as_deref())
+	}
+}
+
+/// Parses the input and solves the requested parts, timing each phase.
+///
+/// Parts are allowed to mutate their parsed data (Day07 sorts it in place, for example), so each
+/// part gets a freshly parsed copy. Only the first parse is reported. A part that overflows is reported
+/// as an error, and doesn't stop the other part from being solved.
+///
+/// Each phase runs in its own span (`parse`, `part1` and `part2`) within a `day` span for the input. With the
+/// `memory` feature, what each phase allocated is counted too.
+///
+/// The day is one of [`days::registry`], which answers with an [`Answer`] whatever its own answer type is, so
+/// that every day's reports can go in the same table.
+pub fn solve(day: &dyn DynDay, input: &Input, parts: Parts) -> DayReport {
+	fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration, Option<Allocations>) {
+		let ((rtn, elapsed), allocations) = memory::measure(|| {
+			let start = Instant::now();
+			let rtn = f();
+			(rtn, start.elapsed())
+		});
+		(rtn, elapsed, allocations)
+	}
+
+	let name = input.name.clone();
+	let input = &*input.text;
+	let _span = tracing::info_span!("day", day = day.day(), input = %name).entered();
+	let (parsed, parse, parse_allocations) = timed(|| tracing::info_span!("parse").in_scope(|| day.parse(input)));
+	let mut data = match parsed {
+		Ok(data) => data,
+		Err(e) => return DayReport {
+			day: day.day(), input: name, parse, parse_allocations, part1: None, part2: None,
+			error: Some(e.to_string()), part1_error: None, part2_error: None,
+		},
+	};
+	let mut reparsed = false;
+	let report = |(answer, elapsed, allocations): (Result<Answer, DayError>, Duration, _)| match answer {
+		Ok(answer) => (Some(PartReport { answer, elapsed, allocations }), None),
+		Err(e) => (None, Some(e.to_string())),
+	};
+
+	let (part1, part1_error) = if parts.part1 {
+		let solved = timed(|| tracing::info_span!("part1").in_scope(|| data.solve(1)));
+		reparsed = true;
+		report(solved)
+	} else {
+		(None, None)
+	};
+	let (part2, part2_error) = if parts.part2 {
+		if reparsed {
+			data = tracing::info_span!("parse").in_scope(|| day.parse(input)).expect("the input parsed the first time");
+		}
+		report(timed(|| tracing::info_span!("part2").in_scope(|| data.solve(2))))
+	} else {
+		(None, None)
+	};
+
+	DayReport { day: day.day(), input: name, parse, parse_allocations, part1, part2, error: None, part1_error, part2_error }
+}
+
+/// The explanations of each requested part of a day, for a single input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explained {
+	pub day: u8,
+	pub input: String,
+	/// Each part along with its explanation, if the day has one
+	pub parts: Vec<(u8, Option<Explanation>)>,
+	/// Set when the input could not be parsed, in which case no parts were explained
+	pub error: Option<String>,
+}
+
+impl fmt::Display for Explained {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(error) = &self.error {
+			return writeln!(f, "Day {} ({}): {}", self.day, self.input, error);
+		}
+		for (part, explanation) in &self.parts {
+			writeln!(f, "Day {} part {} ({}):", self.day, part, self.input)?;
+			match explanation {
+				Some(explanation) => write!(f, "{}", explanation)?,
+				None => writeln!(f, "no explanation available")?,
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Explains the requested parts of a day for an input. Like [`solve`], each part gets a freshly parsed copy.
+pub fn explain(day: &dyn DynDay, input: &Input, parts: Parts) -> Explained {
+	let mut explained = Explained { day: day.day(), input: input.name.clone(), parts: Vec::new(), error: None };
+	for (part, _) in [(1, parts.part1), (2, parts.part2)].into_iter().filter(|(_, wanted)| *wanted) {
+		match day.parse(&input.text).and_then(|mut parsed| parsed.explain(part)) {
+			Ok(explanation) => explained.parts.push((part, explanation)),
+			Err(e) => {
+				explained.error = Some(e.to_string());
+				break;
+			},
+		}
+	}
+	explained
+}
+
+/// Animates how a day solves a part of an input, or `None` if the day has no animation for that part.
+pub fn visualize(day: &dyn DynDay, input: &Input, part: u8) -> Result<Option<Animation>, DayError> {
+	let mut parsed = day.parse(&input.text)?;
+	parsed.visualize(part)
+}
+
+/// Solves each input with its day, returning the reports in the same order.
+///
+/// With the `parallel` feature the inputs are solved concurrently, so their timings include any contention
+/// between them.
+pub fn solve_all(jobs: &[(Input, &dyn DynDay)], parts: Parts) -> Vec<DayReport> {
+	#[cfg(feature = "parallel")]
+	return solve_all_parallel(jobs, parts);
+	#[cfg(not(feature = "parallel"))]
+	return solve_all_serial(jobs, parts);
+}
+
+// only checked against the parallel path in tests when that is enabled
+#[cfg_attr(feature = "parallel", allow(dead_code))]
+fn solve_all_serial(jobs: &[(Input, &dyn DynDay)], parts: Parts) -> Vec<DayReport> {
+	jobs.iter().map(|(input, day)| solve(*day, input, parts)).collect()
+}
+
+#[cfg(feature = "parallel")]
+fn solve_all_parallel(jobs: &[(Input, &dyn DynDay)], parts: Parts) -> Vec<DayReport> {
+	use rayon::prelude::*;
+	jobs.par_iter().map(|(input, day)| solve(*day, input, parts)).collect()
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel() {
+	use aocinput::gen::Generate;
+
+	let input = |name: &str, text: String| Input { name: name.into(), text: text.into() };
+	let day = |n| days::get(n).unwrap();
+	let jobs: Vec<(Input, &dyn DynDay)> = (0..4).flat_map(|seed| [
+		(input("day05", day05::Day05.generate(seed, &day05::gen::Params { segments: 10, ..Default::default() }).input), day(5)),
+		(input("day08", day08::Day08.generate(seed, &Default::default()).input), day(8)),
+		(input("day11", day11::Day11.generate(seed, &Default::default()).input), day(11)),
+	]).collect();
+
+	let answers = |reports: Vec<DayReport>| reports.into_iter()
+		.map(|r| (r.input, r.part1.map(|p| p.answer), r.part2.map(|p| p.answer), r.error, r.part1_error, r.part2_error))
+		.collect::<Vec<_>>();
+	let parts = Parts::from(None);
+	assert_eq!(answers(solve_all_serial(&jobs, parts)), answers(solve_all_parallel(&jobs, parts)));
+}
+
+#[cfg(feature = "checked")]
+#[test]
+fn overflowed_part() {
+	// only day 6's part 2 reads the races as one, and 1000000000000000000 then 30 is too long for a usize
+	let input = Input { name: "long".into(), text: "Time: 1000000000000000000 30\nDistance: 0 200\n".into() };
+	let report = solve(days::get(6).unwrap(), &input, Parts::BOTH);
+	assert_eq!(report.part1.map(|p| p.answer), Some(Answer::Number(9 * (1_000_000_000_000_000_000 - 1))));
+	assert_eq!((report.error, report.part1_error), (None, None));
+	assert_eq!(report.part2_error.as_deref(), Some("arithmetic overflow while combining the races"));
+}
+
+#[test]
+fn located_in_file() {
+	let path = std::env::temp_dir().join(format!("aoc2023-located-{}.txt", std::process::id()));
+	std::fs::write(&path, "\r\n  \r\n\r\nGame 1: 3 blue, 4 red\r\nGame two: 1 red\r\n\r\n").unwrap();
+	let inputs = aocinput::Source::File(path.clone()).read("").unwrap();
+	std::fs::remove_file(path).unwrap();
+
+	// the blank lines at the start are kept, so the error is on the same line as in the file
+	let error = solve(days::get(2).unwrap(), &inputs[0], Parts::BOTH).error.unwrap();
+	assert!(error.starts_with("parse error: line 5, column 6"), "{}", error);
+}
+
+#[test]
+fn explained() {
+	let input = Input { name: "sample".into(), text: day02::TEST_INPUT.into() };
+	let (day02, day09) = (days::get(2).unwrap(), days::get(9).unwrap());
+	let explained = explain(day02, &input, Parts::from(Some(1)));
+	let shown = explained.to_string();
+	assert!(shown.starts_with("Day 2 part 1 (sample):\nGame 1  possible, total 1\n"), "{}", shown);
+
+	let explained = explain(day09, &Input { name: "sample".into(), text: day09::TEST_INPUT.into() }, Parts::BOTH);
+	assert_eq!(explained.parts, [(1, None), (2, None)]);
+
+	let explained = explain(day02, &Input { name: "broken".into(), text: "Game one".into() }, Parts::BOTH);
+	assert!(explained.parts.is_empty());
+	assert!(explained.error.unwrap().starts_with("parse error"));
+}
+
+#[test]
+fn visualized() {
+	let input = Input { name: "sample".into(), text: day10::TEST_INPUT_P2_SIMPLE.into() };
+	let (day09, day10) = (days::get(9).unwrap(), days::get(10).unwrap());
+	let animation = visualize(day10, &input, 2).unwrap().unwrap();
+	assert!(animation.frames().len() > 1);
+	assert_eq!(visualize(day09, &Input { name: "sample".into(), text: day09::TEST_INPUT.into() }, 1), Ok(None));
+	let error = visualize(day10, &Input { name: "broken".into(), text: "".into() }, 1).unwrap_err().to_string();
+	assert!(error.starts_with("parse error"), "{}", error);
+}
+
+/// Draws its input back out as a grid for part 1, and answers part 2 with its width and height
+#[cfg(test)]
+#[derive(Debug, Clone, Copy)]
+struct Drawn;
+
+#[cfg(test)]
+impl aoch::AoCDay for Drawn {
+	type Data<'i> = Vec<&'i str>;
+	type Answer = Answer;
+
+	fn day(&self) -> u8 { 26 }
+
+	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
+		input.lines().collect()
+	}
+	fn part1(&self, data: &mut Self::Data<'_>) -> Self::Answer {
+		Answer::grid(data.iter().copied())
+	}
+	fn part2(&self, data: &mut Self::Data<'_>) -> Self::Answer {
+		Answer::from((data[0].len(), data.len()))
+	}
+}
+
+#[cfg(test)]
+impl aocinput::TryParse for Drawn {
+	type Error = std::convert::Infallible;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		Ok(aoch::AoCDay::parse(self, input))
+	}
+}
+
+#[cfg(test)]
+impl aocinput::TrySolve for Drawn {}
+#[cfg(test)]
+impl aocinput::Explain for Drawn {}
+#[cfg(test)]
+impl aocinput::Visualize for Drawn {}
+
+#[test]
+fn mixed_answers() {
+	aocinput::answers::test_cases(Drawn, 1, &[("#.\n.#", Answer::grid(["#.", ".#"]))]);
+	aocinput::answers::test_cases(Drawn, 2, &[("#..\n.#.", Answer::from((3usize, 2usize)))]);
+
+	let drawn = solve(&days::Registered::new(Drawn, ""), &Input { name: "letters".into(), text: "#..#\n####\n#..#\n".into() }, Parts::BOTH);
+	assert_eq!(drawn.part1.as_ref().unwrap().answer, Answer::grid(["#..#", "####", "#..#"]));
+	assert_eq!(drawn.part2.as_ref().unwrap().answer.to_string(), "4,3");
+	let day09 = solve(days::get(9).unwrap(), &Input { name: "sample".into(), text: day09::TEST_INPUT.into() }, Parts::BOTH);
+	assert_eq!(day09.part1.as_ref().unwrap().answer, Answer::Number(114));
+
+	let table = [day09, drawn].into_iter().collect::<Table>().to_string();
+	assert!(table.lines().any(|l| l.starts_with("26 (letters) |") && l.contains("| 4x3 grid, see below |") && l.contains("| 4,3")), "{}", table);
+	assert!(table.ends_with("\nDay 26 part 1 (letters):\n#..#\n####\n#..#\n"), "{}", table);
+}
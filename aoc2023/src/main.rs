@@ -0,0 +1,277 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use clap::{Parser, Subcommand, ValueEnum};
+use aocinput::Source;
+use aocinput::answers::{self, Answer, Answers, EMBEDDED};
+use aocinput::Input;
+use days::DynDay;
+use aoc2023::{history, json, trace, solve_all, DayReport, DaySelection, Parts, Table, Verification};
+
+/// Runs any subset of the implemented days against their inputs, printing a table of answers and timings.
+#[derive(Debug, Parser)]
+#[command(name = "aoc2023", args_conflicts_with_subcommands = true)]
+struct Args {
+	#[command(flatten)]
+	selection: Selection,
+
+	/// How to print the answers and timings
+	#[arg(short, long, value_enum, default_value_t = Format::Table)]
+	format: Format,
+
+	/// After the table, print a step-by-step account of how each answer was reached, for the days that have one
+	#[arg(long, conflicts_with = "format")]
+	explain: bool,
+
+	/// Write how long was spent parsing and solving each input to a file, as folded stacks for a flame graph
+	#[arg(long, global = true, value_name = "PATH")]
+	flame: Option<PathBuf>,
+
+	/// The history of timings that each run is added to, and that `compare` reads
+	#[arg(long, global = true, value_name = "PATH", default_value_os_t = history::workspace_path())]
+	history: PathBuf,
+
+	/// Don't add this run's timings to the history
+	#[arg(long)]
+	no_history: bool,
+
+	/// Solve a file, or every file in a directory, then solve it again each time it's saved, showing which
+	/// answers changed. Only a single day can be selected with this.
+	#[cfg(feature = "watch")]
+	#[arg(long, value_name = "PATH", conflicts_with_all = ["input", "format", "explain"])]
+	watch: Option<PathBuf>,
+
+	#[command(subcommand)]
+	command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+	/// An aligned table for reading
+	Table,
+	/// A JSON object with a record for each part of each input, for other tools to read
+	Json,
+}
+
+#[derive(Debug, clap::Args)]
+struct Selection {
+	/// Days to run, as a comma separated list of days or inclusive ranges (`1-5,7`). Defaults to every day.
+	#[arg(default_value = "all")]
+	days: DaySelection,
+
+	/// Only run a single part
+	#[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+	part: Option<u8>,
+
+	/// Solve a file, every file in a directory, or `-` for stdin, instead of the embedded input.
+	/// Only a single day can be selected with this.
+	#[arg(short, long, value_name = "PATH")]
+	input: Option<Source>,
+}
+
+#[derive(Debug, clap::Args)]
+struct Registry {
+	/// The registry of known answers
+	#[arg(long, default_value_os_t = answers::workspace_path())]
+	answers: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+	/// Solves the selected days, checking each answer against the registry of known answers
+	Verify {
+		#[command(flatten)]
+		selection: Selection,
+		#[command(flatten)]
+		registry: Registry,
+		/// Record the answers to parts that had no known answer yet
+		#[arg(long)]
+		record: bool,
+	},
+	/// Records the answer to a part in the registry, replacing any that was already there
+	Record {
+		#[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+		day: u8,
+		#[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+		part: u8,
+		answer: String,
+		/// The name of the input the answer is for
+		#[arg(long, default_value = EMBEDDED)]
+		input: String,
+		#[command(flatten)]
+		registry: Registry,
+	},
+	/// Compares the timings of the latest revision in the history against an earlier one, failing if any
+	/// phase got significantly slower. Each revision needs a few runs for the noise between them to be known.
+	Compare {
+		/// The revision to compare against. Defaults to the last one run before the latest.
+		#[arg(long)]
+		baseline: Option<String>,
+		/// How many percent slower a phase has to be before it counts, however consistent the runs are
+		#[arg(long, default_value_t = 5.0)]
+		threshold: f64,
+	},
+	/// Plays an animation of how a day solves a part in the terminal, stepping through it frame by frame
+	#[cfg(feature = "tui")]
+	Visualize {
+		#[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+		day: u8,
+		#[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+		part: u8,
+		/// Animate a file, or `-` for stdin, instead of the embedded input
+		#[arg(short, long, value_name = "PATH")]
+		input: Option<Source>,
+	},
+}
+
+/// An input, along with the day that solves and explains it
+type Job = (Input, &'static dyn DynDay);
+
+impl Selection {
+	fn parts(&self) -> Parts {
+		Parts::from(self.part)
+	}
+	/// The selected days that are implemented
+	fn days(&self) -> Vec<&'static dyn DynDay> {
+		days::registry().iter()
+			.copied()
+			.filter(|day| self.days.contains(day.day()))
+			.collect()
+	}
+	/// Reads each of the selected days' inputs, along with the day that solves and explains them
+	fn inputs(&self) -> Result<Vec<Job>, Box<dyn Error>> {
+		let source = self.input.clone().unwrap_or_default();
+		let selected = self.days();
+		if source != Source::Embedded && selected.len() != 1 {
+			return Err(format!("--input needs a single day to be selected, not {}", selected.len()).into());
+		}
+
+		let mut inputs = Vec::new();
+		for day in selected {
+			for input in source.read(day.embedded())? {
+				inputs.push((input, day));
+			}
+		}
+		Ok(inputs)
+	}
+	/// Solves each input with its day
+	fn solve(&self, jobs: &[Job]) -> Vec<DayReport> {
+		solve_all(jobs, self.parts())
+	}
+	/// Prints how each input's day reaches its answers. The inputs are the ones already solved, as stdin can
+	/// only be read once.
+	fn explain(&self, jobs: &[Job]) {
+		for (input, day) in jobs {
+			print!("\n{}", aoc2023::explain(*day, input, self.parts()));
+		}
+	}
+}
+
+fn verify(selection: &Selection, registry: &Registry, record: bool) -> Result<bool, Box<dyn Error>> {
+	let mut answers = Answers::load(&registry.answers)?;
+	let mut verification = Verification::default();
+	for report in selection.solve(&selection.inputs()?) {
+		verification.push(&answers, &report, selection.parts());
+	}
+	print!("{}", verification);
+
+	if record {
+		let recorded = verification.record_new(&mut answers);
+		if recorded > 0 {
+			answers.save()?;
+			println!("Recorded {} new answers to {}", recorded, answers.path().display());
+		}
+	}
+	Ok(!verification.failed())
+}
+
+fn record(day: u8, part: u8, answer: &str, input: &str, registry: &Registry) -> Result<(), answers::AnswersError> {
+	let mut answers = Answers::load(&registry.answers)?;
+	if let Some(previous) = answers.get(day, input, part) {
+		println!("Replacing day {} part {} answer {} for {:?}", day, part, previous, input);
+	}
+	answers.record(day, input, part, &Answer::of(&answer));
+	answers.save()
+}
+
+#[cfg(feature = "tui")]
+fn visualize_day(day: u8, part: u8, source: Option<&Source>) -> Result<bool, Box<dyn Error>> {
+	let solver = days::get(day).ok_or_else(|| format!("day {} isn't implemented", day))?;
+	let mut inputs = source.cloned().unwrap_or_default().read(solver.embedded())?;
+	if inputs.len() != 1 {
+		return Err(format!("can only visualize a single input, not {}", inputs.len()).into());
+	}
+	let animation = aoc2023::visualize(solver, &inputs.remove(0), part)?
+		.ok_or_else(|| format!("day {} has no animation for part {}", day, part))?;
+	aoc2023::tui::run(animation)?;
+	Ok(true)
+}
+
+#[cfg(feature = "watch")]
+fn watch(selection: &Selection, path: &Path) -> Result<bool, Box<dyn Error>> {
+	let selected = selection.days();
+	let [day] = selected[..] else {
+		return Err(format!("--watch needs a single day to be selected, not {}", selected.len()).into());
+	};
+	aoc2023::watch::watch(path, day, selection.parts())?;
+	Ok(true)
+}
+
+fn compare(path: &Path, baseline: Option<&str>, threshold: f64) -> Result<bool, Box<dyn Error>> {
+	let runs = history::load(path)?;
+	let comparison = history::Comparison::new(&runs, baseline, threshold)?;
+	print!("{}", comparison);
+	let slowed = comparison.slowdowns().next().is_some();
+	Ok(!slowed)
+}
+
+fn main() -> ExitCode {
+	let args = Args::parse();
+	let flame = args.flame.as_ref().map(|_| trace::Flame::default());
+	// diagnostics from the days are logged to stderr, filtered by RUST_LOG
+	trace::init(flame.clone());
+
+	let result = match &args.command {
+		#[cfg(feature = "watch")]
+		None if args.watch.is_some() => watch(&args.selection, args.watch.as_deref().unwrap()),
+		None => {
+			args.selection.inputs().and_then(|jobs| {
+				let reports = args.selection.solve(&jobs);
+				match args.format {
+					Format::Table => print!("{}", reports.iter().cloned().collect::<Table>()),
+					Format::Json => {
+						let run = json::Run::new(&reports, args.selection.parts());
+						println!("{}", serde_json::to_string_pretty(&run).unwrap());
+					},
+				}
+				if !args.no_history {
+					history::append(&args.history, &history::Run::new(&reports, history::revision()))?;
+				}
+				if args.explain {
+					args.selection.explain(&jobs);
+				}
+				Ok(true)
+			})
+		},
+		Some(Command::Verify { selection, registry, record }) => verify(selection, registry, *record),
+		Some(Command::Record { day, part, answer, input, registry }) => record(*day, *part, answer, input, registry).map(|()| true).map_err(Into::into),
+		Some(Command::Compare { baseline, threshold }) => compare(&args.history, baseline.as_deref(), *threshold),
+		#[cfg(feature = "tui")]
+		Some(Command::Visualize { day, part, input }) => visualize_day(*day, *part, input.as_ref()),
+	};
+
+	if let (Some(path), Some(flame)) = (&args.flame, flame) {
+		if let Err(e) = std::fs::write(path, flame.folded()) {
+			eprintln!("unable to write {}: {}", path.display(), e);
+		}
+	}
+
+	match result {
+		Ok(true) => ExitCode::SUCCESS,
+		Ok(false) => ExitCode::FAILURE,
+		Err(e) => {
+			eprintln!("{}", e);
+			ExitCode::FAILURE
+		},
+	}
+}
@@ -0,0 +1,139 @@
+//! Allocation accounting for each phase of a day. With the `memory` feature, a counting allocator wrapping the
+//! system one is installed as the global allocator, and [`measure`] reports what was allocated while it ran.
+//! Without the feature nothing is counted and every measurement is `None`.
+//!
+//! The counts are global, so with the `parallel` feature a phase's numbers include whatever the days being
+//! solved alongside it allocated at the same time.
+use std::fmt;
+use serde::Serialize;
+
+/// What was allocated over a phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Allocations {
+	/// How many allocations were made, counting each reallocation as another one
+	pub count: u64,
+	/// The total size of every allocation
+	pub bytes: u64,
+	/// The most that was allocated at once, beyond what was already allocated when the phase started
+	pub peak_bytes: u64,
+}
+
+fn bytes(n: u64) -> String {
+	const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+	if n < 1024 {
+		return format!("{} B", n);
+	}
+	let mut size = n as f64 / 1024.0;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < UNITS.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+	format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Formatted to fit in a table cell, like `12 allocs, 4.1 KiB, 2.0 KiB peak`.
+impl fmt::Display for Allocations {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} allocs, {}, {} peak", self.count, bytes(self.bytes), bytes(self.peak_bytes))
+	}
+}
+
+#[cfg(feature = "memory")]
+mod counting {
+	use std::alloc::{GlobalAlloc, Layout, System};
+	use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+	pub static COUNT: AtomicU64 = AtomicU64::new(0);
+	pub static BYTES: AtomicU64 = AtomicU64::new(0);
+	pub static LIVE: AtomicU64 = AtomicU64::new(0);
+	pub static PEAK: AtomicU64 = AtomicU64::new(0);
+
+	/// The system allocator, counting everything that goes through it
+	pub struct Counting;
+
+	fn allocated(size: usize) {
+		COUNT.fetch_add(1, Relaxed);
+		BYTES.fetch_add(size as u64, Relaxed);
+		let live = LIVE.fetch_add(size as u64, Relaxed) + size as u64;
+		PEAK.fetch_max(live, Relaxed);
+	}
+
+	unsafe impl GlobalAlloc for Counting {
+		unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+			let ptr = System.alloc(layout);
+			if !ptr.is_null() {
+				allocated(layout.size());
+			}
+			ptr
+		}
+		unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+			let ptr = System.alloc_zeroed(layout);
+			if !ptr.is_null() {
+				allocated(layout.size());
+			}
+			ptr
+		}
+		unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+			System.dealloc(ptr, layout);
+			LIVE.fetch_sub(layout.size() as u64, Relaxed);
+		}
+		unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+			let new = System.realloc(ptr, layout, new_size);
+			if !new.is_null() {
+				LIVE.fetch_sub(layout.size() as u64, Relaxed);
+				allocated(new_size);
+			}
+			new
+		}
+	}
+
+	#[global_allocator]
+	static GLOBAL: Counting = Counting;
+}
+
+/// Runs `f`, along with what it allocated if the counting allocator is installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+	#[cfg(not(feature = "memory"))]
+	return (f(), None);
+
+	#[cfg(feature = "memory")]
+	{
+		use std::sync::atomic::Ordering::Relaxed;
+		use counting::{BYTES, COUNT, LIVE, PEAK};
+
+		let (count, bytes, live) = (COUNT.load(Relaxed), BYTES.load(Relaxed), LIVE.load(Relaxed));
+		PEAK.store(live, Relaxed);
+		let rtn = f();
+		let allocations = Allocations {
+			count: COUNT.load(Relaxed) - count,
+			bytes: BYTES.load(Relaxed) - bytes,
+			peak_bytes: PEAK.load(Relaxed).saturating_sub(live),
+		};
+		(rtn, Some(allocations))
+	}
+}
+
+#[test]
+fn display() {
+	let allocations = Allocations { count: 12, bytes: 4200, peak_bytes: 512 };
+	assert_eq!(allocations.to_string(), "12 allocs, 4.1 KiB, 512 B peak");
+	assert_eq!(bytes(3 * 1024 * 1024 + 1), "3.0 MiB");
+}
+
+#[cfg(feature = "memory")]
+#[test]
+fn counted() {
+	let (v, allocations) = measure(|| {
+		let mut v = Vec::<u64>::with_capacity(16);
+		v.extend(0..16);
+		drop(Vec::<u8>::with_capacity(4096));
+		v
+	});
+	assert_eq!(v.len(), 16);
+	let allocations = allocations.unwrap();
+	// other tests allocate on their own threads at the same time, so these can only be lower bounds, and the
+	// peak can't be relied on at all
+	assert!(allocations.count >= 2);
+	assert!(allocations.bytes >= 16*8 + 4096);
+}
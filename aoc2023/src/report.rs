@@ -0,0 +1,153 @@
+use std::fmt;
+use std::time::Duration;
+use itertools::Itertools;
+use aocinput::Answer;
+use aocinput::answers::EMBEDDED;
+use crate::{Allocations, DayReport, PartReport};
+
+/// Renders a set of day reports as an aligned plain-text table, with a total row at the bottom. Answers that are
+/// grids don't fit in a cell, so they are drawn in full after the table.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+	rows: Vec<DayReport>,
+}
+
+impl Table {
+	pub fn push(&mut self, report: DayReport) {
+		self.rows.push(report);
+	}
+	pub fn total(&self) -> Duration {
+		self.rows.iter().map(DayReport::total).sum()
+	}
+}
+
+impl FromIterator<DayReport> for Table {
+	fn from_iter<T: IntoIterator<Item = DayReport>>(iter: T) -> Self {
+		Table { rows: iter.into_iter().collect() }
+	}
+}
+
+fn time(d: Duration) -> String {
+	format!("{:.1?}", d)
+}
+
+fn part_cells(report: &DayReport, part: u8) -> [String; 3] {
+	let solved = if part == 1 { &report.part1 } else { &report.part2 };
+	match (solved, report.failure(part)) {
+		(Some(PartReport { answer, elapsed, allocations }), _) => [cell(answer), time(*elapsed), memory(allocations)],
+		(None, Some(_)) => ["failed".into(), "-".into(), "-".into()],
+		(None, None) => ["-".into(), "-".into(), "-".into()],
+	}
+}
+
+fn cell(answer: &Answer) -> String {
+	match answer {
+		Answer::Grid(_) => format!("{}, see below", brief(answer)),
+		answer => brief(answer),
+	}
+}
+
+/// An answer on a single line, where a grid is only described by its size
+pub(crate) fn brief(answer: &Answer) -> String {
+	match answer {
+		Answer::Grid(rows) => {
+			let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+			format!("{}x{} grid", width, rows.len())
+		},
+		answer => answer.to_string(),
+	}
+}
+
+fn memory(allocations: &Option<Allocations>) -> String {
+	allocations.map_or_else(|| "-".into(), |a| a.to_string())
+}
+
+/// A column of the table, and whether it is left aligned
+type Column = (&'static str, bool);
+
+impl fmt::Display for Table {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		// answers and allocations are left aligned, everything else is numeric
+		const COLUMNS: [Column; 9] = [
+			("Day", false), ("Parse", false), ("Memory", true),
+			("Part 1", true), ("Time", false), ("Memory", true),
+			("Part 2", true), ("Time", false), ("Memory", true),
+		];
+		// the memory columns are only shown when allocations were counted
+		let measured = self.rows.iter().any(|r| r.parse_allocations.is_some());
+		let visible = |i: &usize| measured || COLUMNS[*i].0 != "Memory";
+		let shown = |row: [String; 9]| -> Vec<String> {
+			row.into_iter().enumerate()
+				.filter(|(i, _)| visible(i))
+				.map(|(_, cell)| cell)
+				.collect()
+		};
+		let columns = COLUMNS.iter().enumerate()
+			.filter(|(i, _)| visible(i))
+			.map(|(_, column)| *column)
+			.collect_vec();
+
+		let mut cells: Vec<Vec<String>> = self.rows.iter()
+			.map(|r| {
+				let [a1, t1, m1] = part_cells(r, 1);
+				let [a2, t2, m2] = part_cells(r, 2);
+				// inputs other than the embedded one are named after the day
+				let day = match r.input.as_str() {
+					EMBEDDED => r.day.to_string(),
+					input => format!("{} ({})", r.day, input),
+				};
+				shown([day, time(r.parse), memory(&r.parse_allocations), a1, t1, m1, a2, t2, m2])
+			})
+			.collect();
+		cells.push(shown(["Total".into(), time(self.total()), "".into(), "".into(), "".into(), "".into(), "".into(), "".into(), "".into()]));
+
+		let widths = (0..columns.len())
+			.map(|i| {
+				cells.iter()
+					.map(|row| row[i].chars().count())
+					.chain([columns[i].0.len()])
+					.max().unwrap()
+			})
+			.collect_vec();
+
+		let line = |f: &mut fmt::Formatter<'_>, row: &[String]| -> fmt::Result {
+			let formatted = row.iter()
+				.zip(&widths)
+				.zip(&columns)
+				.map(|((s, &w), (_, left))| match left {
+					true => format!("{:<w$}", s),
+					false => format!("{:>w$}", s),
+				})
+				.join(" | ");
+			writeln!(f, "{}", formatted.trim_end())
+		};
+
+		line(f, &columns.iter().map(|c| c.0.to_owned()).collect_vec())?;
+		writeln!(f, "{}", widths.iter().map(|w| "-".repeat(*w)).join("-+-"))?;
+		let (total, days) = cells.split_last().unwrap();
+		for row in days {
+			line(f, row)?;
+		}
+		writeln!(f, "{}", widths.iter().map(|w| "-".repeat(*w)).join("-+-"))?;
+		line(f, total)?;
+
+		for r in &self.rows {
+			for (part, solved) in [(1, &r.part1), (2, &r.part2)] {
+				if let Some(PartReport { answer: grid @ Answer::Grid(_), .. }) = solved {
+					writeln!(f, "\nDay {} part {} ({}):\n{}", r.day, part, r.input, grid)?;
+				}
+			}
+		}
+		for r in &self.rows {
+			if let Some(error) = &r.error {
+				writeln!(f, "\nDay {} failed on {}: {}", r.day, r.input, error)?;
+			}
+			for (part, error) in [(1, &r.part1_error), (2, &r.part2_error)] {
+				if let Some(error) = error {
+					writeln!(f, "\nDay {} part {} failed on {}: {}", r.day, part, r.input, error)?;
+				}
+			}
+		}
+		Ok(())
+	}
+}
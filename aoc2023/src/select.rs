@@ -0,0 +1,92 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SelectionError {
+	#[error("invalid day number {0:?}")]
+	InvalidDay(String),
+	#[error("day {0} is out of range (1-25)")]
+	OutOfRange(u8),
+	#[error("day range {0}-{1} is backwards")]
+	Backwards(u8, u8),
+}
+
+/// A set of days to run, parsed from a comma separated list of days and inclusive ranges (`1-5,7`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DaySelection(BTreeSet<u8>);
+
+impl DaySelection {
+	/// Every day of the calendar, filtered down by whatever is actually implemented.
+	pub fn all() -> DaySelection {
+		DaySelection((1..=25).collect())
+	}
+	pub fn contains(&self, day: u8) -> bool {
+		self.0.contains(&day)
+	}
+	pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+		self.0.iter().copied()
+	}
+}
+
+impl FromStr for DaySelection {
+	type Err = SelectionError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		fn day(s: &str) -> Result<u8, SelectionError> {
+			let n: u8 = s.trim().parse().map_err(|_| SelectionError::InvalidDay(s.to_owned()))?;
+			match n {
+				1..=25 => Ok(n),
+				_ => Err(SelectionError::OutOfRange(n)),
+			}
+		}
+
+		if s.trim() == "all" { return Ok(DaySelection::all()); }
+
+		let mut days = BTreeSet::new();
+		for chunk in s.split(',').filter_map(aoch::parsing::trimmed) {
+			match chunk.split_once('-') {
+				None => { days.insert(day(chunk)?); },
+				Some((start, end)) => {
+					let (start, end) = (day(start)?, day(end)?);
+					if start > end {
+						return Err(SelectionError::Backwards(start, end));
+					}
+					days.extend(start..=end);
+				}
+			}
+		}
+		Ok(DaySelection(days))
+	}
+}
+
+/// Which parts of a day should be solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parts {
+	pub part1: bool,
+	pub part2: bool,
+}
+
+impl Parts {
+	pub const BOTH: Parts = Parts { part1: true, part2: true };
+}
+
+impl From<Option<u8>> for Parts {
+	fn from(value: Option<u8>) -> Self {
+		match value {
+			Some(1) => Parts { part1: true, part2: false },
+			Some(2) => Parts { part1: false, part2: true },
+			_ => Parts::BOTH,
+		}
+	}
+}
+
+#[test]
+fn selection() {
+	let parse = |s: &str| s.parse::<DaySelection>().map(|d| d.iter().collect::<Vec<_>>());
+	assert_eq!(parse("1-5,7"), Ok(vec![1, 2, 3, 4, 5, 7]));
+	assert_eq!(parse("3"), Ok(vec![3]));
+	assert_eq!(parse("7, 2,2-3"), Ok(vec![2, 3, 7]));
+	assert_eq!(parse("all"), Ok((1..=25).collect()));
+	assert_eq!(parse("5-2"), Err(SelectionError::Backwards(5, 2)));
+	assert_eq!(parse("0"), Err(SelectionError::OutOfRange(0)));
+	assert_eq!(parse("x"), Err(SelectionError::InvalidDay("x".into())));
+}
@@ -0,0 +1,595 @@
+//! A solve service for other tools, with the `server` feature. `POST /day/{n}/part/{p}` with a puzzle input as
+//! the body is answered with the JSON [`Record`](crate::json::Record) of solving it: the answer, how long parsing
+//! and solving took, and any parse error.
+//!
+//! It is only meant to listen on localhost, and speaks just enough HTTP/1.1 for that: each connection carries a
+//! single request, and is closed once it's answered. Bodies over [`Config::max_body`] bytes are refused, as are
+//! requests that take longer than [`Config::read_timeout`] to arrive, and a request that isn't solved within
+//! [`Config::timeout`] is answered with an error. The solve carries on in the background until it finishes
+//! though, as a thread can't be stopped from outside, so at most [`Config::max_solves`] are run at once, counting
+//! those that timed out, and requests beyond that are turned away until one finishes.
+use std::borrow::Cow;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use aocinput::Input;
+use days::DynDay;
+use crate::json::{self, Status};
+use crate::{DayReport, Parts};
+
+/// Limits on each request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+	/// The largest body accepted, in bytes
+	pub max_body: usize,
+	/// How long a request can take to arrive, headers and body together
+	pub read_timeout: Duration,
+	/// How long a request can take to be parsed and solved
+	pub timeout: Duration,
+	/// How many inputs can be solved at once
+	pub max_solves: usize,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		// the real inputs are all well under 100 KiB
+		let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+		Config { max_body: 1024 * 1024, read_timeout: Duration::from_secs(10), timeout: Duration::from_secs(30), max_solves: cores }
+	}
+}
+
+/// Why a request wasn't solved
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RequestError {
+	#[error("not found, expected `POST /day/{{n}}/part/{{1 or 2}}`")]
+	NotFound,
+	#[error("only POST is allowed")]
+	MethodNotAllowed,
+	#[error("day {0} isn't implemented")]
+	NoSuchDay(u8),
+	#[error("malformed request: {0}")]
+	Malformed(String),
+	#[error("body is over the limit of {0} bytes")]
+	TooLarge(usize),
+	#[error("unable to read the request: {0}")]
+	Read(String),
+	#[error("request not received within {0:?}")]
+	ReadTimeout(Duration),
+	#[error("body isn't valid UTF-8")]
+	NotUtf8,
+	#[error("not solved within {0:?}")]
+	Timeout(Duration),
+	#[error("the solver panicked")]
+	Panicked,
+	#[error("already solving {0} inputs, try again later")]
+	Busy(usize),
+}
+
+impl RequestError {
+	/// The HTTP status the request is answered with
+	pub fn status(&self) -> u16 {
+		match self {
+			RequestError::NotFound | RequestError::NoSuchDay(_) => 404,
+			RequestError::MethodNotAllowed => 405,
+			RequestError::TooLarge(_) => 413,
+			RequestError::Malformed(_) | RequestError::Read(_) | RequestError::NotUtf8 => 400,
+			RequestError::ReadTimeout(_) => 408,
+			RequestError::Timeout(_) => 504,
+			RequestError::Panicked => 500,
+			RequestError::Busy(_) => 503,
+		}
+	}
+
+	/// Why reading the request failed
+	fn reading(e: io::Error, config: Config) -> RequestError {
+		match e.kind() {
+			// a read timeout is reported as either, depending on the platform
+			io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => RequestError::ReadTimeout(config.read_timeout),
+			io::ErrorKind::InvalidData => RequestError::Malformed(e.to_string()),
+			_ => RequestError::Read(e.to_string()),
+		}
+	}
+}
+
+/// The reason phrase sent along with a status
+fn reason(status: u16) -> &'static str {
+	match status {
+		200 => "OK",
+		400 => "Bad Request",
+		404 => "Not Found",
+		405 => "Method Not Allowed",
+		408 => "Request Timeout",
+		413 => "Content Too Large",
+		422 => "Unprocessable Content",
+		500 => "Internal Server Error",
+		503 => "Service Unavailable",
+		504 => "Gateway Timeout",
+		_ => "",
+	}
+}
+
+/// What a request that wasn't solved is answered with
+#[derive(Debug, Serialize)]
+struct Failure {
+	status: Status,
+	error: String,
+}
+
+/// The day and part from a `/day/{n}/part/{p}` path, ignoring any query
+fn route(url: &str) -> Option<(u8, u8)> {
+	let path = url.split_once('?').map_or(url, |(path, _)| path);
+	let (day, part) = path.strip_prefix("/day/")?.split_once("/part/")?;
+	let part = part.parse().ok().filter(|p| matches!(p, 1 | 2))?;
+	Some((day.parse().ok()?, part))
+}
+
+/// A reader that fails once its deadline has passed. Each read is also bounded by the connection's read timeout,
+/// so a request that trickles in is given up on soon after the deadline, as well as one that stops arriving.
+struct Deadline<R> {
+	inner: R,
+	deadline: Instant,
+}
+
+impl<R: Read> Read for Deadline<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if Instant::now() >= self.deadline {
+			return Err(io::ErrorKind::TimedOut.into());
+		}
+		self.inner.read(buf)
+	}
+}
+
+/// The longest request line, header or chunk size line accepted
+const MAX_LINE: u64 = 8 * 1024;
+
+fn malformed(what: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, what)
+}
+
+/// Reads a line of a request, without its line ending.
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+	let mut line = Vec::new();
+	reader.by_ref().take(MAX_LINE).read_until(b'\n', &mut line)?;
+	if line.pop() != Some(b'\n') {
+		return Err(malformed("line too long, or cut short"));
+	}
+	if line.last() == Some(&b'\r') {
+		line.pop();
+	}
+	String::from_utf8(line).map_err(|_| malformed("line isn't valid UTF-8"))
+}
+
+/// A request's line and headers
+struct Head {
+	method: String,
+	url: String,
+	/// The body's length, or `None` when it's chunked
+	length: Option<usize>,
+	/// Whether the client waits to be told to go ahead before sending the body
+	continues: bool,
+}
+
+impl Head {
+	fn read(reader: &mut impl BufRead) -> io::Result<Head> {
+		let line = read_line(reader)?;
+		let mut parts = line.split(' ');
+		let (Some(method), Some(url), Some(version), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+			return Err(malformed("expected a request line"));
+		};
+		if !version.starts_with("HTTP/1.") {
+			return Err(malformed("only HTTP/1 is supported"));
+		}
+
+		let mut head = Head { method: method.to_owned(), url: url.to_owned(), length: Some(0), continues: false };
+		let mut chunked = false;
+		loop {
+			let line = read_line(reader)?;
+			if line.is_empty() {
+				break;
+			}
+			let (name, value) = line.split_once(':').ok_or_else(|| malformed("expected a header"))?;
+			let value = value.trim();
+			match name.to_ascii_lowercase().as_str() {
+				"content-length" => head.length = Some(value.parse().map_err(|_| malformed("invalid Content-Length"))?),
+				"transfer-encoding" if value.eq_ignore_ascii_case("chunked") => chunked = true,
+				"transfer-encoding" => return Err(malformed("only a chunked Transfer-Encoding is supported")),
+				"expect" => head.continues = value.eq_ignore_ascii_case("100-continue"),
+				_ => {},
+			}
+		}
+		if chunked {
+			head.length = None;
+		}
+		Ok(head)
+	}
+}
+
+/// Reads a request's body. A chunked one doesn't say how long it is up front, so reading it stops once it is past
+/// `max` bytes.
+fn read_body(reader: &mut impl BufRead, length: Option<usize>, max: usize) -> io::Result<Vec<u8>> {
+	if let Some(length) = length {
+		let mut body = vec![0; length];
+		reader.read_exact(&mut body)?;
+		return Ok(body);
+	}
+	let mut body = Vec::new();
+	loop {
+		let line = read_line(reader)?;
+		let size = line.split(';').next()
+			.and_then(|size| usize::from_str_radix(size.trim(), 16).ok())
+			.ok_or_else(|| malformed("invalid chunk size"))?;
+		if size == 0 {
+			// skip any trailers, up to the blank line that ends the request
+			while !read_line(reader)?.is_empty() {}
+			return Ok(body);
+		}
+		let start = body.len();
+		body.resize(start + size.min(max + 1 - start), 0);
+		reader.read_exact(&mut body[start..])?;
+		if body.len() > max {
+			return Ok(body);
+		}
+		if !read_line(reader)?.is_empty() {
+			return Err(malformed("chunk longer than its size"));
+		}
+	}
+}
+
+/// A place among the solves running at once, given back when dropped
+struct Permit(Arc<AtomicUsize>);
+
+impl Permit {
+	/// Takes a place if fewer than `max` solves are running.
+	fn acquire(solving: &Arc<AtomicUsize>, max: usize) -> Option<Permit> {
+		solving.fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max).then_some(n + 1)).ok()?;
+		Some(Permit(solving.clone()))
+	}
+}
+
+impl Drop for Permit {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::AcqRel);
+	}
+}
+
+/// Reads a request's input and solves it on another thread, giving up on it after the timeout.
+fn solve(head: &Head, reader: &mut impl BufRead, mut stream: &TcpStream, config: Config, days: &[&'static dyn DynDay], solving: &Arc<AtomicUsize>) -> Result<(DayReport, u8), RequestError> {
+	let (day, part) = route(&head.url).ok_or(RequestError::NotFound)?;
+	if head.method != "POST" {
+		return Err(RequestError::MethodNotAllowed);
+	}
+	let solver = days.iter()
+		.copied()
+		.find(|d| d.day() == day)
+		.ok_or(RequestError::NoSuchDay(day))?;
+
+	if head.length.is_some_and(|len| len > config.max_body) {
+		return Err(RequestError::TooLarge(config.max_body));
+	}
+	if head.continues {
+		stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").map_err(|e| RequestError::Read(e.to_string()))?;
+	}
+	let body = read_body(reader, head.length, config.max_body).map_err(|e| RequestError::reading(e, config))?;
+	if body.len() > config.max_body {
+		return Err(RequestError::TooLarge(config.max_body));
+	}
+	let text = String::from_utf8(body).map_err(|_| RequestError::NotUtf8)?;
+
+	let input = Input::new("request", Cow::Owned(text));
+	let permit = Permit::acquire(solving, config.max_solves).ok_or(RequestError::Busy(config.max_solves))?;
+	let (sender, receiver) = mpsc::channel();
+	std::thread::spawn(move || {
+		// held until the solve finishes, even if the request has already timed out or the solver panics
+		let _permit = permit;
+		// nothing is listening any more if the request timed out
+		let _ = sender.send(crate::solve(solver, &input, Parts::from(Some(part))));
+	});
+	match receiver.recv_timeout(config.timeout) {
+		Ok(report) => Ok((report, part)),
+		Err(RecvTimeoutError::Timeout) => Err(RequestError::Timeout(config.timeout)),
+		Err(RecvTimeoutError::Disconnected) => Err(RequestError::Panicked),
+	}
+}
+
+fn respond(stream: TcpStream, config: Config, days: &[&'static dyn DynDay], solving: &Arc<AtomicUsize>) {
+	// each read is bounded by the timeout, and all of them together by the deadline
+	if let Err(e) = stream.set_read_timeout(Some(config.read_timeout)) {
+		tracing::warn!("unable to set a read timeout: {}", e);
+		return;
+	}
+	let mut reader = BufReader::new(Deadline { inner: &stream, deadline: Instant::now() + config.read_timeout });
+	let head = Head::read(&mut reader).map_err(|e| RequestError::reading(e, config));
+	let solved = head.as_ref()
+		.map_err(Clone::clone)
+		.and_then(|head| solve(head, &mut reader, &stream, config, days, solving));
+	let (status, body) = match solved {
+		Ok((report, part)) => {
+			let reports = [report];
+			let record = json::Run::new(&reports, Parts::from(Some(part))).records.remove(0);
+			// the input was read, but couldn't be solved
+			let status = if record.status == Status::Ok { 200 } else { 422 };
+			(status, serde_json::to_string(&record))
+		},
+		Err(e) => (e.status(), serde_json::to_string(&Failure { status: Status::Error, error: e.to_string() })),
+	};
+	let (method, url) = head.as_ref().map_or(("-", "-"), |head| (head.method.as_str(), head.url.as_str()));
+	tracing::info!(method, url, status, "answered");
+
+	let body = body.expect("records serialize");
+	let response = format!(
+		"HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+		status, reason(status), body.len(), body,
+	);
+	if let Err(e) = (&stream).write_all(response.as_bytes()).and_then(|()| stream.shutdown(Shutdown::Write)) {
+		tracing::warn!("unable to respond: {}", e);
+		return;
+	}
+	// closing with some of the request still unread would reset the connection, which can lose the response
+	// before the client reads it, so whatever is left is read first, for a while
+	reader.get_mut().deadline = Instant::now() + config.read_timeout;
+	let _ = io::copy(&mut reader.take(config.max_body as u64), &mut io::sink());
+}
+
+pub struct Server {
+	listener: TcpListener,
+	config: Config,
+	days: Vec<&'static dyn DynDay>,
+	/// How many solves are running, including those whose request timed out
+	solving: Arc<AtomicUsize>,
+	stopping: AtomicBool,
+}
+
+impl Server {
+	/// Listens on `addr`, solving requests for each of `days`.
+	pub fn bind(addr: impl ToSocketAddrs, config: Config, days: &[&'static dyn DynDay]) -> io::Result<Server> {
+		let listener = TcpListener::bind(addr)?;
+		Ok(Server { listener, config, days: days.to_vec(), solving: Arc::default(), stopping: AtomicBool::new(false) })
+	}
+
+	/// The address being listened on, which has the actual port when bound to port 0
+	pub fn addr(&self) -> SocketAddr {
+		self.listener.local_addr().expect("listening on a TCP address")
+	}
+
+	/// Answers requests until [`Server::stop`] is called. Each connection gets its own thread, so that a slow one
+	/// doesn't hold up the rest.
+	pub fn run(&self) {
+		for stream in self.listener.incoming() {
+			if self.stopping.load(Ordering::Acquire) {
+				break;
+			}
+			match stream {
+				Ok(stream) => {
+					let (config, days, solving) = (self.config, self.days.clone(), self.solving.clone());
+					std::thread::spawn(move || respond(stream, config, &days, &solving));
+				},
+				Err(e) => tracing::warn!("unable to accept a connection: {}", e),
+			}
+		}
+	}
+
+	/// Makes [`Server::run`] return.
+	pub fn stop(&self) {
+		self.stopping.store(true, Ordering::Release);
+		// wakes up the accept that run is waiting in, so that it sees the flag
+		let _ = TcpStream::connect(self.addr());
+	}
+}
+
+/// Starts a server on a free port of 127.0.0.1, returning its address.
+#[cfg(test)]
+fn serve(config: Config, days: &[&'static dyn DynDay]) -> SocketAddr {
+	let server = Server::bind("127.0.0.1:0", config, days).unwrap();
+	let addr = server.addr();
+	std::thread::spawn(move || server.run());
+	addr
+}
+
+/// Reads the status and JSON body of a response.
+#[cfg(test)]
+fn response(mut stream: TcpStream) -> (u16, serde_json::Value) {
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	let (head, body) = response.split_once("\r\n\r\n").unwrap();
+	assert!(head.to_ascii_lowercase().contains("content-type: application/json"), "{}", head);
+	(head.split(' ').nth(1).unwrap().parse().unwrap(), serde_json::from_str(body).unwrap())
+}
+
+/// Sends a request and reads the response.
+#[cfg(test)]
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, serde_json::Value) {
+	let mut stream = TcpStream::connect(addr).unwrap();
+	write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}", method, path, body.len(), body).unwrap();
+	response(stream)
+}
+
+/// Sends a request with its body in chunks of `size` bytes, and reads the response.
+#[cfg(test)]
+fn chunked(addr: SocketAddr, path: &str, body: &str, size: usize) -> (u16, serde_json::Value) {
+	let mut stream = TcpStream::connect(addr).unwrap();
+	write!(stream, "POST {} HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n", path).unwrap();
+	for chunk in body.as_bytes().chunks(size) {
+		write!(stream, "{:x}\r\n", chunk.len()).unwrap();
+		stream.write_all(chunk).unwrap();
+		stream.write_all(b"\r\n").unwrap();
+	}
+	stream.write_all(b"0\r\n\r\n").unwrap();
+	response(stream)
+}
+
+#[test]
+fn routes() {
+	assert_eq!(route("/day/5/part/2"), Some((5, 2)));
+	assert_eq!(route("/day/12/part/1?verbose"), Some((12, 1)));
+	for url in ["/", "/day/5", "/day/5/part/3", "/day/x/part/1", "/day/5/part/1/more", "/days/5/part/1"] {
+		assert_eq!(route(url), None, "{}", url);
+	}
+}
+
+#[test]
+fn solved() {
+	let addr = serve(Config::default(), days::registry());
+
+	let (status, record) = request(addr, "POST", "/day/9/part/1", day09::TEST_INPUT);
+	assert_eq!(status, 200);
+	assert_eq!((&record["day"], &record["part"], &record["status"], &record["answer"]), (&9.into(), &1.into(), &"ok".into(), &"114".into()));
+	assert!(record["parse_ns"].is_u64() && record["solve_ns"].is_u64(), "{}", record);
+	assert!(record["error"].is_null());
+
+	// inputs are normalised like any other
+	let (status, record) = request(addr, "POST", "/day/9/part/2", &day09::TEST_INPUT.replace('\n', "\r\n"));
+	assert_eq!((status, &record["answer"]), (200, &"2".into()));
+
+	let (status, record) = request(addr, "POST", "/day/2/part/1", "Game one");
+	assert_eq!((status, &record["status"], &record["answer"]), (422, &"error".into(), &serde_json::Value::Null));
+	assert!(record["error"].as_str().unwrap().starts_with("parse error"), "{}", record);
+}
+
+#[test]
+fn refused() {
+	let addr = serve(Config { max_body: 64, ..Config::default() }, days::registry());
+
+	let cases = [
+		("POST", "/day/9/part/3", "1 2 3", 404, "not found"),
+		("POST", "/day/25/part/1", "1 2 3", 404, "day 25 isn't implemented"),
+		("GET", "/day/9/part/1", "", 405, "only POST is allowed"),
+		("POST", "/day/9/part/1", &"1 2 3\n".repeat(11), 413, "body is over the limit of 64 bytes"),
+	];
+	for (method, path, body, status, error) in cases {
+		let (got, failure) = request(addr, method, path, body);
+		assert_eq!(got, status, "{} {}", method, path);
+		assert_eq!(failure["status"], "error");
+		assert!(failure["error"].as_str().unwrap().starts_with(error), "{}", failure);
+	}
+	// right at the limit is fine
+	let (status, _) = request(addr, "POST", "/day/9/part/1", &format!("{}1 2 3", "1 2 3\n".repeat(9)));
+	assert_eq!(status, 200);
+
+	// a chunked body is cut off once it's past the limit
+	let (status, failure) = chunked(addr, "/day/9/part/1", &"1 2 3\n".repeat(11), 16);
+	assert_eq!((status, &failure["error"]), (413, &"body is over the limit of 64 bytes".into()));
+
+	let mut stream = TcpStream::connect(addr).unwrap();
+	stream.write_all(b"POST /day/9/part/1\r\n\r\n").unwrap();
+	let (status, failure) = response(stream);
+	assert_eq!((status, &failure["error"]), (400, &"malformed request: expected a request line".into()));
+}
+
+#[test]
+fn chunks() {
+	let addr = serve(Config::default(), days::registry());
+	for size in [1, 7, 1000] {
+		let (status, record) = chunked(addr, "/day/9/part/1", day09::TEST_INPUT, size);
+		assert_eq!((status, &record["answer"]), (200, &"114".into()), "{}", size);
+	}
+}
+
+#[test]
+fn slow_body() {
+	let config = Config { read_timeout: Duration::from_millis(300), ..Config::default() };
+	let addr = serve(config, days::registry());
+	let body = "1 2 3\n".repeat(400);
+	let head = format!("POST /day/9/part/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n", body.len());
+
+	// half the body, then nothing
+	let mut stream = TcpStream::connect(addr).unwrap();
+	stream.write_all(head.as_bytes()).unwrap();
+	stream.write_all(&body.as_bytes()[..body.len() / 2]).unwrap();
+	let started = Instant::now();
+	let (status, failure) = response(stream);
+	assert_eq!((status, &failure["error"]), (408, &"request not received within 300ms".into()));
+	assert!(started.elapsed() < Duration::from_secs(5), "{:?}", started.elapsed());
+
+	// a byte at a time, each soon enough after the last, but never finishing within the deadline
+	let mut stream = TcpStream::connect(addr).unwrap();
+	stream.write_all(head.as_bytes()).unwrap();
+	let mut writer = stream.try_clone().unwrap();
+	std::thread::spawn(move || {
+		for byte in body.as_bytes() {
+			std::thread::sleep(Duration::from_millis(50));
+			if writer.write_all(std::slice::from_ref(byte)).is_err() {
+				break;
+			}
+		}
+	});
+	let started = Instant::now();
+	let (status, failure) = response(stream);
+	assert_eq!((status, &failure["error"]), (408, &"request not received within 300ms".into()));
+	assert!(started.elapsed() < Duration::from_secs(5), "{:?}", started.elapsed());
+}
+
+/// Day 9, but taking a couple of seconds to parse
+#[cfg(test)]
+struct Slow;
+
+#[cfg(test)]
+impl DynDay for Slow {
+	fn day(&self) -> u8 {
+		9
+	}
+	fn embedded(&self) -> &'static str {
+		""
+	}
+	fn parse<'a>(&'a self, input: &'a str) -> Result<Box<dyn days::Parsed + 'a>, days::DayError> {
+		std::thread::sleep(Duration::from_secs(2));
+		days::get(9).unwrap().parse(input)
+	}
+}
+
+/// A day 10 that panics on any input
+#[cfg(test)]
+struct Panicking;
+
+#[cfg(test)]
+impl DynDay for Panicking {
+	fn day(&self) -> u8 {
+		10
+	}
+	fn embedded(&self) -> &'static str {
+		""
+	}
+	fn parse<'a>(&'a self, _: &'a str) -> Result<Box<dyn days::Parsed + 'a>, days::DayError> {
+		panic!("deliberately")
+	}
+}
+
+#[test]
+fn timed_out() {
+	// room for the panicking solve alongside the slow one, which carries on after timing out
+	let config = Config { timeout: Duration::from_millis(100), max_solves: 2, ..Config::default() };
+	let addr = serve(config, &[&Slow, &Panicking]);
+	let (status, failure) = request(addr, "POST", "/day/9/part/1", day09::TEST_INPUT);
+	assert_eq!((status, &failure["error"]), (504, &"not solved within 100ms".into()));
+	let (status, failure) = request(addr, "POST", "/day/10/part/1", "");
+	assert_eq!((status, &failure["error"]), (500, &"the solver panicked".into()));
+}
+
+#[test]
+fn busy() {
+	let config = Config { timeout: Duration::from_millis(100), max_solves: 1, ..Config::default() };
+	let addr = serve(config, &[&Slow, days::get(2).unwrap()]);
+	let (status, _) = request(addr, "POST", "/day/9/part/1", day09::TEST_INPUT);
+	assert_eq!(status, 504);
+	// the timed out solve still holds the only place
+	let (status, failure) = request(addr, "POST", "/day/2/part/1", day02::TEST_INPUT);
+	assert_eq!((status, &failure["error"]), (503, &"already solving 1 inputs, try again later".into()));
+	// until it finishes
+	std::thread::sleep(Duration::from_millis(2500));
+	let (status, record) = request(addr, "POST", "/day/2/part/1", day02::TEST_INPUT);
+	assert_eq!((status, &record["answer"]), (200, &"8".into()));
+}
+
+#[test]
+fn stopped() {
+	let server = Arc::new(Server::bind("127.0.0.1:0", Config::default(), &[]).unwrap());
+	let running = std::thread::spawn({
+		let server = server.clone();
+		move || server.run()
+	});
+	server.stop();
+	running.join().unwrap();
+}
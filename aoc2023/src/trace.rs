@@ -0,0 +1,134 @@
+//! Tracing for the runner: diagnostics from the days are written to stderr, and the time spent in each span can be
+//! summarised as the folded stacks that flame graph tools (`inferno-flamegraph`, `flamegraph.pl`) take.
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::filter::{filter_fn, EnvFilter, LevelFilter};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// How long a span has been entered for, kept in the span's extensions
+struct Timing {
+	label: String,
+	entered: Option<Instant>,
+	busy: Duration,
+	/// Time spent in child spans, which is already counted by their own stacks
+	children: Duration,
+}
+
+/// A span's name along with its fields, such as `day{day=5 input=input}`
+struct Label(String);
+
+impl Visit for Label {
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.record_debug(field, &format_args!("{}", value));
+	}
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+		let sep = if self.0.ends_with('{') { "" } else { " " };
+		let _ = write!(self.0, "{}{}={:?}", sep, field.name(), value);
+	}
+}
+
+/// Collects how long was spent in each stack of spans over a run.
+#[derive(Debug, Clone, Default)]
+pub struct Flame {
+	stacks: Arc<Mutex<BTreeMap<String, Duration>>>,
+}
+
+impl Flame {
+	/// The time spent in each stack, not counting time spent in its child spans, as `outer;inner nanoseconds` lines.
+	pub fn folded(&self) -> String {
+		self.stacks.lock().unwrap().iter()
+			.map(|(stack, time)| format!("{} {}\n", stack, time.as_nanos()))
+			.collect()
+	}
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Flame {
+	fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+		let span = ctx.span(id).expect("new spans are registered");
+		let mut label = Label(format!("{}{{", attrs.metadata().name()));
+		attrs.record(&mut label);
+		let label = match label.0.strip_suffix('{') {
+			Some(name) => name.to_owned(),
+			None => label.0 + "}",
+		};
+		span.extensions_mut().insert(Timing { label, entered: None, busy: Duration::ZERO, children: Duration::ZERO });
+	}
+
+	fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(id) else { return };
+		let mut ext = span.extensions_mut();
+		if let Some(timing) = ext.get_mut::<Timing>() {
+			timing.entered = Some(Instant::now());
+		}
+	}
+
+	fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(id) else { return };
+		let mut ext = span.extensions_mut();
+		if let Some(timing) = ext.get_mut::<Timing>() {
+			if let Some(entered) = timing.entered.take() {
+				timing.busy += entered.elapsed();
+			}
+		}
+	}
+
+	fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+		let Some(span) = ctx.span(&id) else { return };
+		let (busy, own) = match span.extensions().get::<Timing>() {
+			Some(t) => (t.busy, t.busy.saturating_sub(t.children)),
+			None => return,
+		};
+		if let Some(parent) = span.parent() {
+			if let Some(timing) = parent.extensions_mut().get_mut::<Timing>() {
+				timing.children += busy;
+			}
+		}
+
+		let stack = span.scope().from_root()
+			.map(|s| s.extensions().get::<Timing>().map_or_else(|| s.name().to_owned(), |t| t.label.clone()))
+			.collect::<Vec<_>>()
+			.join(";");
+		*self.stacks.lock().unwrap().entry(stack).or_default() += own;
+	}
+}
+
+/// Installs the global subscriber, which logs events to stderr as filtered by `RUST_LOG` (warnings, such as about
+/// input that had to be normalised, by default), and times every span into `flame` if one is given.
+pub fn init(flame: Option<Flame>) {
+	tracing_subscriber::registry()
+		.with(tracing_subscriber::fmt::layer()
+			.with_writer(std::io::stderr)
+			.with_filter(EnvFilter::builder().with_default_directive(LevelFilter::WARN.into()).from_env_lossy()))
+		.with(flame.map(|f| f.with_filter(filter_fn(|meta| meta.is_span()))))
+		.init();
+}
+
+#[test]
+fn flame() {
+	use aocinput::Input;
+	use crate::{solve, Parts};
+
+	let flame = Flame::default();
+	let subscriber = tracing_subscriber::registry().with(flame.clone());
+	tracing::subscriber::with_default(subscriber, || {
+		let input = Input { name: "sample".into(), text: day09::TEST_INPUT.into() };
+		solve(days::get(9).unwrap(), &input, Parts::BOTH);
+	});
+
+	let stacks = flame.folded().lines()
+		.map(|l| l.rsplit_once(' ').unwrap().0.to_owned())
+		.collect::<Vec<_>>();
+	assert_eq!(stacks, [
+		"day{day=9 input=sample}",
+		"day{day=9 input=sample};parse",
+		"day{day=9 input=sample};part1",
+		"day{day=9 input=sample};part2",
+	]);
+}
@@ -0,0 +1,342 @@
+//! Plays a day's [`Animation`] in the terminal, with the `tui` feature.
+//!
+//! Space plays and pauses, the left and right arrows (or `,` and `.`) step a frame at a time, and Home and End
+//! jump to the first and last frames. Large canvases start zoomed out to fit the screen: `+` and `-` zoom in and
+//! out, `f` fits the canvas again, and `h`, `j`, `k` and `l` pan around it. `[` and `]` slow down and speed up
+//! playback, and `q` quits.
+use std::io;
+use std::time::{Duration, Instant};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+use ratatui::{Frame, Terminal};
+use aocinput::Animation;
+use aocinput::visual::{Canvas, Cell, Tone};
+
+const KEYS: &str = "space play/pause  ←/→ step  home/end  +/- zoom  f fit  hjkl pan  [/] speed  q quit";
+
+const FASTEST: Duration = Duration::from_millis(1);
+const SLOWEST: Duration = Duration::from_secs(2);
+
+/// The colours that groups are told apart by, reused once there are more groups than colours
+const GROUPS: [Color; 6] = [Color::Cyan, Color::Magenta, Color::Green, Color::Blue, Color::LightYellow, Color::LightRed];
+
+fn style(cell: &Cell) -> Style {
+	let style = match cell.tone {
+		Tone::Plain => Style::default(),
+		Tone::Dim => Style::default().fg(Color::DarkGray),
+		Tone::Path => Style::default().fg(Color::Yellow),
+		Tone::Highlight => Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD),
+		Tone::Group(gid) => Style::default().fg(GROUPS[gid as usize % GROUPS.len()]),
+		Tone::Inside => Style::default().fg(Color::Black).bg(Color::Green),
+		Tone::Outside => Style::default().fg(Color::DarkGray).bg(Color::Red),
+	};
+	match cell.focused {
+		true => style.add_modifier(Modifier::REVERSED),
+		false => style,
+	}
+}
+
+/// A window onto a canvas, as returned by [`Canvas::view`]
+struct View(Vec<Vec<Option<Cell>>>);
+
+impl Widget for View {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		for (y, row) in self.0.iter().enumerate().take(area.height as usize) {
+			for (x, cell) in row.iter().enumerate().take(area.width as usize) {
+				if let Some(cell) = cell {
+					buf.get_mut(area.x + x as u16, area.y + y as u16)
+						.set_char(cell.glyph)
+						.set_style(style(cell));
+				}
+			}
+		}
+	}
+}
+
+/// Where an animation is up to, and how it is being looked at
+pub struct Viewer {
+	animation: Animation,
+	canvas: Canvas,
+	playing: bool,
+	/// How many cells of the canvas each cell on screen stands for, across and down
+	zoom: usize,
+	/// The cell of the canvas at the top left of the screen
+	origin: (usize, usize),
+	/// How long each frame is shown for while playing
+	delay: Duration,
+	/// How many cells of the screen the canvas was last drawn across and down, or `None` before it was drawn
+	screen: Option<(usize, usize)>,
+}
+
+impl Viewer {
+	pub fn new(animation: Animation) -> Viewer {
+		let canvas = animation.canvas(0);
+		Viewer { animation, canvas, playing: false, zoom: 1, origin: (0, 0), delay: Duration::from_millis(50), screen: None }
+	}
+
+	/// The frame being shown, which is `None` if the animation has no frames
+	pub fn frame(&self) -> Option<usize> {
+		self.canvas.frame()
+	}
+	pub fn playing(&self) -> bool {
+		self.playing
+	}
+	pub fn zoom(&self) -> usize {
+		self.zoom
+	}
+	pub fn delay(&self) -> Duration {
+		self.delay
+	}
+
+	fn last(&self) -> usize {
+		self.animation.frames().len().saturating_sub(1)
+	}
+
+	fn seek(&mut self, frame: usize) {
+		self.animation.seek(&mut self.canvas, frame);
+	}
+
+	/// Zooms out until the whole canvas fits on the screen it was last drawn on.
+	fn fit(&mut self) {
+		let (width, height) = self.screen.unwrap_or((1, 1));
+		let (width, height) = (width.max(1), height.max(1));
+		self.zoom = 1;
+		while self.animation.width().div_ceil(self.zoom) > width || self.animation.height().div_ceil(self.zoom) > height {
+			self.zoom *= 2;
+		}
+		self.origin = (0, 0);
+	}
+
+	/// Moves the view by a quarter of the screen in a direction, without going past the canvas.
+	fn pan(&mut self, (dx, dy): (isize, isize)) {
+		let (width, height) = self.screen.unwrap_or((4, 4));
+		let step = |screen: usize, by: isize| (screen / 4).max(1) as isize * by * self.zoom as isize;
+		let (x, y) = (self.origin.0.saturating_add_signed(step(width, dx)), self.origin.1.saturating_add_signed(step(height, dy)));
+		self.origin = (x.min(self.animation.width().saturating_sub(1)), y.min(self.animation.height().saturating_sub(1)));
+	}
+
+	/// Moves to the next frame if the animation is playing, pausing once it reaches the end.
+	pub fn tick(&mut self) {
+		if !self.playing {
+			return;
+		}
+		match self.frame() {
+			Some(frame) if frame < self.last() => self.seek(frame + 1),
+			_ => self.playing = false,
+		}
+	}
+
+	/// Handles a key press, returning whether to keep going.
+	pub fn key(&mut self, code: KeyCode) -> bool {
+		let frame = self.frame().unwrap_or(0);
+		match code {
+			KeyCode::Char('q') | KeyCode::Esc => return false,
+			KeyCode::Char(' ') => {
+				// playing from the end starts over
+				if !self.playing && frame >= self.last() {
+					self.seek(0);
+				}
+				self.playing = !self.playing;
+			},
+			KeyCode::Right | KeyCode::Char('.') => {
+				self.playing = false;
+				self.seek(frame + 1);
+			},
+			KeyCode::Left | KeyCode::Char(',') => {
+				self.playing = false;
+				self.seek(frame.saturating_sub(1));
+			},
+			KeyCode::Home => self.seek(0),
+			KeyCode::End => self.seek(self.last()),
+			KeyCode::Char('+') | KeyCode::Char('=') => self.zoom = (self.zoom / 2).max(1),
+			KeyCode::Char('-') => {
+				// no further out than the whole canvas in a single cell
+				let furthest = self.animation.width().max(self.animation.height()).next_power_of_two();
+				self.zoom = (self.zoom * 2).min(furthest.max(1));
+			},
+			KeyCode::Char('f') => self.fit(),
+			KeyCode::Char('h') => self.pan((-1, 0)),
+			KeyCode::Char('j') => self.pan((0, 1)),
+			KeyCode::Char('k') => self.pan((0, -1)),
+			KeyCode::Char('l') => self.pan((1, 0)),
+			KeyCode::Char('[') => self.delay = (self.delay * 2).min(SLOWEST),
+			KeyCode::Char(']') => self.delay = (self.delay / 2).max(FASTEST),
+			_ => {},
+		}
+		true
+	}
+
+	/// Draws the canvas in a box filling the screen, with the frame's caption and the controls below it.
+	pub fn draw(&mut self, f: &mut Frame) {
+		let areas = Layout::new(Direction::Vertical, [Constraint::Min(3), Constraint::Length(3)]).split(f.size());
+		let block = Block::default().borders(Borders::ALL).title(format!(" {} ", self.animation.title));
+		let inner = block.inner(areas[0]);
+		f.render_widget(block, areas[0]);
+
+		let first = self.screen.is_none();
+		self.screen = Some((inner.width as usize, inner.height as usize));
+		if first {
+			self.fit();
+		}
+		let view = self.canvas.view(self.origin, self.zoom, inner.width as usize, inner.height as usize);
+		f.render_widget(View(view), inner);
+
+		let caption = self.frame().map_or("", |frame| self.animation.frames()[frame].caption.as_str());
+		let status = format!(
+			"frame {}/{}, {}, zoom 1:{}, {} ms a frame",
+			self.frame().map_or(0, |frame| frame + 1),
+			self.animation.frames().len(),
+			if self.playing { "playing" } else { "paused" },
+			self.zoom,
+			self.delay.as_millis(),
+		);
+		let lines = vec![
+			Line::from(status),
+			Line::from(caption),
+			Line::styled(KEYS, Style::default().fg(Color::DarkGray)),
+		];
+		f.render_widget(Paragraph::new(lines), areas[1]);
+	}
+}
+
+/// Shows the viewer until it's quit, stepping through the frames while it plays.
+fn play<B: Backend>(terminal: &mut Terminal<B>, mut viewer: Viewer) -> io::Result<()> {
+	let mut next = Instant::now() + viewer.delay;
+	loop {
+		terminal.draw(|f| viewer.draw(f))?;
+		if event::poll(next.saturating_duration_since(Instant::now()))? {
+			if let Event::Key(key) = event::read()? {
+				let interrupted = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+				if key.kind == KeyEventKind::Press && (interrupted || !viewer.key(key.code)) {
+					return Ok(());
+				}
+			}
+		} else {
+			viewer.tick();
+			next = Instant::now() + viewer.delay;
+		}
+	}
+}
+
+/// Plays an animation on the terminal's alternate screen until it's quit, then puts the terminal back.
+pub fn run(animation: Animation) -> io::Result<()> {
+	terminal::enable_raw_mode()?;
+	execute!(io::stdout(), EnterAlternateScreen)?;
+	let result = Terminal::new(CrosstermBackend::new(io::stdout()))
+		.and_then(|mut terminal| play(&mut terminal, Viewer::new(animation)));
+	// the terminal is put back however playing ended
+	terminal::disable_raw_mode()?;
+	execute!(io::stdout(), LeaveAlternateScreen)?;
+	result
+}
+
+#[cfg(test)]
+fn rows(terminal: &Terminal<ratatui::backend::TestBackend>) -> Vec<String> {
+	let buffer = terminal.backend().buffer();
+	buffer.content().chunks(buffer.area.width as usize)
+		.map(|row| row.iter().map(|c| c.symbol()).collect())
+		.collect()
+}
+
+#[test]
+fn controls() {
+	let mut animation = Animation::new("test", ["abc", "def"]);
+	for x in 0..3 {
+		animation.push(format!("frame {}", x)).tone((x, 0), Tone::Path);
+	}
+	let mut viewer = Viewer::new(animation);
+	assert_eq!(viewer.frame(), Some(0));
+	for _ in 0..4 {
+		assert!(viewer.key(KeyCode::Right));
+	}
+	assert_eq!(viewer.frame(), Some(2));
+	viewer.key(KeyCode::Char(','));
+	assert_eq!(viewer.frame(), Some(1));
+	viewer.key(KeyCode::Home);
+	assert_eq!(viewer.frame(), Some(0));
+
+	// playing stops at the end, and playing again starts over
+	viewer.key(KeyCode::Char(' '));
+	assert!(viewer.playing());
+	viewer.tick();
+	viewer.tick();
+	assert_eq!((viewer.frame(), viewer.playing()), (Some(2), true));
+	viewer.tick();
+	assert_eq!((viewer.frame(), viewer.playing()), (Some(2), false));
+	viewer.key(KeyCode::Char(' '));
+	assert_eq!((viewer.frame(), viewer.playing()), (Some(0), true));
+	viewer.key(KeyCode::Char('.'));
+	assert_eq!((viewer.frame(), viewer.playing()), (Some(1), false));
+
+	viewer.key(KeyCode::Char(']'));
+	assert_eq!(viewer.delay(), Duration::from_millis(25));
+	for _ in 0..20 {
+		viewer.key(KeyCode::Char('['));
+	}
+	assert_eq!(viewer.delay(), SLOWEST);
+
+	assert!(!viewer.key(KeyCode::Char('q')));
+	assert!(!viewer.key(KeyCode::Esc));
+}
+
+#[test]
+fn drawn() {
+	let mut animation = Animation::new("small", ["abc", "def"]);
+	animation.push("first").tone((0, 0), Tone::Path).focus((2, 1));
+	animation.push("second");
+	let mut viewer = Viewer::new(animation);
+	let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(30, 8)).unwrap();
+	terminal.draw(|f| viewer.draw(f)).unwrap();
+
+	let shown = rows(&terminal);
+	assert!(shown[0].starts_with("┌ small "), "{:?}", shown);
+	assert!(shown[1].starts_with("│abc "), "{:?}", shown);
+	assert!(shown[2].starts_with("│def "), "{:?}", shown);
+	assert_eq!(shown[5].trim_end(), "frame 1/2, paused, zoom 1:1, 5");
+	assert_eq!(shown[6].trim_end(), "first");
+
+	let buffer = terminal.backend().buffer();
+	assert_eq!(buffer.get(1, 1).fg, Color::Yellow);
+	assert!(buffer.get(3, 2).modifier.contains(Modifier::REVERSED));
+}
+
+#[test]
+fn zoomed() {
+	let mut animation = Animation::blank("large", 100, 10);
+	animation.push("only").set((99, 9), '#', Tone::Highlight);
+	let mut viewer = Viewer::new(animation);
+	let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(20, 8)).unwrap();
+	terminal.draw(|f| viewer.draw(f)).unwrap();
+
+	// 18 by 3 cells inside the box, which the canvas fits in at 1:8
+	assert_eq!(viewer.zoom(), 8);
+	assert_eq!(rows(&terminal)[2].chars().nth(1 + 99 / 8), Some('#'));
+	viewer.key(KeyCode::Char('+'));
+	assert_eq!(viewer.zoom(), 4);
+	for _ in 0..10 {
+		viewer.key(KeyCode::Char('-'));
+	}
+	assert_eq!(viewer.zoom(), 128);
+	viewer.key(KeyCode::Char('f'));
+	assert_eq!(viewer.zoom(), 8);
+
+	// panning stops at the edge of the canvas
+	viewer.key(KeyCode::Char('+'));
+	for _ in 0..100 {
+		viewer.key(KeyCode::Char('l'));
+	}
+	terminal.draw(|f| viewer.draw(f)).unwrap();
+	assert_eq!(rows(&terminal)[1].chars().nth(1), Some(' '));
+	for _ in 0..100 {
+		viewer.key(KeyCode::Char('j'));
+	}
+	terminal.draw(|f| viewer.draw(f)).unwrap();
+	assert_eq!(rows(&terminal)[1].chars().nth(1), Some('#'));
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+use itertools::Itertools;
+use aocinput::answers::{Answer, Answers, Verdict, EMBEDDED};
+use crate::{DayReport, Parts};
+
+/// A solved (or unsolved) part, checked against the answer registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+	pub day: u8,
+	pub input: String,
+	pub part: u8,
+	pub answer: Option<Answer>,
+	pub verdict: Verdict,
+}
+
+/// The checks from a verify run, displayed one part per line with a summary at the bottom.
+#[derive(Debug, Clone, Default)]
+pub struct Verification {
+	checks: Vec<Check>,
+}
+
+impl Verification {
+	/// Checks the requested parts of a day's report against the answers recorded for its input.
+	pub fn push(&mut self, answers: &Answers, report: &DayReport, parts: Parts) {
+		let solved = [(1, parts.part1, &report.part1), (2, parts.part2, &report.part2)];
+		for (part, requested, solved) in solved {
+			if !requested { continue; }
+			let answer = solved.as_ref().map(|p| p.answer.clone());
+			let shown = answer.as_ref().map(Answer::to_string);
+			if let Some(verdict) = answers.check(report.day, &report.input, part, shown.as_deref()) {
+				self.checks.push(Check { day: report.day, input: report.input.clone(), part, answer, verdict });
+			}
+		}
+	}
+
+	pub fn checks(&self) -> &[Check] {
+		&self.checks
+	}
+
+	/// Whether any part was wrong, or failed to produce an answer that was recorded
+	pub fn failed(&self) -> bool {
+		self.checks.iter().any(|c| matches!(c.verdict, Verdict::Wrong { .. } | Verdict::Missing { .. }))
+	}
+
+	/// Records the answers to every newly solved part, returning how many there were.
+	pub fn record_new(&self, answers: &mut Answers) -> usize {
+		let new = self.checks.iter()
+			.filter(|c| c.verdict == Verdict::New)
+			.collect_vec();
+		for check in &new {
+			answers.record(check.day, &check.input, check.part, check.answer.as_ref().unwrap());
+		}
+		new.len()
+	}
+}
+
+impl fmt::Display for Verification {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut counts = [0; 4];
+		for Check { day, input, part, answer, verdict } in &self.checks {
+			match input.as_str() {
+				EMBEDDED => write!(f, "Day {:>2} part {}: ", day, part)?,
+				input => write!(f, "Day {:>2} part {} ({}): ", day, part, input)?,
+			}
+			let answer = answer.as_ref().map(Answer::to_string).unwrap_or_default();
+			match verdict {
+				Verdict::Correct => { counts[0] += 1; writeln!(f, "ok       {}", answer)? },
+				Verdict::Wrong { expected } => { counts[1] += 1; writeln!(f, "WRONG    {}, expected {}", answer, expected)? },
+				Verdict::New => { counts[2] += 1; writeln!(f, "new      {} (not recorded)", answer)? },
+				Verdict::Missing { expected } => { counts[3] += 1; writeln!(f, "missing  expected {}, but there was no answer", expected)? },
+			}
+		}
+		let [correct, wrong, new, missing] = counts;
+		writeln!(f, "\n{} correct, {} wrong, {} new, {} missing", correct, wrong, new, missing)
+	}
+}
+
+#[test]
+fn verification() {
+	use std::time::Duration;
+	use crate::PartReport;
+
+	let mut answers = Answers::load("missing.toml").unwrap();
+	answers.record(1, EMBEDDED, 1, &Answer::from(10));
+	answers.record(1, EMBEDDED, 2, &Answer::from(20));
+	answers.record(2, EMBEDDED, 1, &Answer::from(30));
+
+	let part = |answer: &str| Some(PartReport { answer: answer.into(), elapsed: Duration::ZERO, allocations: None });
+	let mut verification = Verification::default();
+	verification.push(&answers, &DayReport { day: 1, input: EMBEDDED.into(), parse: Duration::ZERO, parse_allocations: None, part1: part("10"), part2: part("21"), error: None, part1_error: None, part2_error: None }, Parts::BOTH);
+	verification.push(&answers, &DayReport { day: 2, input: EMBEDDED.into(), parse: Duration::ZERO, parse_allocations: None, part1: None, part2: None, error: Some("bad".into()), part1_error: None, part2_error: None }, Parts::BOTH);
+	verification.push(&answers, &DayReport { day: 3, input: "alice".into(), parse: Duration::ZERO, parse_allocations: None, part1: part("40"), part2: None, error: None, part1_error: None, part2_error: None }, Parts::from(Some(1)));
+
+	let verdicts: Vec<_> = verification.checks().iter().map(|c| (c.day, c.part, c.verdict.clone())).collect();
+	assert_eq!(verdicts, [
+		(1, 1, Verdict::Correct),
+		(1, 2, Verdict::Wrong { expected: "20".into() }),
+		(2, 1, Verdict::Missing { expected: "30".into() }),
+		(3, 1, Verdict::New),
+	]);
+	assert!(verification.failed());
+
+	assert_eq!(verification.record_new(&mut answers), 1);
+	assert_eq!(answers.get(3, "alice", 1), Some("40".into()));
+}
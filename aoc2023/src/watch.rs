@@ -0,0 +1,209 @@
+//! Solving an input again whenever it is saved, with the `watch` feature, for working on hand-made inputs. Each
+//! run's answers are compared against the run before, listing any that changed.
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use aocinput::{Answer, Source};
+use days::DynDay;
+use crate::{report, DayReport, Parts, Table};
+
+/// How long to wait after a change for the rest of the same save, as editors often write a file in several steps
+const SETTLE: Duration = Duration::from_millis(100);
+
+/// A part whose answer is different from the previous run's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+	pub input: String,
+	pub part: u8,
+	/// The previous run's answer, which is `None` if the part failed or the input wasn't there
+	pub before: Option<Answer>,
+	pub after: Option<Answer>,
+}
+
+/// Grids are too big to show whole, so a change between two of them is followed by a line for each row that
+/// differs, numbered from 1.
+impl fmt::Display for Diff {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let shown = |answer: &Option<Answer>| answer.as_ref().map_or_else(|| "no answer".into(), report::brief);
+		write!(f, "{} part {}: {} -> {}", self.input, self.part, shown(&self.before), shown(&self.after))?;
+		if let (Some(Answer::Grid(before)), Some(Answer::Grid(after))) = (&self.before, &self.after) {
+			fn row(rows: &[String], i: usize) -> &str {
+				rows.get(i).map_or("no row", String::as_str)
+			}
+			for i in (0..before.len().max(after.len())).filter(|&i| before.get(i) != after.get(i)) {
+				write!(f, "\n  row {}: {} -> {}", i + 1, row(before, i), row(after, i))?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Every answer that differs between two runs, matching up the inputs by name.
+pub fn diff(previous: &[DayReport], current: &[DayReport]) -> Vec<Diff> {
+	let answers = |reports: &[DayReport]| reports.iter()
+		.flat_map(|r| [(1, &r.part1), (2, &r.part2)].map(|(part, solved)| {
+			((r.input.clone(), part), solved.as_ref().map(|p| p.answer.clone()))
+		}))
+		.collect::<BTreeMap<_, _>>();
+	let (before, after) = (answers(previous), answers(current));
+	let parts = before.keys().chain(after.keys()).cloned().collect::<BTreeSet<_>>();
+	parts.into_iter()
+		.map(|key| Diff {
+			before: before.get(&key).cloned().flatten(),
+			after: after.get(&key).cloned().flatten(),
+			input: key.0,
+			part: key.1,
+		})
+		.filter(|d| d.before != d.after)
+		.collect()
+}
+
+/// Notices when an input file, or any file in a directory of inputs, changes
+pub struct Changes {
+	// events only arrive for as long as the watcher is kept
+	_watcher: RecommendedWatcher,
+	events: Receiver<notify::Result<Event>>,
+	/// The file to look out for within the watched directory, or `None` for any of them
+	file: Option<PathBuf>,
+}
+
+impl Changes {
+	pub fn new(path: &Path) -> notify::Result<Changes> {
+		let path = path.canonicalize().map_err(notify::Error::io)?;
+		// editors often save by writing a new file and renaming it over the old one, which a watch on the old
+		// file wouldn't see, so the directory it is in is watched instead
+		let (dir, file) = match path.is_dir() {
+			true => (path, None),
+			false => (path.parent().expect("a file is in a directory").to_owned(), Some(path)),
+		};
+		let (sender, events) = mpsc::channel();
+		let mut watcher = notify::recommended_watcher(sender)?;
+		watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+		Ok(Changes { _watcher: watcher, events, file })
+	}
+
+	fn relevant(&self, event: Event) -> bool {
+		let changed = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_));
+		changed && self.file.as_ref().is_none_or(|file| event.paths.contains(file))
+	}
+
+	/// Waits for the input to change and for its save to settle, returning false if `timeout` passes first.
+	pub fn wait(&self, timeout: Option<Duration>) -> notify::Result<bool> {
+		let deadline = timeout.map(|t| Instant::now() + t);
+		loop {
+			let received = match deadline {
+				Some(deadline) => self.events.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+				None => self.events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+			};
+			let event = match received {
+				Ok(event) => event?,
+				Err(RecvTimeoutError::Timeout) => return Ok(false),
+				Err(RecvTimeoutError::Disconnected) => unreachable!("the watcher keeps the sender"),
+			};
+			if self.relevant(event) {
+				break;
+			}
+		}
+		while self.events.recv_timeout(SETTLE).is_ok() {}
+		Ok(true)
+	}
+}
+
+/// Solves the input at `path`, then again each time it changes, printing the answers and how they changed since
+/// the previous run. Only returns if watching fails.
+pub fn watch(path: &Path, day: &dyn DynDay, parts: Parts) -> Result<(), Box<dyn Error>> {
+	let changes = Changes::new(path)?;
+	let source = match path.is_dir() {
+		true => Source::Dir(path.to_owned()),
+		false => Source::File(path.to_owned()),
+	};
+	let solve = || match source.read("") {
+		Ok(inputs) => Some(inputs.iter().map(|input| crate::solve(day, input, parts)).collect::<Vec<_>>()),
+		Err(e) => {
+			eprintln!("{}", e);
+			None
+		},
+	};
+
+	let mut previous = solve().unwrap_or_default();
+	print!("{}", previous.iter().cloned().collect::<Table>());
+	eprintln!("\nWatching {} for changes", path.display());
+	loop {
+		changes.wait(None)?;
+		// the input can be missing partway through a save, in which case the rest of the save will bring it back
+		let Some(current) = solve() else { continue };
+		println!("\n{} changed:", path.display());
+		print!("{}", current.iter().cloned().collect::<Table>());
+		let diffs = diff(&previous, &current);
+		if diffs.is_empty() {
+			println!("Answers unchanged");
+		}
+		for d in &diffs {
+			println!("Changed {}", d);
+		}
+		previous = current;
+	}
+}
+
+#[test]
+fn diffs() {
+	use crate::PartReport;
+
+	let report = |input: &str, part1: Option<Answer>, part2: Option<Answer>| {
+		let solved = |answer: Option<Answer>| answer.map(|answer| PartReport { answer, elapsed: Duration::ZERO, allocations: None });
+		DayReport {
+			day: 3, input: input.into(), parse: Duration::ZERO, parse_allocations: None,
+			part1: solved(part1), part2: solved(part2), error: None, part1_error: None, part2_error: None,
+		}
+	};
+	let previous = [
+		report("edges", Some(4361usize.into()), Some(467835usize.into())),
+		report("gone", Some(1usize.into()), None),
+		report("grid", Some(Answer::grid(["#..", "...", "..#"])), Some(Answer::grid(["#."]))),
+	];
+	let current = [
+		report("edges", Some(4361usize.into()), Some(16345usize.into())),
+		report("new", None, Some(Answer::grid(["#.", ".#"]))),
+		// the same size, but not the same grid
+		report("grid", Some(Answer::grid(["#..", ".#.", "..."])), Some(Answer::grid(["#.", ".#"]))),
+	];
+	assert_eq!(diff(&previous, &previous), []);
+	let shown = diff(&previous, &current).iter().map(Diff::to_string).collect::<Vec<_>>();
+	assert_eq!(shown, [
+		"edges part 2: 467835 -> 16345",
+		"gone part 1: 1 -> no answer",
+		"grid part 1: 3x3 grid -> 3x3 grid\n  row 2: ... -> .#.\n  row 3: ..# -> ...",
+		"grid part 2: 2x1 grid -> 2x2 grid\n  row 2: no row -> .#",
+		"new part 2: no answer -> 2x2 grid",
+	]);
+}
+
+#[test]
+fn changes() {
+	let dir = std::env::temp_dir().join(format!("aoc2023-watch-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let (input, other) = (dir.join("edges.txt"), dir.join("other.txt"));
+	std::fs::write(&input, "467..114..").unwrap();
+
+	let changes = Changes::new(&input).unwrap();
+	let timeout = Some(Duration::from_secs(5));
+	std::fs::write(&other, "unrelated").unwrap();
+	assert!(!changes.wait(Some(Duration::from_millis(300))).unwrap());
+	std::fs::write(&input, "467..114.*").unwrap();
+	assert!(changes.wait(timeout).unwrap());
+	// saved by renaming a new file over the old one
+	std::fs::write(dir.join("edges.txt.tmp"), "*67..114..").unwrap();
+	std::fs::rename(dir.join("edges.txt.tmp"), &input).unwrap();
+	assert!(changes.wait(timeout).unwrap());
+
+	// anything in a watched directory counts
+	let changes = Changes::new(&dir).unwrap();
+	std::fs::write(&other, "related").unwrap();
+	assert!(changes.wait(timeout).unwrap());
+
+	std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,27 @@
+//! Runs the `aoc2023` binary the way it is used from a shell.
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the binary without touching the history, feeding it `stdin`, and returns what it printed.
+fn run(args: &[&str], stdin: &str) -> String {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2023"))
+		.args(args)
+		.arg("--no-history")
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+	let output = child.wait_with_output().unwrap();
+	assert!(output.status.success(), "{:?} failed: {}", args, String::from_utf8_lossy(&output.stderr));
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn explains_stdin() {
+	// stdin can only be read once, so the explanation has to come from the input that was solved
+	let output = run(&["2", "--input", "-", "--explain"], "Game 1: 3 blue, 4 red\n");
+	assert!(output.contains("Day 2 part 1 (stdin):\nGame 1  possible, total 1\n"), "{}", output);
+	assert!(output.contains("Day 2 part 2 (stdin):\nGame 1  needs 4 red, 3 blue, power 0, total 0\n"), "{}", output);
+}
@@ -0,0 +1,207 @@
+//! Property tests for every day's parser.
+//!
+//! Each day has a strategy for structurally valid input, which must parse, and a corrupted version of it
+//! with a few characters removed, inserted or truncated, which must either parse or fail with an error
+//! rather than panic. Line types that can be displayed back out in the input format are round-tripped.
+use proptest::prelude::*;
+use proptest::collection::vec;
+use proptest::sample::Index;
+use aocinput::TryParse;
+
+/// Parses the input, also rendering any error to make sure its span can be displayed.
+fn parses<D: TryParse>(day: D, input: &str) -> bool {
+	match day.try_parse(input) {
+		Ok(_) => true,
+		Err(e) => { let _ = e.to_string(); false },
+	}
+}
+
+/// Joins generated lines into an input, with the leading and trailing newlines the samples have.
+fn lines<S: Strategy<Value = String>>(line: S, count: std::ops::Range<usize>) -> impl Strategy<Value = String> {
+	vec(line, count).prop_map(|l| format!("\n{}\n", l.join("\n")))
+}
+
+/// Numbers separated by single spaces
+fn numbers(range: std::ops::Range<i64>, count: std::ops::Range<usize>) -> impl Strategy<Value = String> {
+	vec(range, count).prop_map(|n| n.iter().map(i64::to_string).collect::<Vec<_>>().join(" "))
+}
+
+/// A rectangular grid made up of the given tiles
+fn grid(tiles: &'static [char]) -> impl Strategy<Value = String> {
+	(1..12usize, 1..12usize).prop_flat_map(move |(w, h)| {
+		let row = vec(prop::sample::select(tiles), w).prop_map(String::from_iter);
+		lines(row, h..h+1)
+	})
+}
+
+#[derive(Debug, Clone)]
+enum Edit {
+	Delete(Index),
+	Insert(Index, char),
+	Truncate(Index),
+}
+
+/// Valid input with a few small edits made to it, favouring characters that mean something to the parsers.
+fn corrupted<S: Strategy<Value = String>>(valid: S) -> impl Strategy<Value = String> {
+	let interesting = prop_oneof![
+		prop::sample::select(vec![' ', '\n', '\r', ':', ',', ';', '|', '-', '(', ')', '=', '0', '9', '#', '.', 'S']),
+		any::<char>(),
+	];
+	let edit = prop_oneof![
+		any::<Index>().prop_map(Edit::Delete),
+		(any::<Index>(), interesting).prop_map(|(i, c)| Edit::Insert(i, c)),
+		any::<Index>().prop_map(Edit::Truncate),
+	];
+	(valid, vec(edit, 1..4)).prop_map(|(mut input, edits)| {
+		for edit in edits {
+			let boundaries: Vec<usize> = input.char_indices().map(|(i, _)| i).chain([input.len()]).collect();
+			match edit {
+				Edit::Delete(i) if !input.is_empty() => { input.remove(boundaries[i.index(boundaries.len()-1)]); },
+				Edit::Delete(_) => {},
+				Edit::Insert(i, c) => input.insert(*i.get(&boundaries), c),
+				Edit::Truncate(i) => input.truncate(*i.get(&boundaries)),
+			}
+		}
+		input
+	})
+}
+
+fn day01() -> impl Strategy<Value = String> {
+	lines("[a-z]{0,6}[1-9][a-z1-9]{0,6}", 1..10)
+}
+
+fn day02_line() -> impl Strategy<Value = String> {
+	let color = prop::sample::subsequence(vec!["red", "green", "blue"], 1..=3);
+	let play = (color, vec(0..20u32, 3)).prop_map(|(colors, counts)| {
+		colors.iter().zip(counts).map(|(c, n)| format!("{n} {c}")).collect::<Vec<_>>().join(", ")
+	});
+	(1..200u32, vec(play, 1..5)).prop_map(|(id, plays)| format!("Game {}: {}", id, plays.join("; ")))
+}
+
+fn day04() -> impl Strategy<Value = String> {
+	let card = (1..300u32, numbers(0..100, 1..6), numbers(0..100, 1..10))
+		.prop_map(|(id, winning, ours)| format!("Card {id}: {winning} | {ours}"));
+	lines(card, 1..8)
+}
+
+/// A map's segments, in any order, whose sources don't overlap. Each map's numbers are either all near 0 or all
+/// past isize::MAX, so that every segment's offset fits in an isize.
+fn day05_segments() -> impl Strategy<Value = Vec<String>> {
+	let base = prop_oneof![Just(0u64), Just(1u64 << 63)];
+	// each segment's destination, then the gap before its source and its length
+	(base, vec((0..200u64, 0..20u64, 1..50u64), 1..4)).prop_map(|(base, segments)| {
+		let mut src = base;
+		segments.into_iter().map(|(dst, gap, len)| {
+			src += gap;
+			let segment = format!("{} {} {}", base + dst, src, len);
+			src += len;
+			segment
+		}).collect::<Vec<_>>()
+	}).prop_shuffle()
+}
+
+/// A map's segments, which can overlap, and can move values from near 0 to past isize::MAX or back, further
+/// than an offset can.
+fn day05_any_segments() -> impl Strategy<Value = Vec<String>> {
+	let number = prop_oneof![0..200u64, (1u64 << 63)..(1u64 << 63) + 200];
+	let segment = (number.clone(), number, 1..50u64).prop_map(|(dst, src, len)| format!("{dst} {src} {len}"));
+	vec(segment, 1..4)
+}
+
+fn day05<S: Strategy<Value = Vec<String>>>(segments: S) -> impl Strategy<Value = String> {
+	const KINDS: [&str; 4] = ["seed", "soil", "fertilizer", "water"];
+	(numbers(0..200, 1..6), vec(segments, 1..4)).prop_map(|(seeds, maps)| {
+		let mut input = format!("\nseeds: {seeds}\n");
+		for (i, segments) in maps.iter().enumerate() {
+			input += &format!("\n{}-to-{} map:\n{}\n", KINDS[i], KINDS[i+1], segments.join("\n"));
+		}
+		input
+	})
+}
+
+fn day06() -> impl Strategy<Value = String> {
+	(1..5usize).prop_flat_map(|n| (numbers(1..100, n..n+1), numbers(0..1000, n..n+1)))
+		.prop_map(|(time, dist)| format!("\nTime: {time}\nDistance: {dist}\n"))
+}
+
+fn day07_line() -> impl Strategy<Value = String> {
+	("[2-9TJQKA]{5}", 1..1000u32).prop_map(|(cards, bid)| format!("{cards} {bid}"))
+}
+
+fn day08() -> impl Strategy<Value = String> {
+	let nodes = prop::collection::btree_set("[A-Z0-9]{3}", 1..8)
+		.prop_map(|n| n.into_iter().collect::<Vec<_>>());
+	(nodes, "[LR]{1,10}").prop_flat_map(|(nodes, directions)| {
+		let pick = prop::sample::select(nodes.clone());
+		(Just(nodes.clone()), Just(directions), vec((pick.clone(), pick), nodes.len()))
+	}).prop_map(|(nodes, directions, dests)| {
+		let mapping = nodes.iter().zip(dests)
+			.map(|(n, (l, r))| format!("{n} = ({l}, {r})"))
+			.collect::<Vec<_>>();
+		format!("\n{}\n\n{}\n", directions, mapping.join("\n"))
+	})
+}
+
+fn day09() -> impl Strategy<Value = String> {
+	lines(numbers(-100..100, 2..8), 1..6)
+}
+
+fn day12_line() -> impl Strategy<Value = String> {
+	("[.#?]{1,12}", numbers(1..6, 1..4))
+		.prop_map(|(springs, groups)| format!("{} {}", springs, groups.replace(' ', ",")))
+}
+
+/// Tests that valid input parses, and that corrupted input never panics.
+macro_rules! parser_props {
+	($valid:ident, $corrupt:ident, $day:expr, $strategy:expr) => {
+		proptest! {
+			#[test]
+			fn $valid(input in $strategy) {
+				prop_assert!(parses($day, &input), "failed to parse {:?}", input);
+			}
+			#[test]
+			fn $corrupt(input in corrupted($strategy)) {
+				parses($day, &input);
+			}
+		}
+	};
+}
+
+parser_props!(day01_valid, day01_corrupted, day01::Day01, day01());
+parser_props!(day02_valid, day02_corrupted, day02::Day02, lines(day02_line(), 1..6));
+parser_props!(day03_valid, day03_corrupted, day03::Day03, grid(&['.', '.', '.', '*', '#', '$', '1', '2', '3', '4', '5']));
+parser_props!(day04_valid, day04_corrupted, day04::Day04, day04());
+parser_props!(day05_valid, day05_corrupted, day05::Day05, day05(day05_segments()));
+parser_props!(day06_valid, day06_corrupted, day06::Day06, day06());
+parser_props!(day07_valid, day07_corrupted, day07::Day07, lines(day07_line(), 1..6));
+parser_props!(day08_valid, day08_corrupted, day08::Day08, day08());
+parser_props!(day09_valid, day09_corrupted, day09::Day09, day09());
+parser_props!(day10_valid, day10_corrupted, day10::Day10, grid(&['|', '-', 'L', 'J', '7', 'F', '.', 'S']));
+parser_props!(day11_valid, day11_corrupted, day11::Day11, grid(&['.', '.', '#']));
+parser_props!(day12_valid, day12_corrupted, day12::Day12, lines(day12_line(), 1..6));
+
+proptest! {
+	// segments like these are often rejected, which only has to happen without panicking
+	#[test]
+	fn day05_any_segments_never_panic(input in day05(day05_any_segments())) {
+		parses(day05::Day05, &input);
+	}
+
+	#[test]
+	fn day02_round_trip(line in day02_line()) {
+		let game: day02::Game = line.parse().unwrap();
+		prop_assert_eq!(game.to_string().parse::<day02::Game>(), Ok(game));
+	}
+	#[test]
+	fn day07_round_trip(line in day07_line()) {
+		let play: day07::Play = line.parse().unwrap();
+		prop_assert_eq!(&play.to_string(), &line);
+		prop_assert_eq!(play.to_string().parse::<day07::Play>(), Ok(play));
+	}
+	#[test]
+	fn day12_round_trip(line in day12_line()) {
+		let row: day12::Row = line.parse().unwrap();
+		prop_assert_eq!(&row.to_string(), &line);
+		prop_assert_eq!(row.to_string().parse::<day12::Row>(), Ok(row));
+	}
+}
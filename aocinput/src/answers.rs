@@ -0,0 +1,351 @@
+//! The registry of known answers, kept in `answers.toml` at the root of the workspace.
+//!
+//! Answers are keyed by day, then by the name of the input, then by part:
+//!
+//! ```toml
+//! [day05.input]
+//! part1 = 278755257
+//! part2 = 26829166
+//! ```
+//!
+//! [`Number`](Answer::Number) answers that fit in an `i64` are stored as integers, anything else as a string.
+//! They are always compared by how the day's answer displays, so a day can answer with any type that implements
+//! `Display`.
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use aoch::{AoCDay, DayPart};
+use toml_edit::{Document, Item, Table};
+use crate::normalize::normalize;
+
+/// The name the day's embedded puzzle input is recorded under.
+pub const EMBEDDED: &str = "input";
+
+/// An answer of any day, whatever its own answer type is, so that days with different types of answer can be
+/// reported together.
+///
+/// Days can also answer with this directly, for answers that are more than a single number or string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+	Number(i128),
+	Text(String),
+	/// Several answers, displayed separated by commas
+	Tuple(Vec<Answer>),
+	/// A picture drawn with characters, like letters spelled out on a screen, displayed one row per line
+	Grid(Vec<String>),
+}
+
+impl Answer {
+	/// Reads a day's answer back from how it displays. An answer that displays on several lines is a
+	/// [`Grid`](Answer::Grid), and one that displays as an integer (without any leading zeros or `+`) is a
+	/// [`Number`](Answer::Number). Anything else is [`Text`](Answer::Text).
+	pub fn of(answer: &impl Display) -> Answer {
+		let shown = answer.to_string();
+		if shown.contains('\n') {
+			return Answer::Grid(shown.lines().map(str::to_owned).collect());
+		}
+		match shown.parse::<i128>() {
+			Ok(n) if n.to_string() == shown => Answer::Number(n),
+			_ => Answer::Text(shown),
+		}
+	}
+
+	/// A grid of the rows yielded, which should all be the same width
+	pub fn grid<R: Into<String>>(rows: impl IntoIterator<Item = R>) -> Answer {
+		Answer::Grid(rows.into_iter().map(Into::into).collect())
+	}
+}
+
+impl fmt::Display for Answer {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Answer::Number(n) => write!(f, "{}", n),
+			Answer::Text(s) => write!(f, "{}", s),
+			Answer::Tuple(answers) => {
+				for (i, answer) in answers.iter().enumerate() {
+					if i > 0 { write!(f, ",")?; }
+					write!(f, "{}", answer)?;
+				}
+				Ok(())
+			},
+			Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+		}
+	}
+}
+
+macro_rules! number {
+	($($t:ty),*) => {$(
+		impl From<$t> for Answer {
+			fn from(n: $t) -> Answer {
+				Answer::Number(n.into())
+			}
+		}
+	)*};
+}
+number!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+	fn from(n: usize) -> Answer {
+		Answer::Number(n as i128)
+	}
+}
+impl From<isize> for Answer {
+	fn from(n: isize) -> Answer {
+		Answer::Number(n as i128)
+	}
+}
+impl From<String> for Answer {
+	fn from(s: String) -> Answer {
+		Answer::Text(s)
+	}
+}
+impl From<&str> for Answer {
+	fn from(s: &str) -> Answer {
+		Answer::Text(s.to_owned())
+	}
+}
+impl<A: Into<Answer>, B: Into<Answer>> From<(A, B)> for Answer {
+	fn from((a, b): (A, B)) -> Answer {
+		Answer::Tuple(vec![a.into(), b.into()])
+	}
+}
+impl<A: Into<Answer>, B: Into<Answer>, C: Into<Answer>> From<(A, B, C)> for Answer {
+	fn from((a, b, c): (A, B, C)) -> Answer {
+		Answer::Tuple(vec![a.into(), b.into(), c.into()])
+	}
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AnswersError {
+	#[error("unable to access {0}: {1}")]
+	Io(PathBuf, std::io::Error),
+	#[error("{0} is not valid TOML: {1}")]
+	Toml(PathBuf, toml_edit::TomlError),
+}
+
+/// How a solved part compares against the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+	/// The answer matches the recorded one
+	Correct,
+	/// The answer differs from the recorded one
+	Wrong { expected: String },
+	/// There is no recorded answer for this part yet
+	New,
+	/// There is a recorded answer, but the part produced no answer to check against it
+	Missing { expected: String },
+}
+
+/// The answers from an `answers.toml`, which can be added to and written back out without losing its comments.
+#[derive(Debug, Clone)]
+pub struct Answers {
+	path: PathBuf,
+	doc: Document,
+}
+
+/// The path of `answers.toml` at the root of the workspace.
+pub fn workspace_path() -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("answers.toml")
+}
+
+fn day_key(day: u8) -> String {
+	format!("day{:02}", day)
+}
+fn part_key(part: u8) -> String {
+	format!("part{}", part)
+}
+
+impl Answers {
+	/// Loads the answers from `path`. A file that doesn't exist yet is treated as having no answers.
+	pub fn load(path: impl Into<PathBuf>) -> Result<Answers, AnswersError> {
+		let path = path.into();
+		let raw = match std::fs::read_to_string(&path) {
+			Ok(raw) => raw,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+			Err(e) => return Err(AnswersError::Io(path, e)),
+		};
+		let doc = raw.parse().map_err(|e| AnswersError::Toml(path.clone(), e))?;
+		Ok(Answers { path, doc })
+	}
+
+	/// Loads the answers in `answers.toml` at the root of the workspace.
+	pub fn workspace() -> Result<Answers, AnswersError> {
+		Answers::load(workspace_path())
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// The recorded answer for a part of a day's input, if there is one.
+	pub fn get(&self, day: u8, input: &str, part: u8) -> Option<String> {
+		let value = self.doc.get(&day_key(day))?
+			.get(input)?
+			.get(part_key(part))?;
+		value.as_integer().map(|n| n.to_string())
+			.or_else(|| value.as_str().map(str::to_owned))
+	}
+
+	/// Records the answer for a part of a day's input, replacing any existing one.
+	pub fn record(&mut self, day: u8, input: &str, part: u8, answer: &Answer) {
+		let days = self.doc.as_table_mut();
+		let inputs = days.entry(&day_key(day))
+			.or_insert_with(|| {
+				let mut t = Table::new();
+				t.set_implicit(true);
+				Item::Table(t)
+			});
+		let parts = inputs[input].or_insert(toml_edit::table());
+		// text that looks like a number, such as "007", has to stay a string to be read back as it was
+		parts[part_key(part).as_str()] = match answer {
+			Answer::Number(n) => match i64::try_from(*n) {
+				Ok(n) => toml_edit::value(n),
+				Err(_) => toml_edit::value(n.to_string()),
+			},
+			answer => toml_edit::value(answer.to_string()),
+		};
+	}
+
+	/// Writes the answers back to the file they were loaded from.
+	pub fn save(&self) -> Result<(), AnswersError> {
+		std::fs::write(&self.path, self.doc.to_string())
+			.map_err(|e| AnswersError::Io(self.path.clone(), e))
+	}
+
+	/// Compares the answer a part produced against the recorded one. Returns `None` if there's neither.
+	pub fn check(&self, day: u8, input: &str, part: u8, answer: Option<&str>) -> Option<Verdict> {
+		match (self.get(day, input, part), answer) {
+			(None, None) => None,
+			(None, Some(_)) => Some(Verdict::New),
+			(Some(expected), None) => Some(Verdict::Missing { expected }),
+			(Some(expected), Some(answer)) if expected == answer => Some(Verdict::Correct),
+			(Some(expected), Some(_)) => Some(Verdict::Wrong { expected }),
+		}
+	}
+}
+
+/// `aoch::test_runner`, but with each case's input normalised first, like any input read from a
+/// [`Source`](crate::Source) is. Empty inputs are skipped.
+pub fn test_runner<D: AoCDay, I: AsRef<str>>(day: D, part: DayPart, cases: &[(I, D::Answer)])
+where
+	D::Answer: fmt::Debug + PartialEq,
+{
+	for (i, (input, expected)) in cases.iter().enumerate() {
+		if input.as_ref().is_empty() { continue; }
+		let input = normalize(input.as_ref()).text;
+		let mut data = day.parse(&input);
+		let answer = match part {
+			DayPart::Part1 => day.part1(&mut data),
+			DayPart::Part2 => day.part2(&mut data),
+		};
+		assert_eq!(&answer, expected, "case {} of day {} {:?} failed", i, day.day(), part);
+	}
+}
+
+/// Solves a part of a day for each case's normalised input, asserting that it gives the case's answer. Empty
+/// inputs are skipped.
+///
+/// Like [`test_runner`], but answers only need to be displayable, and are shown as they display when they
+/// differ, which is far easier to read than their `Debug` for grids and the like.
+pub fn test_cases<D: AoCDay, I: AsRef<str>>(day: D, part: u8, cases: &[(I, D::Answer)])
+where
+	D::Answer: Display + Eq,
+{
+	for (i, (input, expected)) in cases.iter().enumerate() {
+		if input.as_ref().is_empty() { continue; }
+		let input = normalize(input.as_ref()).text;
+		let mut data = day.parse(&input);
+		let answer = match part {
+			1 => day.part1(&mut data),
+			_ => day.part2(&mut data),
+		};
+		if answer != *expected {
+			panic!("case {} of day {} part {} failed\nanswered:\n{}\nexpected:\n{}", i, day.day(), part, answer, expected);
+		}
+	}
+}
+
+/// Solves both parts of a day's embedded input, asserting each against the answer recorded in the workspace's
+/// `answers.toml`. Parts without a recorded answer are only solved, and an empty input is skipped entirely.
+pub fn test_answers<D: AoCDay>(day: D, input: &str)
+where
+	D::Answer: Display,
+{
+	if input.is_empty() { return; }
+	let answers = Answers::workspace().unwrap_or_else(|e| panic!("{}", e));
+	let input = normalize(input).text;
+
+	for part in [1, 2] {
+		let mut data = day.parse(&input);
+		let answer = match part {
+			1 => day.part1(&mut data),
+			_ => day.part2(&mut data),
+		}.to_string();
+		match answers.check(day.day(), EMBEDDED, part, Some(&answer)) {
+			Some(Verdict::Wrong { expected }) => panic!("day {} part {} answered {}, but {} was expected", day.day(), part, answer, expected),
+			Some(Verdict::New) => eprintln!("day {} part {} answered {}, which isn't recorded yet", day.day(), part, answer),
+			_ => {},
+		}
+	}
+}
+
+#[test]
+fn registry() {
+	const SAMPLE: &str = "# known answers\n\n[day05.input]\npart1 = 278755257\npart2 = \"abc\"\n";
+	let mut answers = Answers { path: PathBuf::new(), doc: SAMPLE.parse().unwrap() };
+	assert_eq!(answers.get(5, EMBEDDED, 1), Some("278755257".into()));
+	assert_eq!(answers.get(5, EMBEDDED, 2), Some("abc".into()));
+	assert_eq!(answers.get(5, "sample", 1), None);
+	assert_eq!(answers.get(6, EMBEDDED, 1), None);
+
+	assert_eq!(answers.check(5, EMBEDDED, 1, Some("278755257")), Some(Verdict::Correct));
+	assert_eq!(answers.check(5, EMBEDDED, 1, Some("1")), Some(Verdict::Wrong { expected: "278755257".into() }));
+	assert_eq!(answers.check(5, EMBEDDED, 1, None), Some(Verdict::Missing { expected: "278755257".into() }));
+	assert_eq!(answers.check(6, EMBEDDED, 1, Some("1")), Some(Verdict::New));
+	assert_eq!(answers.check(6, EMBEDDED, 1, None), None);
+
+	answers.record(5, EMBEDDED, 2, &Answer::from(42));
+	answers.record(11, EMBEDDED, 2, &Answer::from("1.5"));
+	assert_eq!(answers.doc.to_string(), format!("{}{}", SAMPLE.replace("\"abc\"", "42"), "\n[day11.input]\npart2 = \"1.5\"\n"));
+
+	// grids are recorded and compared as they display, over several lines
+	let grid = Answer::grid(["#..#", ".##."]);
+	answers.record(12, EMBEDDED, 1, &grid);
+	let reloaded = Answers { path: PathBuf::new(), doc: answers.doc.to_string().parse().unwrap() };
+	assert_eq!(reloaded.check(12, EMBEDDED, 1, Some(&grid.to_string())), Some(Verdict::Correct));
+
+	// every answer reads back as it displays, whether it's stored as an integer or not
+	let cases = [Answer::from("007"), Answer::from("+5"), Answer::from("-3"), Answer::from(-3), Answer::from(u64::MAX), Answer::from(i128::MIN)];
+	for (i, answer) in cases.iter().enumerate() {
+		answers.record(13, EMBEDDED, i as u8, answer);
+	}
+	let reloaded = Answers { path: PathBuf::new(), doc: answers.doc.to_string().parse().unwrap() };
+	for (i, answer) in cases.iter().enumerate() {
+		assert_eq!(reloaded.get(13, EMBEDDED, i as u8), Some(answer.to_string()));
+	}
+	let parts = &reloaded.doc["day13"][EMBEDDED];
+	assert!(parts["part3"].is_integer() && parts["part2"].is_str() && parts["part4"].is_str(), "{}", parts);
+}
+
+#[test]
+fn answer_types() {
+	let cases = [
+		(Answer::from(42usize), "42"),
+		(Answer::from(-7isize), "-7"),
+		(Answer::from("abc"), "abc"),
+		(Answer::from((3u32, "x")), "3,x"),
+		(Answer::from((1u8, -2i64, (3u8, 4u8))), "1,-2,3,4"),
+		(Answer::grid(["#.", ".#"]), "#.\n.#"),
+	];
+	for (answer, shown) in cases {
+		assert_eq!(answer.to_string(), shown);
+	}
+
+	// reading an answer back from its display
+	assert_eq!(Answer::of(&42usize), Answer::Number(42));
+	assert_eq!(Answer::of(&u128::MAX), Answer::Text(u128::MAX.to_string()));
+	assert_eq!(Answer::of(&"007"), Answer::Text("007".into()));
+	assert_eq!(Answer::of(&"EHPZPJGL"), Answer::Text("EHPZPJGL".into()));
+	assert_eq!(Answer::of(&"#.\n.#\n"), Answer::grid(["#.", ".#"]));
+	let tuple = Answer::from((3u32, "x"));
+	assert_eq!(Answer::of(&tuple).to_string(), tuple.to_string());
+}
@@ -0,0 +1,126 @@
+//! Arithmetic for the answers that can outgrow a `usize`.
+//!
+//! By default these are plain operations, which silently wrap in release builds. The `checked` feature checks
+//! each one, failing with an [`Overflow`] instead of producing a wrong answer. The `bigint` feature makes
+//! [`Answer`] an arbitrary precision integer, so answers built up from it can't overflow at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("arithmetic overflow while {0}")]
+pub struct Overflow(pub &'static str);
+
+/// The answer of days whose answers are built up from many smaller numbers
+#[cfg(not(feature = "bigint"))]
+pub type Answer = usize;
+/// The answer of days whose answers are built up from many smaller numbers
+#[cfg(feature = "bigint")]
+pub type Answer = num_bigint::BigUint;
+
+/// Converts a number into an [`Answer`]
+#[cfg(not(feature = "bigint"))]
+pub fn answer(n: usize) -> Answer {
+	n
+}
+/// Converts a number into an [`Answer`]
+#[cfg(feature = "bigint")]
+pub fn answer(n: usize) -> Answer {
+	n.into()
+}
+
+/// Integers that can be checked for overflow, depending on the `checked` feature.
+pub trait Checked: Sized {
+	fn add(self, rhs: &Self) -> Option<Self>;
+	fn mul(self, rhs: &Self) -> Option<Self>;
+	fn pow(self, exp: u32) -> Option<Self>;
+	fn lcm(self, rhs: &Self) -> Option<Self>;
+}
+
+impl Checked for usize {
+	#[cfg(feature = "checked")]
+	fn add(self, rhs: &Self) -> Option<Self> { self.checked_add(*rhs) }
+	#[cfg(feature = "checked")]
+	fn mul(self, rhs: &Self) -> Option<Self> { self.checked_mul(*rhs) }
+	#[cfg(feature = "checked")]
+	fn pow(self, exp: u32) -> Option<Self> { self.checked_pow(exp) }
+	#[cfg(feature = "checked")]
+	fn lcm(self, rhs: &Self) -> Option<Self> { numtheory::lcm(self, *rhs) }
+
+	#[cfg(not(feature = "checked"))]
+	fn add(self, rhs: &Self) -> Option<Self> { Some(self + rhs) }
+	#[cfg(not(feature = "checked"))]
+	fn mul(self, rhs: &Self) -> Option<Self> { Some(self * rhs) }
+	#[cfg(not(feature = "checked"))]
+	fn pow(self, exp: u32) -> Option<Self> { Some(usize::pow(self, exp)) }
+	#[cfg(not(feature = "checked"))]
+	fn lcm(self, rhs: &Self) -> Option<Self> {
+		if self == 0 || *rhs == 0 {
+			return Some(0);
+		}
+		Some(self / numtheory::gcd(self, *rhs) * rhs)
+	}
+}
+
+#[cfg(feature = "bigint")]
+impl Checked for num_bigint::BigUint {
+	fn add(self, rhs: &Self) -> Option<Self> { Some(self + rhs) }
+	fn mul(self, rhs: &Self) -> Option<Self> { Some(self * rhs) }
+	fn pow(self, exp: u32) -> Option<Self> { Some(num_bigint::BigUint::pow(&self, exp)) }
+	fn lcm(self, rhs: &Self) -> Option<Self> {
+		// numtheory only covers the primitive integers, so this is its gcd by hand
+		let zero = num_bigint::BigUint::default();
+		if self == zero || *rhs == zero {
+			return Some(zero);
+		}
+		let (mut a, mut b) = (self.clone(), rhs.clone());
+		while b != zero {
+			(a, b) = (b.clone(), a % b);
+		}
+		Some(self / a * rhs)
+	}
+}
+
+/// Adds two numbers, where `what` describes the calculation if it overflows
+pub fn add<T: Checked>(a: T, b: &T, what: &'static str) -> Result<T, Overflow> {
+	a.add(b).ok_or(Overflow(what))
+}
+
+/// Multiplies two numbers, where `what` describes the calculation if it overflows
+pub fn mul<T: Checked>(a: T, b: &T, what: &'static str) -> Result<T, Overflow> {
+	a.mul(b).ok_or(Overflow(what))
+}
+
+/// Raises a number to a power, where `what` describes the calculation if it overflows
+pub fn pow<T: Checked>(base: T, exp: u32, what: &'static str) -> Result<T, Overflow> {
+	base.pow(exp).ok_or(Overflow(what))
+}
+
+/// Sums numbers, where `what` describes the calculation if it overflows
+pub fn sum<T: Checked>(iter: impl IntoIterator<Item = T>, zero: T, what: &'static str) -> Result<T, Overflow> {
+	iter.into_iter().try_fold(zero, |acc, n| add(acc, &n, what))
+}
+
+/// The least common multiple of two numbers, where `what` describes the calculation if it overflows
+pub fn lcm<T: Checked>(a: T, b: T, what: &'static str) -> Result<T, Overflow> {
+	a.lcm(&b).ok_or(Overflow(what))
+}
+
+#[test]
+fn overflow() {
+	assert_eq!(add(2usize, &3, "adding"), Ok(5));
+	assert_eq!(lcm(4usize, 6, "lcm"), Ok(12));
+	assert_eq!(lcm(0usize, 6, "lcm"), Ok(0));
+	assert_eq!(pow(10usize, 3, "pow"), Ok(1000));
+	assert_eq!(sum([1usize, 2, 3], 0, "sum"), Ok(6));
+
+	#[cfg(feature = "checked")]
+	{
+		assert_eq!(add(usize::MAX, &1, "adding"), Err(Overflow("adding")));
+		assert_eq!(mul(usize::MAX / 2, &3, "multiplying"), Err(Overflow("multiplying")));
+		assert_eq!(pow(10usize, 20, "pow"), Err(Overflow("pow")));
+		assert_eq!(lcm(usize::MAX - 1, usize::MAX, "lcm").map_err(|e| e.to_string()), Err("arithmetic overflow while lcm".into()));
+	}
+	#[cfg(feature = "bigint")]
+	{
+		assert_eq!(lcm(answer(usize::MAX - 1), answer(usize::MAX), "lcm").unwrap().to_string(), "340282366920938463408034375210639556610");
+		assert_eq!(lcm(answer(12), answer(18), "lcm"), Ok(answer(36)));
+		assert_eq!(lcm(answer(0), answer(18), "lcm"), Ok(answer(0)));
+	}
+}
@@ -0,0 +1,56 @@
+//! Step-by-step accounts of how a day's answers were produced, for when an answer is wrong and the solver needs
+//! to be followed along by hand.
+use std::fmt;
+use aoch::AoCDay;
+
+/// A single step towards an answer, such as a line of input and what it added to the total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+	/// What the step is about, like `Game 3` or `seed 79`
+	pub subject: String,
+	/// What was worked out about it
+	pub detail: String,
+}
+
+/// How a part's answer was produced, in the order the solver worked it out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Explanation {
+	pub steps: Vec<Step>,
+}
+
+impl Explanation {
+	pub fn step(&mut self, subject: impl fmt::Display, detail: impl fmt::Display) {
+		self.steps.push(Step { subject: subject.to_string(), detail: detail.to_string() });
+	}
+}
+
+/// Each step on its own line, with the details lined up.
+impl fmt::Display for Explanation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let width = self.steps.iter().map(|s| s.subject.chars().count()).max().unwrap_or(0);
+		for Step { subject, detail } in &self.steps {
+			writeln!(f, "{:<width$}  {}", subject, detail)?;
+		}
+		Ok(())
+	}
+}
+
+/// An opt-in account of how each part reaches its answer.
+///
+/// Days that don't explain themselves implement this with the defaults, which have nothing to say.
+pub trait Explain: AoCDay {
+	fn explain_part1(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		None
+	}
+	fn explain_part2(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		None
+	}
+}
+
+#[test]
+fn display() {
+	let mut explanation = Explanation::default();
+	explanation.step("Game 1", "possible, adds 1");
+	explanation.step("Game 12", "impossible, 14 red");
+	assert_eq!(explanation.to_string(), "Game 1   possible, adds 1\nGame 12  impossible, 14 red\n");
+}
@@ -0,0 +1,174 @@
+//! Seeded generation of synthetic puzzle input, for testing how the solvers scale past the real input.
+//!
+//! Generated input also drives differential testing: a day can give a deliberately naive [`Reference`] solution
+//! for a part, and [`cross_check`] compares it against the real solver on generated input small enough for the
+//! naive solution to handle.
+use std::fmt::{self, Debug, Display};
+use std::ops::Range;
+use aoch::AoCDay;
+use crate::normalize::normalize;
+
+pub use rand::{Rng, SeedableRng};
+pub use rand::rngs::StdRng;
+pub use rand::seq::SliceRandom;
+
+/// A generated input, along with the answer to each part where it was cheap to work out while generating it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated<A> {
+	pub input: String,
+	pub part1: Option<A>,
+	pub part2: Option<A>,
+}
+
+/// Generates valid input for a day, so that solvers can be run at sizes the real input doesn't reach.
+pub trait Generate: AoCDay {
+	/// Size parameters for the input. The defaults are roughly the size of a real input.
+	type Params: Default + Debug;
+
+	/// Generates an input, which is always the same for the same seed and parameters.
+	fn generate(&self, seed: u64, params: &Self::Params) -> Generated<Self::Answer>;
+}
+
+/// The random number generator every day generates its input from.
+pub fn rng(seed: u64) -> StdRng {
+	StdRng::seed_from_u64(seed)
+}
+
+/// Solves the normalised input generated from each seed, checking each part against the generated answer if
+/// there was one.
+pub fn verify<D>(day: D, seeds: Range<u64>, params: &D::Params)
+where
+	D: Generate,
+	D::Answer: PartialEq + Debug,
+{
+	for seed in seeds {
+		let generated = day.generate(seed, params);
+		let input = normalize(&generated.input).text;
+		if let Some(expected) = generated.part1 {
+			let mut data = day.parse(&input);
+			assert_eq!(day.part1(&mut data), expected, "part 1 of day {} with seed {} and {:?}", day.day(), seed, params);
+		}
+		if let Some(expected) = generated.part2 {
+			let mut data = day.parse(&input);
+			assert_eq!(day.part2(&mut data), expected, "part 2 of day {} with seed {} and {:?}", day.day(), seed, params);
+		}
+	}
+}
+
+/// The obvious, slow way to solve a day's parts, kept only to check the real solver against. Parts without a
+/// reference solution return `None`, which they do by default.
+///
+/// References work from the raw input, so they can skip whatever the real parser sets up to make solving fast.
+pub trait Reference: AoCDay {
+	fn reference_part1(&self, _input: &str) -> Option<Self::Answer> {
+		None
+	}
+	fn reference_part2(&self, _input: &str) -> Option<Self::Answer> {
+		None
+	}
+}
+
+/// The first input a day's solver and its reference solution disagreed on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence<A> {
+	pub day: u8,
+	pub part: u8,
+	pub seed: u64,
+	pub input: String,
+	pub solver: A,
+	pub reference: A,
+}
+
+impl<A: Display> fmt::Display for Divergence<A> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "day {} part {} answered {}, but the reference answered {}, for the input generated from seed {}:",
+			self.day, self.part, self.solver, self.reference, self.seed)?;
+		write!(f, "{}", self.input)
+	}
+}
+
+/// Solves the input generated from each seed with both the day's solver and its reference solution, stopping
+/// at the first seed and part where they disagree. Returns how many parts were compared otherwise.
+pub fn cross_check<D>(day: D, seeds: Range<u64>, params: &D::Params) -> Result<usize, Divergence<D::Answer>>
+where
+	D: Generate + Reference,
+	D::Answer: PartialEq,
+{
+	let mut compared = 0;
+	for seed in seeds {
+		let generated = day.generate(seed, params).input;
+		let input = normalize(&generated).text;
+		for part in [1, 2] {
+			let reference = match part {
+				1 => day.reference_part1(&input),
+				_ => day.reference_part2(&input),
+			};
+			let Some(reference) = reference else { continue };
+			let solver = {
+				let mut data = day.parse(&input);
+				match part {
+					1 => day.part1(&mut data),
+					_ => day.part2(&mut data),
+				}
+			};
+			if solver != reference {
+				return Err(Divergence { day: day.day(), part, seed, input: input.into_owned(), solver, reference });
+			}
+			compared += 1;
+		}
+	}
+	Ok(compared)
+}
+
+/// Sums a line of numbers, with a reference that forgets about the numbers past the fourth
+#[cfg(test)]
+struct Summed;
+
+#[cfg(test)]
+impl AoCDay for Summed {
+	type Data<'i> = Vec<u64>;
+	type Answer = u64;
+
+	fn day(&self) -> u8 { 0 }
+
+	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
+		input.split_whitespace().map(|n| n.parse().unwrap()).collect()
+	}
+	fn part1(&self, data: &mut Self::Data<'_>) -> Self::Answer {
+		data.iter().sum()
+	}
+	fn part2(&self, data: &mut Self::Data<'_>) -> Self::Answer {
+		data.len() as u64
+	}
+}
+
+#[cfg(test)]
+impl Generate for Summed {
+	type Params = ();
+
+	fn generate(&self, seed: u64, _params: &()) -> Generated<u64> {
+		let mut rng = rng(seed);
+		let nums = (0..rng.gen_range(1..=6)).map(|_| rng.gen_range(0..10u64).to_string()).collect::<Vec<_>>();
+		Generated { input: nums.join(" "), part1: None, part2: None }
+	}
+}
+
+#[cfg(test)]
+impl Reference for Summed {
+	fn reference_part1(&self, input: &str) -> Option<u64> {
+		Some(self.parse(input).iter().take(4).sum())
+	}
+}
+
+#[test]
+fn cross_checked() {
+	assert_eq!(cross_check(Summed, 0..0, &()), Ok(0));
+
+	let first = (0..).find(|&seed| Summed.generate(seed, &()).input.split(' ').skip(4).any(|n| n != "0")).unwrap();
+	let divergence = cross_check(Summed, 0..100, &()).unwrap_err();
+	assert_eq!((divergence.part, divergence.seed), (1, first));
+	assert_eq!(divergence.input, Summed.generate(first, &()).input);
+	assert!(divergence.to_string().starts_with(&format!("day 0 part 1 answered {}, but the reference answered {}", divergence.solver, divergence.reference)));
+	// only part 1 has a reference to compare against
+	assert_eq!(cross_check(Summed, 0..first, &()), Ok(first as usize));
+}
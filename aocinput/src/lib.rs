@@ -0,0 +1,54 @@
+//! Helpers shared by each day for turning raw puzzle input into parsed data.
+use aoch::AoCDay;
+
+mod span;
+pub mod answers;
+pub mod source;
+pub mod arith;
+pub mod explain;
+pub mod normalize;
+pub mod visual;
+#[cfg(feature = "gen")]
+pub mod gen;
+
+pub use span::{Span, Located, from_lines};
+pub use source::{Input, Source};
+pub use arith::Overflow;
+pub use answers::Answer;
+pub use explain::{Explain, Explanation};
+pub use visual::{Animation, Visualize};
+
+/// A fallible version of [`AoCDay::parse`], reporting where a malformed input went wrong instead of panicking.
+///
+/// Each day implements `AoCDay::parse` by unwrapping this, so the two always agree. Every input is put through
+/// [`normalize`](normalize::normalize) before it is parsed, whether it was read through a [`Source`] or is one
+/// of the tests' samples, so parsers can assume `\n` line endings and no trailing whitespace.
+pub trait TryParse: AoCDay {
+	type Error: std::error::Error;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error>;
+}
+
+/// Why a part of an input that parsed couldn't be solved
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SolveError {
+	#[error(transparent)]
+	Overflow(#[from] Overflow),
+	/// The input is missing something only this part needs, so it wasn't rejected when it was parsed
+	#[error("{0}")]
+	Unsolvable(String),
+}
+
+/// A fallible version of [`AoCDay::part1`] and [`AoCDay::part2`], for days whose arithmetic can overflow, or
+/// whose parts need more of the input than the other does.
+///
+/// By default these just solve the part. Days that can fail override them instead, and implement the infallible
+/// parts by unwrapping these.
+pub trait TrySolve: AoCDay {
+	fn try_part1(&self, data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		Ok(self.part1(data))
+	}
+	fn try_part2(&self, data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		Ok(self.part2(data))
+	}
+}
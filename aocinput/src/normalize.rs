@@ -0,0 +1,145 @@
+//! Cleans up puzzle input before any day parses it, so that the parsers only ever have to deal with `\n`
+//! separated lines without any stray whitespace.
+//!
+//! Every [`Input`](crate::Input) read from a [`Source`](crate::Source) is normalised, and each change made to it
+//! is logged as a warning. Blank lines at the start are kept, so that line numbers in the warnings and in any
+//! parse errors are the same as in the file.
+use std::borrow::Cow;
+use std::fmt;
+
+/// Something that was changed about the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+	/// The input started with a UTF-8 byte order mark
+	Bom,
+	/// How many lines ended with `\r\n` or a lone `\r`
+	LineEndings(usize),
+	/// How many blank lines were at the end of the input
+	TrailingBlankLines(usize),
+	/// A line ended with spaces or tabs
+	TrailingWhitespace { line: usize },
+	/// A line had tabs within it, which were each replaced by a space
+	Tabs { line: usize },
+}
+
+impl fmt::Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Change::Bom => write!(f, "removed a byte order mark"),
+			Change::LineEndings(n) => write!(f, "converted {} CRLF or CR line endings to LF", n),
+			Change::TrailingBlankLines(n) => write!(f, "removed {} blank lines from the end", n),
+			Change::TrailingWhitespace { line } => write!(f, "line {}: removed trailing whitespace", line),
+			Change::Tabs { line } => write!(f, "line {}: replaced tabs with spaces", line),
+		}
+	}
+}
+
+/// Normalised text, along with what had to change to get it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized<'a> {
+	pub text: Cow<'a, str>,
+	pub changes: Vec<Change>,
+}
+
+/// Splits text into lines on `\r\n`, `\r` or `\n`, keeping whether each line had an ending and whether it was LF.
+fn lines(text: &str) -> impl Iterator<Item = (&str, Option<bool>)> {
+	let mut rest = text;
+	std::iter::from_fn(move || {
+		if rest.is_empty() {
+			return None;
+		}
+		let Some(end) = rest.find(['\r', '\n']) else {
+			return Some((std::mem::take(&mut rest), None));
+		};
+		let line = &rest[..end];
+		let (len, lf) = match &rest[end..end+1] {
+			"\n" => (1, true),
+			_ if rest[end+1..].starts_with('\n') => (2, false),
+			_ => (1, false),
+		};
+		rest = &rest[end+len..];
+		Some((line, Some(lf)))
+	})
+}
+
+/// Removes a byte order mark, converts line endings to `\n`, removes trailing spaces and tabs from each line,
+/// replaces the tabs within lines with spaces, and removes blank lines from the end. Whether the last line ended
+/// with a newline is kept as it was.
+///
+/// The text is only copied if something needed to change.
+pub fn normalize(text: &str) -> Normalized<'_> {
+	let mut changes = Vec::new();
+	let body = match text.strip_prefix('\u{feff}') {
+		Some(body) => {
+			changes.push(Change::Bom);
+			body
+		},
+		None => text,
+	};
+
+	let all = lines(body).collect::<Vec<_>>();
+	let blank = |(line, _): &&(&str, _)| line.trim().is_empty();
+	let trailing = all.iter().rev().take_while(blank).count();
+	let kept = &all[..all.len()-trailing];
+
+	let crlf = kept.iter().filter(|(_, lf)| *lf == Some(false)).count();
+	if crlf > 0 {
+		changes.push(Change::LineEndings(crlf));
+	}
+
+	let mut out = String::with_capacity(body.len());
+	for (i, (line, ending)) in kept.iter().enumerate() {
+		let trimmed = line.trim_end_matches([' ', '\t']);
+		if trimmed.len() != line.len() {
+			changes.push(Change::TrailingWhitespace { line: i+1 });
+		}
+		if trimmed.contains('\t') {
+			changes.push(Change::Tabs { line: i+1 });
+			out.push_str(&trimmed.replace('\t', " "));
+		} else {
+			out.push_str(trimmed);
+		}
+		// the last line keeps its ending, or lack of one
+		if i+1 < kept.len() || ending.is_some() {
+			out.push('\n');
+		}
+	}
+	if trailing > 0 {
+		changes.push(Change::TrailingBlankLines(trailing));
+	}
+
+	let text = match changes.is_empty() {
+		true => Cow::Borrowed(text),
+		false => Cow::Owned(out),
+	};
+	Normalized { text, changes }
+}
+
+#[test]
+fn unchanged() {
+	for text in ["", "a\nb\n", "a\n\nb", "\n\na\n", "Time: 7 15\nDistance: 9 40\n"] {
+		let normalized = normalize(text);
+		assert_eq!(normalized.changes, []);
+		assert!(matches!(normalized.text, Cow::Borrowed(t) if t == text));
+	}
+}
+
+#[test]
+fn changed() {
+	let cases = [
+		("\u{feff}a\n", "a\n", vec![Change::Bom]),
+		("a\r\nb\rc\n", "a\nb\nc\n", vec![Change::LineEndings(2)]),
+		("\n \n\na\n\n b\n\t\n\n", "\n\n\na\n\n b\n", vec![Change::TrailingWhitespace { line: 2 }, Change::TrailingBlankLines(2)]),
+		(" \n\t\n", "", vec![Change::TrailingBlankLines(2)]),
+		("a  \nb\t\nc", "a\nb\nc", vec![Change::TrailingWhitespace { line: 1 }, Change::TrailingWhitespace { line: 2 }]),
+		("Time:\t7\t15 \r\n", "Time: 7 15\n", vec![
+			Change::LineEndings(1),
+			Change::TrailingWhitespace { line: 1 },
+			Change::Tabs { line: 1 },
+		]),
+	];
+	for (text, expected, changes) in cases {
+		let normalized = normalize(text);
+		assert_eq!((normalized.text.as_ref(), normalized.changes), (expected, changes), "normalizing {:?}", text);
+	}
+}
@@ -0,0 +1,164 @@
+//! Where puzzle input is read from: the day's embedded input by default, or a file, a directory of files, or stdin.
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use crate::answers::EMBEDDED;
+use crate::normalize::normalize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SourceError {
+	#[error("unable to read {0}: {1}")]
+	Io(String, std::io::Error),
+	#[error("{0} contains no input files")]
+	EmptyDir(String),
+	#[error("expected a path after --input")]
+	MissingPath,
+	#[error("unexpected argument {0:?}, expected `--input <path>`")]
+	UnexpectedArg(String),
+}
+
+/// Where to read puzzle input from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Source {
+	/// The input compiled into the binary
+	#[default]
+	Embedded,
+	Stdin,
+	File(PathBuf),
+	/// Every file directly inside a directory, in name order
+	Dir(PathBuf),
+}
+
+/// A puzzle input along with the name it is known by, which is also what its answers are recorded under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+	pub name: String,
+	pub text: Cow<'static, str>,
+}
+
+impl Input {
+	/// Normalises the text of an input (see [`normalize`]), warning about each change made to it.
+	pub fn new(name: impl Into<String>, text: Cow<'static, str>) -> Input {
+		let name = name.into();
+		let normalized = normalize(&text);
+		for change in &normalized.changes {
+			tracing::warn!(input = %name, "{}", change);
+		}
+		let changed = match normalized.text {
+			Cow::Owned(changed) => Some(changed),
+			Cow::Borrowed(_) => None,
+		};
+		Input { name, text: changed.map_or(text, Cow::Owned) }
+	}
+	pub fn embedded(text: &'static str) -> Input {
+		Input::new(EMBEDDED, Cow::Borrowed(text))
+	}
+}
+
+/// Parses `-` as stdin, or a path as either a file or a directory depending on what is there.
+impl FromStr for Source {
+	type Err = std::convert::Infallible;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"-" => Source::Stdin,
+			path if Path::new(path).is_dir() => Source::Dir(path.into()),
+			path => Source::File(path.into()),
+		})
+	}
+}
+
+/// The name of a file's input, which is its name without the extension
+fn file_input(path: &Path) -> Result<Input, SourceError> {
+	let text = std::fs::read_to_string(path)
+		.map_err(|e| SourceError::Io(path.display().to_string(), e))?;
+	let name = path.file_stem().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+	Ok(Input::new(name, Cow::Owned(text)))
+}
+
+impl Source {
+	/// Picks the source from the process arguments, which may only be `--input <path>` (or `--input=<path>`).
+	pub fn from_args() -> Result<Source, SourceError> {
+		let mut args = std::env::args().skip(1);
+		let source = match args.next() {
+			None => Source::Embedded,
+			Some(arg) if arg == "--input" => args.next().ok_or(SourceError::MissingPath)?.parse().unwrap(),
+			Some(arg) => match arg.strip_prefix("--input=") {
+				Some(path) => path.parse().unwrap(),
+				None => return Err(SourceError::UnexpectedArg(arg)),
+			},
+		};
+		match args.next() {
+			Some(arg) => Err(SourceError::UnexpectedArg(arg)),
+			None => Ok(source),
+		}
+	}
+
+	/// Reads every input from this source, using `embedded` if it is the embedded input.
+	pub fn read(&self, embedded: &'static str) -> Result<Vec<Input>, SourceError> {
+		match self {
+			Source::Embedded => Ok(vec![Input::embedded(embedded)]),
+			Source::Stdin => {
+				let mut text = String::new();
+				std::io::stdin().read_to_string(&mut text)
+					.map_err(|e| SourceError::Io("stdin".into(), e))?;
+				Ok(vec![Input::new("stdin", Cow::Owned(text))])
+			},
+			Source::File(path) => Ok(vec![file_input(path)?]),
+			Source::Dir(dir) => {
+				let io_err = |e| SourceError::Io(dir.display().to_string(), e);
+				let mut paths = std::fs::read_dir(dir).map_err(io_err)?
+					.map(|entry| entry.map(|e| e.path()))
+					.collect::<Result<Vec<_>, _>>().map_err(io_err)?;
+				paths.retain(|p| p.is_file());
+				paths.sort();
+				if paths.is_empty() {
+					return Err(SourceError::EmptyDir(dir.display().to_string()));
+				}
+				paths.iter().map(|p| file_input(p)).collect()
+			},
+		}
+	}
+}
+
+/// Runs a day binary's solver over each input picked by its arguments, defaulting to the `embedded` input.
+///
+/// Inputs other than the embedded one are announced by name before they are solved. Bad arguments or unreadable
+/// input are reported, and exit the process.
+pub fn for_each_input(embedded: &'static str, mut solve: impl FnMut(&str)) {
+	let inputs = Source::from_args().and_then(|source| source.read(embedded));
+	let inputs = match inputs {
+		Ok(inputs) => inputs,
+		Err(e) => {
+			eprintln!("{}", e);
+			std::process::exit(2);
+		}
+	};
+	for input in inputs {
+		if input.name != EMBEDDED {
+			println!("== {} ==", input.name);
+		}
+		solve(&input.text);
+	}
+}
+
+#[test]
+fn sources() {
+	let dir = std::env::temp_dir().join(format!("aocinput-sources-{}", std::process::id()));
+	std::fs::create_dir_all(dir.join("nested")).unwrap();
+	std::fs::write(dir.join("bob.txt"), "2").unwrap();
+	std::fs::write(dir.join("alice.txt"), "1").unwrap();
+	std::fs::write(dir.join("nested").join("carol.txt"), "\u{feff}3 \r\n4\r\n\r\n").unwrap();
+
+	let named = |inputs: Vec<Input>| inputs.into_iter().map(|i| (i.name, i.text.into_owned())).collect::<Vec<_>>();
+	assert_eq!("-".parse(), Ok(Source::Stdin));
+	assert_eq!(named(Source::Embedded.read("0").unwrap()), [(EMBEDDED.into(), "0".into())]);
+	assert_eq!(named(dir.join("bob.txt").to_str().unwrap().parse::<Source>().unwrap().read("").unwrap()), [("bob".into(), "2".into())]);
+	assert_eq!(named(dir.to_str().unwrap().parse::<Source>().unwrap().read("").unwrap()), [("alice".into(), "1".into()), ("bob".into(), "2".into())]);
+	assert_eq!(named(Source::Dir(dir.join("nested")).read("").unwrap()), [("carol".into(), "3\n4\n".into())]);
+	std::fs::remove_file(dir.join("nested").join("carol.txt")).unwrap();
+	assert!(matches!(Source::Dir(dir.join("nested")).read(""), Err(SourceError::EmptyDir(_))));
+	assert!(matches!(Source::File(dir.join("carol.txt")).read(""), Err(SourceError::Io(..))));
+
+	std::fs::remove_dir_all(dir).unwrap();
+}
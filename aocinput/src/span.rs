@@ -0,0 +1,129 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A position within some puzzle input, 1-based like an editor would show it, along with the text found there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+	pub line: usize,
+	pub col: usize,
+	pub text: String,
+}
+
+impl Span {
+	/// Finds where `part` sits within `src`.
+	///
+	/// Panics if `part` is not a subslice of `src`, as the position is found from their pointers.
+	pub fn locate(src: &str, part: &str) -> Span {
+		let offset = (part.as_ptr() as usize).checked_sub(src.as_ptr() as usize)
+			.filter(|o| o + part.len() <= src.len())
+			.expect("span text is not a subslice of its source");
+
+		let before = &src[..offset];
+		let line = before.matches('\n').count() + 1;
+		let col = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+		Span { line, col, text: part.to_owned() }
+	}
+
+	/// Moves a span that was located within `inner` to be relative to `outer` instead.
+	pub fn rebase(self, outer: &str, inner: &str) -> Span {
+		let base = Span::locate(outer, inner);
+		Span {
+			line: base.line + self.line - 1,
+			// only the first line of `inner` is offset by its position on the line
+			col: if self.line == 1 { base.col + self.col - 1 } else { self.col },
+			text: self.text,
+		}
+	}
+}
+
+impl fmt::Display for Span {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "line {}, column {}", self.line, self.col)
+	}
+}
+
+/// An error of kind `K` found at some span of the input.
+///
+/// Each day defines its own enum of error kinds, and wraps it in this to report where it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located<K> {
+	pub span: Span,
+	pub kind: K,
+}
+
+impl<K> Located<K> {
+	/// Creates an error of `kind` for `part`, a subslice of `src`.
+	pub fn new(src: &str, part: &str, kind: K) -> Located<K> {
+		Located { span: Span::locate(src, part), kind }
+	}
+	/// Moves an error that was found within `inner` to be relative to `outer` instead.
+	pub fn rebase(self, outer: &str, inner: &str) -> Located<K> {
+		Located { span: self.span.rebase(outer, inner), kind: self.kind }
+	}
+}
+
+impl<K: fmt::Display> fmt::Display for Located<K> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {} (at {:?})", self.span, self.kind, self.span.text)
+	}
+}
+
+impl<K: fmt::Debug + fmt::Display> Error for Located<K> {}
+
+/// Parses each non-blank line of the input like `aoch::parsing::from_lines`, but with any error
+/// moved from being relative to its line to being relative to the whole input.
+pub fn from_lines<T, K>(input: &str) -> Result<Vec<T>, Located<K>>
+where
+	T: FromStr<Err = Located<K>>,
+{
+	input.lines()
+		.map(str::trim)
+		.filter(|l| !l.is_empty())
+		.map(|line| line.parse().map_err(|e: Located<K>| e.rebase(input, line)))
+		.collect()
+}
+
+#[test]
+fn locate() {
+	const SRC: &str = "abc\ndef ghi\n\njkl";
+	let at = |start: usize, len: usize| {
+		let span = Span::locate(SRC, &SRC[start..start+len]);
+		(span.line, span.col, span.text)
+	};
+	assert_eq!(at(0, 3), (1, 1, "abc".into()));
+	assert_eq!(at(8, 3), (2, 5, "ghi".into()));
+	assert_eq!(at(12, 0), (3, 1, "".into()));
+	assert_eq!(at(13, 3), (4, 1, "jkl".into()));
+}
+
+#[test]
+fn rebase() {
+	const SRC: &str = "first line\n  second: bad\nthird";
+	let line = SRC.lines().nth(1).unwrap().trim();
+	let inner = Span::locate(line, &line[8..]);
+	assert_eq!((inner.line, inner.col), (1, 9));
+	let outer = inner.rebase(SRC, line);
+	assert_eq!((outer.line, outer.col, outer.text.as_str()), (2, 11, "bad"));
+}
+
+#[test]
+fn lines() {
+	#[derive(Debug)]
+	struct Even(u32);
+	impl FromStr for Even {
+		type Err = Located<&'static str>;
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			match s.parse() {
+				Ok(n) if n % 2 == 0 => Ok(Even(n)),
+				_ => Err(Located::new(s, s, "not an even number")),
+			}
+		}
+	}
+
+	let parsed = |input| from_lines::<Even, _>(input)
+		.map(|v| v.iter().map(|e| e.0).collect::<Vec<_>>())
+		.map_err(|e| e.to_string());
+	assert_eq!(parsed("\n2\n 4\n\n  6 \n8"), Ok(vec![2, 4, 6, 8]));
+	assert_eq!(parsed("\n2\n 4\n\n  7 \n8"), Err("line 5, column 3: not an even number (at \"7\")".to_owned()));
+}
@@ -0,0 +1,304 @@
+//! Step-by-step pictures of how a day solves a part, for the runner's interactive visualiser to play through.
+//!
+//! An [`Animation`] is a canvas of characters along with a series of [`Frame`]s. Each frame changes some of the
+//! canvas's cells, and those changes stay for the frames after it, so a frame only needs to hold what changed.
+//! A frame can also focus on some cells, which are only highlighted while that frame is shown.
+use aoch::AoCDay;
+
+/// What a cell shows, which the visualiser picks a colour for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Tone {
+	#[default]
+	Plain,
+	/// Something that doesn't matter to the solution
+	Dim,
+	/// Something the solver has visited or worked out
+	Path,
+	/// Something that stands out, like reaching a goal
+	Highlight,
+	/// One of a set of things told apart by colour
+	Group(u32),
+	Inside,
+	Outside,
+}
+
+impl Tone {
+	/// How much a cell with this tone matters, for choosing which cell of a block to show when zoomed out
+	fn weight(self) -> u8 {
+		match self {
+			Tone::Dim => 0,
+			Tone::Plain => 1,
+			Tone::Group(_) | Tone::Outside => 2,
+			Tone::Inside => 3,
+			Tone::Path => 4,
+			Tone::Highlight => 5,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+	pub glyph: char,
+	pub tone: Tone,
+	/// Whether the current frame focuses on this cell
+	pub focused: bool,
+}
+
+impl Default for Cell {
+	fn default() -> Self {
+		Cell { glyph: ' ', tone: Tone::Plain, focused: false }
+	}
+}
+
+/// A change to a cell, which keeps its glyph if no new one is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+	pub at: (usize, usize),
+	pub glyph: Option<char>,
+	pub tone: Tone,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+	/// What happened in this frame, shown alongside it
+	pub caption: String,
+	/// Changes to the canvas, which stay for the frames after this one
+	pub marks: Vec<Mark>,
+	/// Cells to highlight while only this frame is shown
+	pub focus: Vec<(usize, usize)>,
+}
+
+impl Frame {
+	/// Changes a cell's glyph and tone.
+	pub fn set(&mut self, at: (usize, usize), glyph: char, tone: Tone) -> &mut Self {
+		self.marks.push(Mark { at, glyph: Some(glyph), tone });
+		self
+	}
+	/// Changes a cell's tone, keeping its glyph.
+	pub fn tone(&mut self, at: (usize, usize), tone: Tone) -> &mut Self {
+		self.marks.push(Mark { at, glyph: None, tone });
+		self
+	}
+	/// Writes text across a row, starting at `at`.
+	pub fn text(&mut self, (x, y): (usize, usize), text: &str, tone: Tone) -> &mut Self {
+		for (i, c) in text.chars().enumerate() {
+			self.set((x + i, y), c, tone);
+		}
+		self
+	}
+	pub fn focus(&mut self, at: (usize, usize)) -> &mut Self {
+		self.focus.push(at);
+		self
+	}
+}
+
+/// The cells of an animation as they are at one of its frames
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+	width: usize,
+	height: usize,
+	cells: Vec<Cell>,
+	/// Which frames have been applied, or `None` for none of them
+	frame: Option<usize>,
+	focus: Vec<(usize, usize)>,
+}
+
+impl Canvas {
+	pub fn width(&self) -> usize {
+		self.width
+	}
+	pub fn height(&self) -> usize {
+		self.height
+	}
+	/// The last frame that was applied, or `None` if the canvas is as it was before the first one
+	pub fn frame(&self) -> Option<usize> {
+		self.frame
+	}
+
+	pub fn get(&self, (x, y): (usize, usize)) -> Option<Cell> {
+		(x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+	}
+
+	fn set_focus(&mut self, focused: bool) {
+		for &(x, y) in &self.focus {
+			if x < self.width && y < self.height {
+				self.cells[y * self.width + x].focused = focused;
+			}
+		}
+	}
+
+	/// Applies the next frame of an animation, changing its cells and moving the focus to the frame's.
+	fn apply(&mut self, index: usize, frame: &Frame) {
+		self.set_focus(false);
+		for mark in &frame.marks {
+			let (x, y) = mark.at;
+			// marks off the canvas are ignored, like drawing past the edge of a picture
+			if x >= self.width || y >= self.height { continue; }
+			let cell = &mut self.cells[y * self.width + x];
+			cell.glyph = mark.glyph.unwrap_or(cell.glyph);
+			cell.tone = mark.tone;
+		}
+		self.focus.clone_from(&frame.focus);
+		self.set_focus(true);
+		self.frame = Some(index);
+	}
+
+	/// A `width` by `height` window onto the canvas from `origin`, where each cell of the window stands for a
+	/// `zoom` by `zoom` block of the canvas. Each block is shown as whichever of its cells matters most: a
+	/// focused one, otherwise the one with the weightiest tone. Cells past the edge of the canvas are `None`.
+	pub fn view(&self, origin: (usize, usize), zoom: usize, width: usize, height: usize) -> Vec<Vec<Option<Cell>>> {
+		let zoom = zoom.max(1);
+		(0..height)
+			.map(|vy| {
+				(0..width)
+					.map(|vx| {
+						let (bx, by) = (origin.0 + vx * zoom, origin.1 + vy * zoom);
+						(by..by + zoom)
+							.flat_map(|y| (bx..bx + zoom).map(move |x| (x, y)))
+							.filter_map(|at| self.get(at))
+							.max_by_key(|cell| (cell.focused, cell.tone.weight(), cell.glyph != ' '))
+					})
+					.collect()
+			})
+			.collect()
+	}
+}
+
+/// A picture of a day's state, and the frames that change it as the solver works through the part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+	pub title: String,
+	width: usize,
+	height: usize,
+	base: Vec<Cell>,
+	frames: Vec<Frame>,
+}
+
+impl Animation {
+	/// An animation starting from some text, one row per line.
+	pub fn new<R: AsRef<str>>(title: impl Into<String>, rows: impl IntoIterator<Item = R>) -> Animation {
+		let rows = rows.into_iter()
+			.map(|r| r.as_ref().chars().collect::<Vec<_>>())
+			.collect::<Vec<_>>();
+		let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+		let mut animation = Animation::blank(title, width, rows.len());
+		for (y, row) in rows.iter().enumerate() {
+			for (x, &glyph) in row.iter().enumerate() {
+				animation.base[y * width + x].glyph = glyph;
+			}
+		}
+		animation
+	}
+	/// An animation starting from a blank canvas.
+	pub fn blank(title: impl Into<String>, width: usize, height: usize) -> Animation {
+		Animation { title: title.into(), width, height, base: vec![Cell::default(); width * height], frames: Vec::new() }
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+	pub fn height(&self) -> usize {
+		self.height
+	}
+	pub fn frames(&self) -> &[Frame] {
+		&self.frames
+	}
+
+	/// Adds a frame to the end of the animation, to be filled in.
+	pub fn push(&mut self, caption: impl Into<String>) -> &mut Frame {
+		self.frames.push(Frame { caption: caption.into(), ..Frame::default() });
+		self.frames.last_mut().unwrap()
+	}
+
+	/// The canvas before any frames are applied
+	pub fn start(&self) -> Canvas {
+		Canvas { width: self.width, height: self.height, cells: self.base.clone(), frame: None, focus: Vec::new() }
+	}
+
+	/// The canvas as it is at a frame, which is the last one if `frame` is past the end.
+	pub fn canvas(&self, frame: usize) -> Canvas {
+		let mut canvas = self.start();
+		self.seek(&mut canvas, frame);
+		canvas
+	}
+
+	/// Moves a canvas of this animation to another frame, only applying the frames in between when it is
+	/// moving forwards.
+	pub fn seek(&self, canvas: &mut Canvas, frame: usize) {
+		if self.frames.is_empty() {
+			return;
+		}
+		let frame = frame.min(self.frames.len() - 1);
+		if canvas.frame.is_some_and(|current| current > frame) {
+			*canvas = self.start();
+		}
+		let next = canvas.frame.map_or(0, |current| current + 1);
+		for (i, f) in self.frames.iter().enumerate().take(frame + 1).skip(next) {
+			canvas.apply(i, f);
+		}
+	}
+}
+
+/// Animates how a day solves each of its parts, for the days that can. Days without an animation for a part
+/// return `None`, which they do by default.
+pub trait Visualize: AoCDay {
+	fn visualize_part1(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		None
+	}
+	fn visualize_part2(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		None
+	}
+}
+
+#[test]
+fn frames() {
+	let mut animation = Animation::new("test", ["ab", "c"]);
+	animation.push("first").set((0, 0), 'x', Tone::Path).focus((1, 1));
+	animation.push("second").tone((1, 0), Tone::Highlight).text((0, 1), "yz!", Tone::Dim);
+	animation.push("third").focus((0, 0));
+	assert_eq!((animation.width(), animation.height(), animation.frames().len()), (2, 2, 3));
+
+	let glyphs = |canvas: &Canvas| (0..2).map(|y| (0..2).map(|x| canvas.get((x, y)).unwrap().glyph).collect::<String>()).collect::<Vec<_>>();
+	let start = animation.start();
+	assert_eq!(glyphs(&start), ["ab", "c "]);
+	assert_eq!(start.frame(), None);
+
+	let first = animation.canvas(0);
+	assert_eq!(glyphs(&first), ["xb", "c "]);
+	assert_eq!(first.get((0, 0)).unwrap().tone, Tone::Path);
+	assert!(first.get((1, 1)).unwrap().focused);
+
+	let third = animation.canvas(2);
+	assert_eq!(glyphs(&third), ["xb", "yz"]);
+	assert_eq!(third.get((1, 0)).unwrap().tone, Tone::Highlight);
+	// marks stay, focus doesn't
+	assert!(!third.get((1, 1)).unwrap().focused);
+	assert!(third.get((0, 0)).unwrap().focused);
+	assert_eq!(animation.canvas(10), third);
+
+	// seeking backwards and forwards ends up the same as starting over
+	let mut canvas = animation.canvas(2);
+	animation.seek(&mut canvas, 0);
+	assert_eq!(canvas, first);
+	animation.seek(&mut canvas, 2);
+	assert_eq!(canvas, third);
+}
+
+#[test]
+fn zoomed() {
+	let mut animation = Animation::new("zoom", ["    #", "", "  @", ""]);
+	animation.push("mark").tone((2, 2), Tone::Path).focus((1, 3));
+	let canvas = animation.canvas(0);
+
+	let glyphs = |view: &[Vec<Option<Cell>>]| view.iter()
+		.map(|row| row.iter().map(|c| c.map_or('~', |c| c.glyph)).collect::<String>())
+		.collect::<Vec<_>>();
+	assert_eq!(glyphs(&canvas.view((0, 0), 1, 6, 2)), ["    #~", "     ~"]);
+	assert_eq!(glyphs(&canvas.view((3, 1), 1, 3, 4)), ["  ~", "  ~", "  ~", "~~~"]);
+	// zoomed out, blocks show their focused cell, then their most important
+	let view = canvas.view((0, 0), 2, 3, 2);
+	assert_eq!(glyphs(&view), ["  #", " @ "]);
+	assert!(view[1][0].unwrap().focused);
+	assert_eq!(view[1][1].unwrap().tone, Tone::Path);
+	assert_eq!(glyphs(&canvas.view((0, 0), 8, 2, 1)), [" ~"]);
+}
@@ -0,0 +1,67 @@
+use aocinput::gen::{rng, Generate, Generated, Rng};
+use crate::Day01;
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+/// Letters that aren't in any digit's name, so filler can never spell out a digit of its own
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub lines: usize,
+	/// The most digits, spelled out or not, on a single line
+	pub digits: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { lines: 1000, digits: 6 }
+	}
+}
+
+impl Generate for Day01 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let mut input = String::new();
+		let (mut part1, mut part2) = (0, 0);
+
+		for _ in 0..params.lines {
+			let count = rng.gen_range(1..=params.digits.max(1));
+			// every line needs a numeric digit for part 1
+			let numeric = rng.gen_range(0..count);
+			let digits: Vec<(usize, bool)> = (0..count)
+				.map(|i| (rng.gen_range(1..=9), i == numeric || rng.gen_bool(0.5)))
+				.collect();
+
+			for (i, &(value, is_numeric)) in digits.iter().enumerate() {
+				// keep digits apart, so spelled out ones can't run together into another
+				let min_filler = if i == 0 { 0 } else { 1 };
+				for _ in 0..rng.gen_range(min_filler..=3) {
+					input.push(FILLER[rng.gen_range(0..FILLER.len())] as char);
+				}
+				if is_numeric {
+					input.push(char::from_digit(value as u32, 10).unwrap());
+				} else {
+					input.push_str(WORDS[value-1]);
+				}
+			}
+			for _ in 0..rng.gen_range(0..=3) {
+				input.push(FILLER[rng.gen_range(0..FILLER.len())] as char);
+			}
+			input.push('\n');
+
+			let mut numerics = digits.iter().filter(|(_, n)| *n).map(|(v, _)| *v);
+			let first = numerics.next().unwrap();
+			part1 += first*10 + numerics.next_back().unwrap_or(first);
+			part2 += digits[0].0*10 + digits[count-1].0;
+		}
+
+		Generated { input, part1: Some(part1), part2: Some(part2) }
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day01, 0..20, &Params { lines: 50, ..Params::default() });
+}
@@ -3,13 +3,26 @@ use std::str::FromStr;
 use std::fmt::Debug;
 use itertools::Itertools;
 use aoch::AoCDay;
+use aocinput::{Explain, Explanation, Located, SolveError, TryParse, TrySolve, Visualize};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 use regex::{Regex, Captures};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Day01;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("line does not contain any digits, numeric or spelled out")]
+	NoDigits,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 lazy_static::lazy_static! {
 	static ref ENGLISH_DIGITS: Regex = Regex::new(r#"([1-9])|(one)|(two)|(three)|(four)|(five)|(six)|(seven)|(eight)|(nine)"#).unwrap();
 }
@@ -40,6 +53,21 @@ impl Day01 {
 		Some((first, last))
 	}
 
+	/// Explains each line's calibration value, as found by `find`
+	fn explain(data: &[String], find: fn(&str) -> Option<(u8, u8)>) -> Explanation {
+		let mut explanation = Explanation::default();
+		let mut total = 0;
+		for (si, s) in data.iter().enumerate() {
+			let Some((f, l)) = find(s) else {
+				explanation.step(format!("line {}", si+1), format!("{:?} -> no digits", s));
+				continue;
+			};
+			total += (f*10 + l) as usize;
+			explanation.step(format!("line {}", si+1), format!("{:?} -> {}{}, total {}", s, f, l, total));
+		}
+		explanation
+	}
+
 	fn extract_capture_value(capture: &Captures<'_>) -> u8 {
 		capture.iter()
 			.enumerate()
@@ -66,40 +94,67 @@ impl AoCDay for Day01 {
 	fn day(&self) -> u8 { 1 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		aoch::parsing::from_lines(input).unwrap()
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, data: &mut Self::Data<'_>) -> Self::Answer {
+		self.try_part1(data).unwrap_or_else(|e| panic!("{}", e))
+	}
+	fn part2(&self, data: &mut Self::Data<'_>) -> Self::Answer {
 		data.iter()
-			.enumerate()
-			.map(|(_si, s)| {
-				let (f, l) = Day01::find_numeric_digits(s).unwrap();
-				// eprintln!("{}: {:?} -> {:?}", _si, s, (f, l));
+			.map(|s| {
+				let (f, l) = Day01::find_english_digits(s).unwrap();
 				(f*10 + l) as usize
 			})
 			.sum()
 	}
-	fn part2(&self, data: &mut Self::Data<'_>) -> Self::Answer {
+}
+
+impl TrySolve for Day01 {
+	// parsing only checks for digits that are either numeric or spelled out, as part 2's lines can have no
+	// numeric ones
+	fn try_part1(&self, data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
 		data.iter()
-			.enumerate()
-			.map(|(_si, s)| {
-				let (f, l) = Day01::find_english_digits(s).unwrap();
-				// eprintln!("{}: {:?} -> {:?}", _si, s, (f, l));
-				(f*10 + l) as usize
+			.map(|s| match Day01::find_numeric_digits(s) {
+				Some((f, l)) => Ok((f*10 + l) as usize),
+				None => Err(SolveError::Unsolvable(format!("{:?} has no numeric digits", s))),
 			})
 			.sum()
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT_P1: &'static str = "
+impl Explain for Day01 {
+	fn explain_part1(&self, data: &mut Self::Data<'_>) -> Option<Explanation> {
+		Some(Day01::explain(data, Day01::find_numeric_digits))
+	}
+	fn explain_part2(&self, data: &mut Self::Data<'_>) -> Option<Explanation> {
+		Some(Day01::explain(data, Day01::find_english_digits))
+	}
+}
+
+impl Visualize for Day01 {}
+
+impl TryParse for Day01 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		input.lines()
+			.filter_map(aoch::parsing::trimmed)
+			.map(|line| match ENGLISH_DIGITS.is_match(line) {
+				true => Ok(line.to_owned()),
+				false => Err(ParseError::new(input, line, ParseErrorKind::NoDigits)),
+			})
+			.collect()
+	}
+}
+
+pub const TEST_INPUT_P1: &'static str = "
 1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet
 ";
 
-#[cfg(test)]
-const TEST_INPUT_P2: &'static str = "
+pub const TEST_INPUT_P2: &'static str = "
 two1nine
 eightwothree
 abcone2threexyz
@@ -113,7 +168,6 @@ zoneight234
 fn part1() {
 	let cases = [
 		(TEST_INPUT_P1, 142),
-		(daystr!("01"), 55208),
 	];
 	test_runner::<Day01, _>(Day01, DayPart::Part1, &cases);
 }
@@ -121,7 +175,29 @@ fn part1() {
 fn part2() {
 	let cases = [
 		(TEST_INPUT_P2, 281),
-		(daystr!("01"), 54578),
 	];
 	test_runner::<Day01, _>(Day01, DayPart::Part2, &cases);
 }
+
+#[test]
+fn explain() {
+	let lines = Day01.parse(TEST_INPUT_P2);
+	let explanation = Day01.explain_part2(&mut lines.clone()).unwrap();
+	assert_eq!(explanation.steps.len(), 7);
+	assert_eq!(explanation.steps[1].detail, "\"eightwothree\" -> 83, total 112");
+	assert!(explanation.steps[6].detail.ends_with("total 281"));
+}
+
+#[test]
+fn spelled_out_only() {
+	let mut lines = Day01.try_parse("1abc2\nonetwo").unwrap();
+	assert_eq!(Day01.try_part1(&mut lines), Err(SolveError::Unsolvable("\"onetwo\" has no numeric digits".into())));
+	assert_eq!(Day01.try_part2(&mut lines), Ok(24));
+	let explanation = Day01.explain_part1(&mut lines).unwrap();
+	assert_eq!(explanation.steps[1].detail, "\"onetwo\" -> no digits");
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day01, daystr!("01"));
+}
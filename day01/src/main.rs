@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day01::Day01;
 
 fn main() {
-	run_day::<Day01>(Day01, daystr!("01"), None);
+	aocinput::source::for_each_input(daystr!("01"), |input| {
+		run_day::<Day01>(Day01, input, None);
+	});
 }
 
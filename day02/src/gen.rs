@@ -0,0 +1,67 @@
+use aocinput::gen::{rng, Generate, Generated, Rng, SliceRandom};
+use crate::Day02;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+/// The bag's contents from part 1
+const BAG: [usize; 3] = [12, 13, 14];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub games: usize,
+	/// The most plays in a single game
+	pub plays: usize,
+	/// The most cubes of one color shown in a single play
+	pub cubes: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { games: 100, plays: 6, cubes: 20 }
+	}
+}
+
+impl Generate for Day02 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let mut input = String::new();
+		let (mut part1, mut part2) = (0, 0);
+
+		for id in 1..=params.games {
+			let mut most = [0; 3];
+			let plays = (0..rng.gen_range(1..=params.plays.max(1)))
+				.map(|_| {
+					// any non-empty subset of the colors, in any order
+					let mut shown = Vec::new();
+					while shown.is_empty() {
+						shown = (0..3).filter(|_| rng.gen_bool(0.6)).collect();
+					}
+					shown.shuffle(&mut rng);
+
+					shown.iter()
+						.map(|&c| {
+							let count = rng.gen_range(1..=params.cubes.max(1));
+							most[c] = most[c].max(count);
+							format!("{} {}", count, COLORS[c])
+						})
+						.collect::<Vec<_>>()
+						.join(", ")
+				})
+				.collect::<Vec<_>>();
+			input += &format!("Game {}: {}\n", id, plays.join("; "));
+
+			if most.iter().zip(BAG).all(|(m, b)| *m <= b) {
+				part1 += id;
+			}
+			part2 += most.iter().product::<usize>();
+		}
+
+		Generated { input, part1: Some(part1), part2: Some(part2) }
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day02, 0..20, &Params::default());
+}
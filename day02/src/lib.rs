@@ -1,54 +1,77 @@
 #![allow(unused_imports)]
-use std::{str::FromStr, os::raw};
+use std::{fmt, str::FromStr, os::raw};
 use std::fmt::Debug;
 use itertools::Itertools;
 use aoch::AoCDay;
+use aocinput::{Explain, Explanation, Located, TryParse, TrySolve, Visualize};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Day02;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a `Game <id>: ` prefix")]
+	MissingGameId,
+	#[error("expected a `<count> <color>` pair")]
+	MissingColor,
+	#[error("invalid number: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+	#[error("unknown color")]
+	UnknownColor,
+	#[error("color is repeated within the same play")]
+	RepeatedColor,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game {
 	id: usize,
 	plays: Vec<Rgb>
 }
 impl FromStr for Game {
-	type Err = ();
+	type Err = ParseError;
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let (raw_gid, raw_plays) = s.split_once(": ").unwrap();
-		let (_pre, gid) = raw_gid.split_once(' ').unwrap();
-		let id: usize = gid.parse().unwrap();
+		let err = |part: &str, kind| ParseError::new(s, part, kind);
+
+		let (raw_gid, raw_plays) = s.split_once(": ").ok_or_else(|| err(s, ParseErrorKind::MissingGameId))?;
+		let (_pre, gid) = raw_gid.split_once(' ').ok_or_else(|| err(raw_gid, ParseErrorKind::MissingGameId))?;
+		let id: usize = gid.parse().map_err(|e| err(gid, ParseErrorKind::InvalidNumber(e)))?;
 
 		let plays = raw_plays.split("; ")
-			.enumerate()
-			.map(|(play, f)| {
+			.map(|f| {
 				let mut r = Option::None;
 				let mut g = Option::None;
 				let mut b = Option::None;
 
-				f.split(", ")
-					.map(|k| k.split_once(' ').unwrap())
-					.for_each(|(num, color)| {
-						let count = num.parse().unwrap();
-						let opt = match color {
-							"red" => &mut r,
-							"green" => &mut g,
-							"blue" => &mut b,
-							_ => panic!("unknown color: {:?}", color),
-						};
-						assert!(opt.is_none(), "game {}, play {} has repeated colors!", id, play);
-						*opt = Some(count);
-					});
-
-				Rgb {
+				for k in f.split(", ") {
+					let (num, color) = k.split_once(' ').ok_or_else(|| err(k, ParseErrorKind::MissingColor))?;
+					let count = num.parse().map_err(|e| err(num, ParseErrorKind::InvalidNumber(e)))?;
+					let opt = match color {
+						"red" => &mut r,
+						"green" => &mut g,
+						"blue" => &mut b,
+						_ => return Err(err(color, ParseErrorKind::UnknownColor)),
+					};
+					if opt.is_some() {
+						return Err(err(color, ParseErrorKind::RepeatedColor));
+					}
+					*opt = Some(count);
+				}
+
+				Ok(Rgb {
 					red: r.unwrap_or_default(),
 					green: g.unwrap_or_default(),
 					blue: b.unwrap_or_default(),
-				}
+				})
 			})
-			.collect();
+			.collect::<Result<_, _>>()?;
 
 		Ok(Game {
 			id,
@@ -57,7 +80,17 @@ impl FromStr for Game {
 	}
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Formats the game as it would appear in the input.
+impl fmt::Display for Game {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Game {}: {}", self.id, self.plays.iter().join("; "))
+	}
+}
+
+/// The cubes in the bag for part 1
+const MAX: Rgb = Rgb { red: 12, green: 13, blue: 14 };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Rgb {
 	red: usize,
 	green: usize,
@@ -78,6 +111,19 @@ impl Rgb {
 	}
 }
 
+impl fmt::Display for Rgb {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let colors = [(self.red, "red"), (self.green, "green"), (self.blue, "blue")];
+		let shown = colors.iter().filter(|(n, _)| *n > 0).map(|(n, c)| format!("{n} {c}")).join(", ");
+		// a play has to name at least one color to be parsed back
+		if shown.is_empty() {
+			write!(f, "0 red")
+		} else {
+			write!(f, "{}", shown)
+		}
+	}
+}
+
 impl AoCDay for Day02 {
 	type Data<'i> = Vec<Game>;
 	type Answer = usize;
@@ -85,10 +131,9 @@ impl AoCDay for Day02 {
 	fn day(&self) -> u8 { 2 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		aoch::parsing::from_lines(input).unwrap()
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		const MAX: Rgb = Rgb { red: 12, green: 13, blue: 14 };
 		_data.iter()
 			// only the plays that fit within our limit above
 			.filter(|game| game.plays.iter().all(|p| p.is_subset_of(&MAX)))
@@ -109,8 +154,48 @@ impl AoCDay for Day02 {
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+impl TrySolve for Day02 {}
+
+impl Explain for Day02 {
+	fn explain_part1(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		let mut explanation = Explanation::default();
+		let mut total = 0;
+		for game in _data.iter() {
+			let detail = match game.plays.iter().find(|p| !p.is_subset_of(&MAX)) {
+				Some(play) => format!("impossible, drew {} from {}", play, MAX),
+				None => {
+					total += game.id;
+					format!("possible, total {}", total)
+				},
+			};
+			explanation.step(format!("Game {}", game.id), detail);
+		}
+		Some(explanation)
+	}
+	fn explain_part2(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		let mut explanation = Explanation::default();
+		let mut total = 0;
+		for game in _data.iter() {
+			let rgb = game.plays.iter().copied().reduce(|acc, o| acc.max(&o)).unwrap();
+			let power = rgb.red * rgb.green * rgb.blue;
+			total += power;
+			explanation.step(format!("Game {}", game.id), format!("needs {}, power {}, total {}", rgb, power, total));
+		}
+		Some(explanation)
+	}
+}
+
+impl Visualize for Day02 {}
+
+impl TryParse for Day02 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		aocinput::from_lines(input)
+	}
+}
+
+pub const TEST_INPUT: &'static str = "
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -128,11 +213,25 @@ fn fuel_calc() {
 }
 */
 
+#[test]
+fn parse_errors() {
+	let cases = [
+		("Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 purple", Some((2, 19, "purple".into()))),
+		("Game 1: 3 blue, 4 red; 2 blue, 5 blue", Some((1, 34, "blue".into()))),
+		("Game one: 3 blue", Some((1, 6, "one".into()))),
+		("Game 1 3 blue", Some((1, 1, "Game 1 3 blue".into()))),
+		("Game 1: 3 blue, 4 red", None),
+	];
+	run_test(|input| {
+		Day02.try_parse(input).err()
+			.map(|e| (e.span.line, e.span.col, e.span.text))
+	}, &cases);
+}
+
 #[test]
 fn part1() {
 	let cases = [
 		(TEST_INPUT, 8),
-		(daystr!("02"), 3099),
 	];
 	test_runner::<Day02, _>(Day02, DayPart::Part1, &cases);
 }
@@ -141,7 +240,24 @@ fn part1() {
 fn part2() {
 	let cases = [
 		(TEST_INPUT, 2286),
-		(daystr!("02"), 72970),
 	];
 	test_runner::<Day02, _>(Day02, DayPart::Part2, &cases);
 }
+
+#[test]
+fn explain() {
+	let mut games = Day02.parse(TEST_INPUT);
+	let explanation = Day02.explain_part1(&mut games).unwrap();
+	assert_eq!(explanation.steps[1].detail, "possible, total 3");
+	assert_eq!(explanation.steps[2].detail, "impossible, drew 20 red, 8 green, 6 blue from 12 red, 13 green, 14 blue");
+	assert_eq!(explanation.steps[4].detail, "possible, total 8");
+
+	let explanation = Day02.explain_part2(&mut games).unwrap();
+	assert_eq!(explanation.steps[0].detail, "needs 4 red, 2 green, 6 blue, power 48, total 48");
+	assert!(explanation.steps[4].detail.ends_with("total 2286"));
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day02, daystr!("02"));
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day02::Day02;
 
 fn main() {
-	run_day::<Day02>(Day02, daystr!("02"), None);
+	aocinput::source::for_each_input(daystr!("02"), |input| {
+		run_day::<Day02>(Day02, input, None);
+	});
 }
 
@@ -0,0 +1,137 @@
+use std::collections::{BTreeSet, HashMap};
+use itertools::Itertools;
+use aocinput::gen::{rng, Generate, Generated, Reference, Rng};
+use crate::Day03;
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub width: usize,
+	pub height: usize,
+	/// How many part numbers to try placing. Some may not fit if the schematic is crowded.
+	pub numbers: usize,
+	/// How many symbols to try placing
+	pub symbols: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { width: 140, height: 140, numbers: 1200, symbols: 750 }
+	}
+}
+
+impl Generate for Day03 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let Params { width, height, .. } = *params;
+		let mut tiles = vec![vec![b'.'; width]; height];
+
+		// (x, y, digits, value)
+		let mut numbers = Vec::new();
+		for _ in 0..params.numbers {
+			let value: usize = rng.gen_range(1..1000);
+			let digits = value.to_string();
+			if digits.len() > width { continue; }
+			let (x, y) = (rng.gen_range(0..=width-digits.len()), rng.gen_range(0..height));
+
+			// leave a gap on either side, so it doesn't join up with another number
+			let around = x.saturating_sub(1)..(x+digits.len()+1).min(width);
+			if tiles[y][around].iter().all(|t| *t == b'.') {
+				tiles[y][x..x+digits.len()].copy_from_slice(digits.as_bytes());
+				numbers.push((x, y, digits.len(), value));
+			}
+		}
+		for _ in 0..params.symbols {
+			let (x, y) = (rng.gen_range(0..width), rng.gen_range(0..height));
+			if tiles[y][x] == b'.' {
+				tiles[y][x] = SYMBOLS[rng.gen_range(0..SYMBOLS.len())];
+			}
+		}
+
+		let mut part1 = 0;
+		let mut gears: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+		for &(x, y, len, value) in &numbers {
+			let mut adjacent = false;
+			let around = (y.saturating_sub(1)..=(y+1).min(height-1))
+				.cartesian_product(x.saturating_sub(1)..=(x+len).min(width-1));
+			for (ay, ax) in around {
+				let t = tiles[ay][ax];
+				if t != b'.' && !t.is_ascii_digit() {
+					adjacent = true;
+					if t == b'*' {
+						gears.entry((ax, ay)).or_default().push(value);
+					}
+				}
+			}
+			if adjacent {
+				part1 += value;
+			}
+		}
+		let part2 = gears.values()
+			.filter(|nums| nums.len() == 2)
+			.map(|nums| nums[0] * nums[1])
+			.sum();
+
+		let input = tiles.into_iter()
+			.map(|row| String::from_utf8(row).unwrap() + "\n")
+			.collect();
+		Generated { input, part1: Some(part1), part2: Some(part2) }
+	}
+}
+
+/// A number in the schematic, as (row, start column, value)
+type Number = (usize, usize, usize);
+
+/// Each symbol, along with the numbers in the cells around it, found by checking each of those cells for a
+/// digit and walking left to where its number starts.
+fn symbol_neighbours(input: &str) -> Vec<(u8, BTreeSet<Number>)> {
+	let rows: Vec<&[u8]> = input.trim().lines().map(str::as_bytes).collect();
+	let mut symbols = Vec::new();
+	for (y, row) in rows.iter().enumerate() {
+		for (x, &c) in row.iter().enumerate() {
+			if c == b'.' || c.is_ascii_digit() { continue; }
+			let mut numbers = BTreeSet::new();
+			let around = (y.saturating_sub(1)..=(y+1).min(rows.len()-1))
+				.cartesian_product(x.saturating_sub(1)..=(x+1).min(row.len()-1));
+			for (ny, nx) in around {
+				if !rows[ny][nx].is_ascii_digit() { continue; }
+				let mut start = nx;
+				while start > 0 && rows[ny][start-1].is_ascii_digit() {
+					start -= 1;
+				}
+				let digits = rows[ny][start..].iter().take_while(|d| d.is_ascii_digit()).count();
+				let value = std::str::from_utf8(&rows[ny][start..start+digits]).unwrap().parse().unwrap();
+				numbers.insert((ny, start, value));
+			}
+			symbols.push((c, numbers));
+		}
+	}
+	symbols
+}
+
+impl Reference for Day03 {
+	fn reference_part1(&self, input: &str) -> Option<usize> {
+		let touched: BTreeSet<_> = symbol_neighbours(input).into_iter().flat_map(|(_, numbers)| numbers).collect();
+		Some(touched.iter().map(|(_, _, value)| value).sum())
+	}
+	fn reference_part2(&self, input: &str) -> Option<usize> {
+		let gears = symbol_neighbours(input).into_iter()
+			.filter(|(c, numbers)| *c == b'*' && numbers.len() == 2)
+			.map(|(_, numbers)| numbers.iter().map(|(_, _, value)| value).product::<usize>());
+		Some(gears.sum())
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day03, 0..10, &Params { width: 40, height: 40, numbers: 150, symbols: 100 });
+}
+
+#[test]
+fn reference() {
+	let compared = aocinput::gen::cross_check(Day03, 0..50, &Params { width: 12, height: 12, numbers: 20, symbols: 15 }).unwrap_or_else(|d| panic!("{}", d));
+	assert_eq!(compared, 100);
+}
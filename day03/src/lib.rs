@@ -1,47 +1,46 @@
 #![allow(unused_imports)]
 use core::num;
 use std::str::FromStr;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use itertools::Itertools;
 use aoch::AoCDay;
+use aocinput::{Explain, Explanation, Located, TryParse, TrySolve, Visualize};
+use grid::{Coord, Grid, GridErrorKind};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug,Clone,Copy)]
 pub struct Day03;
 
-type Symbol = (char, usize, usize);
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error(transparent)]
+	Grid(#[from] GridErrorKind),
+	#[error("invalid part number: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+}
+pub type ParseError = Located<ParseErrorKind>;
 
 #[derive(Debug,Clone)]
-pub struct GameBoard {
+pub struct GameBoard<'i> {
+	grid: Grid<'i>,
 	numbers: Vec<PartNbr>,
-	symbols: Vec<Symbol>,
-	line_lens: Vec<usize>,
 }
 
-impl GameBoard {
-	fn number_adjacent_to(&self, nbr: &PartNbr, sym: &Symbol) -> bool {
-		let (c, r) = (sym.1, sym.2);
-
-		let bound_left = nbr.left.saturating_sub(1);
-		let bound_right = nbr.right.unwrap_or(self.line_lens[nbr.row]+1)+1;
-		let range = bound_left..=bound_right;
-
-		if ! range.contains(&c) { return false; }
-
-		if r == nbr.row {
-			// eprintln!("[{:?}] symbol adjacent to {:?}", sym, nbr);
-			return true;
-		}
-		if r+1 == nbr.row {
-			// eprintln!("[{:?}] symbol above {:?}", sym, nbr);
-			return true;
-		}
-		if r == nbr.row+1 {
-			// eprintln!("[{:?}] symbol below {:?}", sym, nbr);
-			return true;
-		}
-		false
+impl<'i> GameBoard<'i> {
+	/// Every symbol touching any digit of the number, including diagonally.
+	fn symbols_around(&self, nbr: &PartNbr) -> impl Iterator<Item = (Coord, char)> + '_ {
+		let nbr = *nbr;
+		(nbr.left..=nbr.right)
+			.flat_map(move |x| self.grid.neighbours8((x, nbr.row)))
+			.filter(|(_, c)| *c != '.' && !c.is_ascii_digit())
+			.unique()
 	}
 }
 
@@ -51,105 +50,114 @@ struct PartNbr {
 	value: usize,
 	row: usize,
 	left: usize,
-	right: Option<usize>,
+	right: usize,
 }
 
 impl AoCDay for Day03 {
-	type Data<'i> = GameBoard;
+	type Data<'i> = GameBoard<'i>;
 	type Answer = usize;
 
 	fn day(&self) -> u8 { 3 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		assert!(input.is_ascii(), "input string not ascii");
-		// let mut numbers: Vec<PartNbr> = Vec::new();
-		// indicates if we are actively within a number
-		// let mut num_start = Option::None;
-		let (line_lens, mut numbers, symbols, num_start) = input.lines()
-			.filter_map(aoch::parsing::trimmed)
-			.enumerate()
-			// create variables that can be used for an effectively flattened iterator
-			.fold((Vec::new(), Vec::new(), Vec::new(), Option::None),
-				|(mut line_lens, mut nums, mut syms, mut num_start), (row, raw)| {
-					raw.char_indices()
-						.for_each(|(col, c)| {
-							let digit = c.is_ascii_digit();
-							// eprintln!("{:?} => {:?}", (c, col, row), (digit, num_start));
-							match (digit, num_start) {
-								(true, None) => { // start a numebr
-									num_start = Some((raw, col, row));
-								},
-								(true, Some(_)) => { // continue this number
-
-								},
-								(false, Some((numstr, nc, nr))) if nr == row => { // finish this number
-									num_start = None;
-									assert!(numstr == raw, "same line string using different string for source number");
-									let txt = &numstr[nc..col];
-									let value = txt.parse::<usize>().unwrap_or_else(|_| panic!("unable for parse string at ({:?}) as number : {:?}", (nc, nr), txt));
-									nums.push(PartNbr { value, row: nr, left: nc, right: Some(col-1) });
-									syms.push((c, col, row));
-								},
-								(false, Some((numstr, nc, nr))) => { // finish last number
-									num_start = None;
-									let txt = &numstr[nc..];
-									let value = txt.parse::<usize>().unwrap();
-									nums.push(PartNbr { value, row: nr, left: nc, right: None });
-									syms.push((c, col, row));
-								},
-								(false, None) => {
-									syms.push((c, col, row));
-								}
-							};
-						});
-					line_lens.push(raw.len());
-					(line_lens, nums, syms, num_start)
-			});
-		if let Some((numstr, nc, nr)) = num_start {
-			// finish last number
-			let txt = &numstr[nc..];
-			let value = txt.parse::<usize>().unwrap();
-			numbers.push(PartNbr { value, row: nr, left: nc, right: None });
-		}
-
-		// for (num, v) in numbers.iter().enumerate() {
-		// 	eprintln!("N {}: {:?}", num, v);
-		// }
-		// for (num, v) in symbols.iter().enumerate().filter(|(_, (c, _, _))| *c != '.') {
-		// 	eprintln!("S {}: {:?}", num, v);
-		// }
-		eprintln!("max checksum: {}", numbers.iter().map(|nbr| nbr.value).sum::<usize>());
-
-		GameBoard { line_lens, numbers, symbols }
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
 		_data.numbers.iter()
-			.filter(|nbr| {
-				_data.symbols.iter()
-					.filter(|sym| sym.0 != '.')
-					.any(|sym| _data.number_adjacent_to(nbr, sym))
-			})
+			.filter(|nbr| _data.symbols_around(nbr).next().is_some())
 			.map(|nbr| nbr.value)
 			.sum()
 	}
 	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		_data.symbols.iter()
-			.filter(|sym| sym.0 == '*')
-			.filter_map(|sym| {
-				let (num1, num2) = _data.numbers.iter()
-					.filter(|nbr| _data.number_adjacent_to(nbr, sym))
-					.copied()
-					.collect_tuple()?;
-
-				// only yield these elements if this gear has two siblings
-				Some(num1.value * num2.value)
-			})
+		let mut gears: HashMap<Coord, Vec<usize>> = HashMap::new();
+		for nbr in &_data.numbers {
+			for (pos, _) in _data.symbols_around(nbr).filter(|(_, c)| *c == '*') {
+				gears.entry(pos).or_default().push(nbr.value);
+			}
+		}
+
+		gears.values()
+			// only count gears with exactly two numbers next to them
+			.filter_map(|nums| nums.iter().collect_tuple())
+			.map(|(num1, num2)| num1 * num2)
 			.sum()
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+impl TrySolve for Day03 {}
+
+impl Explain for Day03 {
+	fn explain_part1(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		let mut explanation = Explanation::default();
+		let mut total = 0;
+		for nbr in &_data.numbers {
+			let symbols = _data.symbols_around(nbr)
+				.map(|((x, y), c)| format!("{:?} at ({}, {})", c, x, y))
+				.collect_vec();
+			let detail = match symbols.is_empty() {
+				true => "touches no symbols".to_owned(),
+				false => {
+					total += nbr.value;
+					format!("touches {}, total {}", symbols.join(", "), total)
+				},
+			};
+			explanation.step(format!("{} at ({}, {})", nbr.value, nbr.left, nbr.row), detail);
+		}
+		Some(explanation)
+	}
+	fn explain_part2(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		let mut gears: HashMap<Coord, Vec<usize>> = HashMap::new();
+		for nbr in &_data.numbers {
+			for (pos, _) in _data.symbols_around(nbr).filter(|(_, c)| *c == '*') {
+				gears.entry(pos).or_default().push(nbr.value);
+			}
+		}
+
+		let mut explanation = Explanation::default();
+		let mut total = 0;
+		for ((x, y), nums) in gears.into_iter().sorted_by_key(|((x, y), _)| (*y, *x)) {
+			let detail = match nums.as_slice() {
+				[num1, num2] => {
+					total += num1 * num2;
+					format!("{} * {} = {}, total {}", num1, num2, num1 * num2, total)
+				},
+				_ => format!("touches {} numbers, so isn't a gear", nums.len()),
+			};
+			explanation.step(format!("'*' at ({}, {})", x, y), detail);
+		}
+		Some(explanation)
+	}
+}
+
+impl Visualize for Day03 {}
+
+impl TryParse for Day03 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		let trimmed = input.trim();
+		let grid = Grid::new(trimmed)
+			.map_err(|e| ParseError::new(input, e.text, e.kind.into()))?;
+
+		let mut numbers = Vec::new();
+		for (row, line) in grid.rows().enumerate() {
+			let digits = line.char_indices()
+				.group_by(|(_, c)| c.is_ascii_digit());
+			for (_, mut run) in digits.into_iter().filter(|(digit, _)| *digit) {
+				let (left, _) = run.next().unwrap();
+				let right = run.last().map_or(left, |(col, _)| col);
+				let txt = &line[left..=right];
+				let value = txt.parse::<usize>()
+					.map_err(|e| ParseError::new(input, txt, ParseErrorKind::InvalidNumber(e)))?;
+				numbers.push(PartNbr { value, row, left, right });
+			}
+		}
+
+		Ok(GameBoard { grid, numbers })
+	}
+}
+
+pub const TEST_INPUT: &'static str = "
 467..114..
 ...*......
 ..35..633.
@@ -162,8 +170,7 @@ const TEST_INPUT: &'static str = "
 .664.598..
 ";
 
-#[cfg(test)]
-const TEST_INPUT_L: &'static str = "
+pub const TEST_INPUT_L: &'static str = "
 467..114..
 ...*......
 ..35..633.
@@ -176,8 +183,7 @@ const TEST_INPUT_L: &'static str = "
 .664.598..
 ";
 
-#[cfg(test)]
-const TEST_INPUT_TL: &'static str = "
+pub const TEST_INPUT_TL: &'static str = "
 467..114..
 ...*......
 ..35..633.
@@ -206,7 +212,6 @@ fn part1() {
 		(TEST_INPUT, 4361),
 		(TEST_INPUT_L, 4361+58),
 		(TEST_INPUT_TL, 4361+58),
-		(daystr!("03"), 553825),
 	];
 	test_runner::<Day03, _>(Day03, DayPart::Part1, &cases);
 }
@@ -214,7 +219,25 @@ fn part1() {
 fn part2() {
 	let cases = [
 		(TEST_INPUT, 467835),
-		(daystr!("03"), 93994191),
 	];
 	test_runner::<Day03, _>(Day03, DayPart::Part2, &cases);
 }
+
+#[test]
+fn explain() {
+	let mut board = Day03.parse(TEST_INPUT);
+	let explanation = Day03.explain_part1(&mut board).unwrap();
+	assert_eq!(explanation.steps[0].subject, "467 at (0, 0)");
+	assert_eq!(explanation.steps[0].detail, "touches '*' at (3, 1), total 467");
+	assert_eq!(explanation.steps[1].detail, "touches no symbols");
+
+	let explanation = Day03.explain_part2(&mut board).unwrap();
+	assert_eq!(explanation.steps[0].detail, "467 * 35 = 16345, total 16345");
+	assert_eq!(explanation.steps[1].detail, "touches 1 numbers, so isn't a gear");
+	assert!(explanation.steps.last().unwrap().detail.ends_with("total 467835"));
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day03, daystr!("03"));
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day03::Day03;
 
 fn main() {
-	run_day::<Day03>(Day03, daystr!("03"), None);
+	aocinput::source::for_each_input(daystr!("03"), |input| {
+		run_day::<Day03>(Day03, input, None);
+	});
 }
 
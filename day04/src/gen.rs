@@ -0,0 +1,69 @@
+use aocinput::arith::{answer, Answer};
+use aocinput::gen::{rng, Generate, Generated, Rng, SliceRandom};
+use crate::Day04;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub cards: usize,
+	/// Winning numbers on each card
+	pub winning: usize,
+	/// Numbers we have on each card
+	pub ours: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { cards: 200, winning: 10, ours: 25 }
+	}
+}
+
+impl Generate for Day04 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<Answer> {
+		let mut rng = rng(seed);
+		let pool: Vec<usize> = (1..100).collect();
+		let mut input = String::new();
+		let mut matches = Vec::with_capacity(params.cards);
+
+		for id in 1..=params.cards {
+			let winning: Vec<usize> = pool.choose_multiple(&mut rng, params.winning).copied().collect();
+			// every match wins copies of more cards, so keep them rare enough that the copies don't grow exponentially
+			let matched = match rng.gen_bool(0.75) {
+				true => 0,
+				false => rng.gen_range(1..=5).min(params.winning).min(params.ours),
+			};
+			let losing: Vec<usize> = pool.iter().filter(|n| !winning.contains(n)).copied().collect();
+			let mut ours: Vec<usize> = winning.choose_multiple(&mut rng, matched)
+				.chain(losing.choose_multiple(&mut rng, params.ours - matched))
+				.copied()
+				.collect();
+			ours.shuffle(&mut rng);
+			matches.push(matched);
+
+			let fmt = |nums: &[usize]| nums.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+			input += &format!("Card {:>3}: {} | {}\n", id, fmt(&winning), fmt(&ours));
+		}
+
+		let part1: usize = matches.iter()
+			.filter(|m| **m > 0)
+			.map(|m| 1 << (m-1))
+			.sum();
+
+		// each card wins copies of the cards after it, once for every copy of itself
+		let mut copies = vec![1; matches.len()];
+		for (i, m) in matches.iter().enumerate() {
+			for j in i+1..(i+1+m).min(copies.len()) {
+				copies[j] += copies[i];
+			}
+		}
+		let part2: usize = copies.iter().sum();
+
+		Generated { input, part1: Some(answer(part1)), part2: Some(answer(part2)) }
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day04, 0..20, &Params::default());
+}
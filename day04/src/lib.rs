@@ -6,8 +6,15 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Explain, Located, Overflow, SolveError, TryParse, TrySolve, Visualize};
+use aocinput::arith::{self, answer, Answer};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug,Clone,Copy)]
 pub struct Day04;
@@ -17,17 +24,33 @@ fn iter_set_bits(num: u128) -> impl Iterator<Item = usize> {
 		.filter(move |i| num & (1u128 << i) != 0)
 		.map(|n| n as usize)
 }
-fn collect_into_bitset(s: &str) -> Result<u128, std::num::ParseIntError> {
+/// Collects the space separated numbers of `s`, part of the card `line`, into a bitset
+fn collect_into_bitset(line: &str, s: &str) -> Result<u128, ParseError> {
 	s.split(' ')
 		.map(|s| s.trim())
 		.filter(|s| !s.is_empty())
 		.try_fold(0u128,|acc, s| {
-			let n: u32 = s.parse()?;
-			assert!(n < u128::BITS, "attempt to register scratchcard number over 128: {}", n);
+			let n: u32 = s.parse().map_err(|e| ParseError::new(line, s, ParseErrorKind::InvalidNumber(e)))?;
+			if n >= u128::BITS {
+				return Err(ParseError::new(line, s, ParseErrorKind::NumberTooLarge(n)));
+			}
 			Ok(acc | (1u128 << n))
 		})
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a `Card <index>: ` prefix")]
+	MissingCardIndex,
+	#[error("expected a ` | ` between the winning numbers and results")]
+	MissingSeparator,
+	#[error("invalid number: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+	#[error("scratchcard number {0} is over the supported maximum of 127")]
+	NumberTooLarge(u32),
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 #[derive(Clone,Copy,PartialEq,Eq,Hash)]
 pub struct ScratchCard {
 	index: usize,
@@ -35,23 +58,29 @@ pub struct ScratchCard {
 	results: u128,
 }
 impl ScratchCard {
-	fn points(&self) -> usize {
+	fn points(&self) -> Result<Answer, Overflow> {
 		let masked = self.winning & self.results;
 		let count = masked.count_ones();
-		if count == 0 { return 0; }
-		1 << (count - 1)
+		if count == 0 { return Ok(answer(0)); }
+		arith::pow(answer(2), count - 1, "doubling a card's points")
 	}
 }
 impl FromStr for ScratchCard {
-	type Err = std::num::ParseIntError;
+	type Err = ParseError;
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let (ridx, rnums) = s.split_once(": ").unwrap();
-		log::debug!("card#: {:?}", ridx);
-		let index: usize = ridx[4..].trim().parse().unwrap();
-		let (winning, results) = rnums.split_once(" | ").unwrap();
-		log::debug!("(winning,results) = {:?}", (winning,results));
-		let winning = collect_into_bitset(winning)?;
-		let results = collect_into_bitset(results)?;
+		let (ridx, rnums) = s.split_once(": ")
+			.ok_or_else(|| ParseError::new(s, s, ParseErrorKind::MissingCardIndex))?;
+		tracing::debug!("card#: {:?}", ridx);
+		let ridx = ridx.strip_prefix("Card")
+			.ok_or_else(|| ParseError::new(s, ridx, ParseErrorKind::MissingCardIndex))?
+			.trim();
+		let index: usize = ridx.parse()
+			.map_err(|e| ParseError::new(s, ridx, ParseErrorKind::InvalidNumber(e)))?;
+		let (winning, results) = rnums.split_once(" | ")
+			.ok_or_else(|| ParseError::new(s, rnums, ParseErrorKind::MissingSeparator))?;
+		tracing::debug!("(winning,results) = {:?}", (winning,results));
+		let winning = collect_into_bitset(s, winning)?;
+		let results = collect_into_bitset(s, results)?;
 		Ok(ScratchCard { index, winning, results })
 	}
 }
@@ -70,38 +99,58 @@ impl fmt::Debug for ScratchCard {
 
 impl AoCDay for Day04 {
 	type Data<'i> = Vec<ScratchCard>;
-	type Answer = usize;
+	type Answer = Answer;
 
 	fn day(&self) -> u8 { 4 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		aoch::parsing::from_lines(input).unwrap()
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		_data.iter().enumerate()
-			.inspect(|t| log::debug!("{:?}", t))
-			.map(|(_, sc)| sc.points())
-			.sum()
+		self.try_part1(_data).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		let mut hits = vec![1; _data.len()];
+		self.try_part2(_data).unwrap_or_else(|e| panic!("{}", e))
+	}
+}
+
+impl TrySolve for Day04 {
+	fn try_part1(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		let points = _data.iter().enumerate()
+			.inspect(|t| tracing::debug!("{:?}", t))
+			.map(|(_, sc)| sc.points())
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(arith::sum(points, answer(0), "totalling points")?)
+	}
+	fn try_part2(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		let mut hits = vec![answer(1); _data.len()];
 		for (ci, card) in _data.iter().enumerate() {
 			assert!(ci+1 == card.index, "card index not matching array index");
 			let matching = (card.winning & card.results).count_ones();
-			let card_count = hits[ci];
-			for i in 0..matching {
-				let dest_card = card.index + (i as usize);
-				if let Some(oc) = hits.get_mut(dest_card) {
-					*oc += card_count;
-				}
+			// copies only ever go to later cards, so this card's count is final by now
+			let (counted, rest) = hits.split_at_mut(ci+1);
+			let card_count = &counted[ci];
+			for oc in rest.iter_mut().take(matching as usize) {
+				*oc = arith::add(std::mem::take(oc), card_count, "counting copies of cards")?;
 			}
 		}
-		hits.iter().sum()
+		Ok(arith::sum(hits, answer(0), "totalling cards")?)
+	}
+}
+
+impl Explain for Day04 {}
+
+impl Visualize for Day04 {}
+
+impl TryParse for Day04 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		aocinput::from_lines(input)
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+pub const TEST_INPUT: &'static str = "
 Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -123,16 +172,19 @@ fn fuel_calc() {
 #[test]
 fn part1() {
 	let cases = [
-		(TEST_INPUT, 13),
-		(daystr!("04"), 21158),
+		(TEST_INPUT, answer(13)),
 	];
 	test_runner::<Day04, _>(Day04, DayPart::Part1, &cases);
 }
 #[test]
 fn part2() {
 	let cases = [
-		(TEST_INPUT, 30),
-		(daystr!("04"), 6050769),
+		(TEST_INPUT, answer(30)),
 	];
 	test_runner::<Day04, _>(Day04, DayPart::Part2, &cases);
 }
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day04, daystr!("04"));
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day04::Day04;
 
 fn main() {
-	run_day::<Day04>(Day04, daystr!("04"), None);
+	aocinput::source::for_each_input(daystr!("04"), |input| {
+		run_day::<Day04>(Day04, input, None);
+	});
 }
 
@@ -0,0 +1,166 @@
+use std::ops::Range;
+use aocinput::gen::{rng, Generate, Generated, Reference, Rng, SliceRandom};
+use crate::Day05;
+
+const CATEGORIES: [&str; 8] = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	/// How many seed numbers there are, rounded down to a pair of them
+	pub seeds: usize,
+	/// How many segments each map is split into
+	pub segments: usize,
+	/// Every number falls within `0..span`
+	pub span: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { seeds: 20, segments: 40, span: 4_000_000_000 }
+	}
+}
+
+/// A map's segments, as (source range, destination start)
+type Map = Vec<(Range<usize>, usize)>;
+
+/// Splits `0..span` into pieces that are moved around, leaving a few of them unmapped.
+fn shuffled_map<R: Rng>(rng: &mut R, segments: usize, span: usize) -> Map {
+	let mut cuts: Vec<usize> = (1..segments).map(|_| rng.gen_range(1..span)).collect();
+	cuts.extend([0, span]);
+	cuts.sort();
+	cuts.dedup();
+	let mut pieces: Vec<Range<usize>> = cuts.windows(2).map(|w| w[0]..w[1]).collect();
+	pieces.shuffle(rng);
+
+	let mut dst = 0;
+	pieces.into_iter()
+		.filter_map(|src| {
+			let start = dst;
+			dst += src.len();
+			rng.gen_bool(0.9).then_some((src, start))
+		})
+		.collect()
+}
+
+/// Maps every range through a map, splitting them wherever they cross a segment's edge.
+fn map_ranges(map: &Map, mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+	let mut mapped = Vec::new();
+	while let Some(r) = ranges.pop() {
+		match map.iter().find(|(src, _)| src.start < r.end && r.start < src.end) {
+			Some((src, dst)) => {
+				let inner = r.start.max(src.start)..r.end.min(src.end);
+				mapped.push(inner.start - src.start + dst..inner.end - src.start + dst);
+				// the rest may fall into other segments
+				if r.start < inner.start { ranges.push(r.start..inner.start); }
+				if inner.end < r.end { ranges.push(inner.end..r.end); }
+			},
+			None => mapped.push(r),
+		}
+	}
+	mapped
+}
+
+impl Generate for Day05 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let Params { span, .. } = *params;
+
+		// pairs of numbers, so that they make sense as ranges too
+		let pairs: Vec<Range<usize>> = (0..(params.seeds/2).max(1))
+			.map(|_| {
+				let start = rng.gen_range(0..span-1);
+				start..rng.gen_range(start+1..=(start + span/20).min(span))
+			})
+			.collect();
+		let maps: Vec<Map> = (1..CATEGORIES.len())
+			.map(|_| shuffled_map(&mut rng, params.segments, span))
+			.collect();
+
+		let mut input = "seeds:".to_owned();
+		for r in &pairs {
+			input += &format!(" {} {}", r.start, r.len());
+		}
+		input += "\n";
+		for (kinds, map) in CATEGORIES.windows(2).zip(&maps) {
+			input += &format!("\n{}-to-{} map:\n", kinds[0], kinds[1]);
+			for (src, dst) in map {
+				input += &format!("{} {} {}\n", dst, src.start, src.len());
+			}
+		}
+
+		let part1 = pairs.iter()
+			.flat_map(|r| [r.start, r.len()])
+			.map(|seed| maps.iter().fold(seed, |n, map| {
+				map.iter()
+					.find(|(src, _)| src.contains(&n))
+					.map_or(n, |(src, dst)| n - src.start + dst)
+			}))
+			.min();
+		let part2 = maps.iter()
+			.fold(pairs, |ranges, map| map_ranges(map, ranges))
+			.iter()
+			.map(|r| r.start)
+			.min();
+
+		Generated { input, part1, part2 }
+	}
+}
+
+/// The seed numbers, and each map's segments as (destination, source, length)
+fn almanac(input: &str) -> (Vec<usize>, Vec<Vec<[usize; 3]>>) {
+	let nums = |line: &str| line.split_whitespace().map(|n| n.parse().unwrap()).collect::<Vec<usize>>();
+	let mut sections = input.trim().split("\n\n");
+	let seeds = nums(sections.next().unwrap().strip_prefix("seeds:").unwrap());
+	let maps = sections
+		.map(|section| section.lines().skip(1).map(|l| nums(l).try_into().unwrap()).collect())
+		.collect();
+	(seeds, maps)
+}
+
+/// Looks a seed up in each map in turn.
+fn location(maps: &[Vec<[usize; 3]>], seed: usize) -> usize {
+	maps.iter().fold(seed, |n, map| {
+		map.iter()
+			.find(|[_, src, len]| (*src..src+len).contains(&n))
+			.map_or(n, |[dst, src, _]| n - src + dst)
+	})
+}
+
+impl Reference for Day05 {
+	fn reference_part1(&self, input: &str) -> Option<usize> {
+		let (seeds, maps) = almanac(input);
+		seeds.into_iter().map(|seed| location(&maps, seed)).min()
+	}
+	/// Looks up every seed in every range, one at a time.
+	fn reference_part2(&self, input: &str) -> Option<usize> {
+		let (seeds, maps) = almanac(input);
+		seeds.chunks_exact(2)
+			.flat_map(|r| r[0]..r[0]+r[1])
+			.map(|seed| location(&maps, seed))
+			.min()
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day05, 0..20, &Params { segments: 10, ..Params::default() });
+}
+
+#[test]
+fn reference() {
+	let compared = aocinput::gen::cross_check(Day05, 0..50, &Params { seeds: 6, segments: 8, span: 2000 }).unwrap_or_else(|d| panic!("{}", d));
+	assert_eq!(compared, 100);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel() {
+	use aoch::AoCDay;
+	for seed in 0..10 {
+		let almanac = Day05.parse(&Day05.generate(seed, &Params { segments: 10, ..Params::default() }).input);
+		let seeds: Vec<_> = almanac.seeds.chunks_exact(2).map(|r| r[0]..r[0]+r[1]).collect();
+		assert_eq!(almanac.derive_ranges_serial(seeds.clone()), almanac.derive_ranges_parallel(seeds), "seed {}", seed);
+	}
+}
@@ -11,12 +11,42 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Animation, Explain, Explanation, Located, TryParse, TrySolve, Visualize};
+use aocinput::visual::Tone;
+use ranges::{MapError, OffsetMap};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug,Clone,Copy)]
 pub struct Day05;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a `seeds: ` line")]
+	MissingSeeds,
+	#[error("expected a `<source>-to-<destination> map:` header")]
+	InvalidMapHeader,
+	#[error("mapping found before any map header")]
+	SegmentWithoutMap,
+	#[error("expected three numbers for a mapping: `<destination> <source> <length>`")]
+	InvalidSegment,
+	#[error("invalid number: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+	#[error("mapping reaches past the largest representable number")]
+	SegmentTooLong,
+	#[error("map has overlapping source ranges")]
+	OverlappingSegments,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
+/// A seed range, along with the ranges it ends up split into through each map
+type Derivation = (Range<usize>, Vec<Vec<(isize, Range<usize>)>>);
+
 #[derive(Debug,Clone,PartialEq,Eq)]
 pub struct Almanac {
 	seeds: Vec<usize>,
@@ -28,6 +58,30 @@ impl Almanac {
 	fn process(&self, seed: usize) -> AlmanacLookup<'_> {
 		AlmanacLookup::new(&self.mappings, seed)
 	}
+	/// Derives every seed range through the maps. The ranges are independent of each other, so with the
+	/// `parallel` feature they are spread across threads.
+	fn derive_ranges(&self, seeds: Vec<Range<usize>>) -> Vec<Derivation> {
+		#[cfg(feature = "parallel")]
+		return self.derive_ranges_parallel(seeds);
+		#[cfg(not(feature = "parallel"))]
+		return self.derive_ranges_serial(seeds);
+	}
+	// only checked against the parallel path in tests when that is enabled
+	#[cfg_attr(feature = "parallel", allow(dead_code))]
+	fn derive_ranges_serial(&self, seeds: Vec<Range<usize>>) -> Vec<Derivation> {
+		seeds.into_iter()
+			// .inspect(|seed| eprintln!("seed[{:?}] starting search", seed))
+			.map(|range| (range.clone(), self.by_divisible_range(range)))
+			.collect()
+	}
+	#[cfg(feature = "parallel")]
+	fn derive_ranges_parallel(&self, seeds: Vec<Range<usize>>) -> Vec<Derivation> {
+		use rayon::prelude::*;
+		seeds.into_par_iter()
+			.map(|range| (range.clone(), self.by_divisible_range(range)))
+			.collect()
+	}
+
 	fn by_divisible_range(&self, range: Range<usize>) -> Vec<Vec<(isize, Range<usize>)>> {
 		fn inner<F: FnMut(&[(isize, Range<usize>)])>(mappings: &[Layer], stack: &mut Vec<(isize, Range<usize>)>, search: (isize, Range<usize>), visit: &mut F) {
 			use Ordering::{Less, Equal, Greater};
@@ -35,7 +89,7 @@ impl Almanac {
 			let depthstr: String = (1..=stack.len()).map(|_| '\t').collect();
 
 			let root_seed = stack.first().cloned().unwrap_or((last_offset, search.clone())).1;
-			log::debug!("seed[_, {root_seed:?}] {depthstr:}searching for match to {}[{search:?}]->{}",
+			tracing::debug!("seed[_, {root_seed:?}] {depthstr:}searching for match to {}[{search:?}]->{}",
 				mappings.first().map(|s| s.src_type.as_str()).unwrap_or("location"),
 				mappings.first().map(|s| s.dst_type.as_str()).unwrap_or("<end>"),
 			);
@@ -45,7 +99,7 @@ impl Almanac {
 			match mappings.split_first() {
 				None => {
 					/* nothing left */
-					eprintln!("seed[_, {:?}] {depthstr:}found -> {:?}",
+					tracing::debug!("seed[_, {:?}] {depthstr:}found -> {:?}",
 						stack.first().unwrap_or(&(last_offset, search.clone())).1,
 						search,
 					);
@@ -107,102 +161,15 @@ impl<'a> Iterator for AlmanacLookup<'a> {
 struct Layer {
 	src_type: String,
 	dst_type: String,
-	ranges: Vec<Segment>
+	segments: OffsetMap,
 }
 impl Layer {
-	fn find(&self, value: usize) -> Option<usize> {
-		let m = self.ranges.iter()
-			.find(|m| m.src <= value && value < m.src + m.len)?;
-		Some(value - m.src + m.dst)
-	}
 	fn map(&self, value: usize) -> usize {
-		self.find(value).unwrap_or(value)
+		self.segments.map(value)
 	}
-	fn check_overlapping(&mut self) -> bool {
-		self.ranges.sort_by_key(|m| m.src);
-		if self.ranges.is_empty() { return false; }
-		let first_end = { let k = self.ranges[0]; k.src+k.len };
-		let (overlapping, _) = self.ranges.iter()
-			.skip(1)
-			.fold((false, first_end), |acc, r| {
-				let (overlapping, last_end) = acc;
-				if !overlapping && r.src < last_end {
-					(true, 0)
-				} else {
-					(false, r.src+r.len)
-				}
-			});
-		overlapping
-	}
-	fn search_segments(&self, search: Range<usize>) -> SegmentMapper {
-		SegmentMapper { search: Some(search), segments: self.ranges.as_slice() }
-	}
-}
-
-#[derive(Debug, Clone)]
-struct SegmentMapper<'r> {
-	search: Option<Range<usize>>,
-	segments: &'r [Segment],
-}
-impl Iterator for SegmentMapper<'_> {
-	type Item = (isize, Range<usize>);
-	fn next(&mut self) -> Option<Self::Item> {
-		use std::cmp::Ordering::{Greater, Equal, Less};
-		// assumes the ranges are ordered
-		while let Some((mr, rest)) = self.segments.split_first() {
-			let Range { start: ss, end: se } = self.search.as_mut()?;
-			let Range { start: rs, end: re } = mr.range();
-
-			assert!(ss <= se);
-			assert!(rs <= re);
-
-			let ordering = ((*ss).cmp(&rs), (*se).cmp(&rs), (*ss).cmp(&re), (*se).cmp(&re));
-
-			return Some(match ordering {
-				// action 1 | starts before segment, does not enter
-				// action 2 | starts before segment, terminates inside or goes past
-				// action 3 | starts with segment, ends before or at segment end
-				// action 4 | starts with segment, goes past segment end
-				// action 5 | starting after segment end
-
-				(Less, Less|Equal, Less|Equal, Less|Equal) => { // 1: starts before segment, does not enter
-				// (_/* Less|Equal */, Less|Equal, _ /* Less */, _ /* Less */) => { // starts before segment, does not enter
-					let rtn = *ss..*se; // return it as is, before sorted segments
-					self.search = None; // stop operations
-					self.segments = rest;
-					(0, rtn) // no offset, first segment
-				},
-				(Less, Greater, Less|Equal, Less|Equal|Greater) => { // 2: starts before segment, terminates inside or goes past
-					let rtn = *ss..rs; // save first segment
-					*ss = rs; // search start for next loop
-					(0, rtn) // no offset, first segment
-				},
-				(Equal|Greater, Greater|Equal, Less|Equal, Less|Equal) => { // 3: starts with segment, ends before or at segment end
-					// let rtn = *ss..*se; // return as-is
-					let rtn = mr.rangemap_opt(*ss..*se).unwrap(); // save mapped
-					self.search = None; // clear search, we're done
-					self.segments = &[];
-					(mr.offset(), rtn) // yield mapped
-				},
-				(Equal|Greater, Greater|Equal, Less|Equal, Greater) => { // 4: starts with segment, goes past end
-					let rtn = mr.rangemap_opt(*ss..re).unwrap();
-					*ss = re; // update search
-					self.segments = rest; // update segments
-					(mr.offset(), rtn) // yield mapped
-				},
-				(Greater, Greater, Greater, Greater) => { // 5: starts after segment end
-					self.segments = rest;
-					continue;
-				},
-
-				_ => {
-					unreachable!("Found unhandled search/segment ordering: {:?}", ordering);
-				}
-			});
-		}
-
-		// Handle case when the search is after all ranges
-		self.search.take().map(|r| (0, r))
+	/// Splits a range of source values into the pieces that map the same way, along with where each piece maps to.
+	fn search_segments(&self, search: Range<usize>) -> impl Iterator<Item = (isize, Range<usize>)> + '_ {
+		self.segments.split(search)
 	}
 }
 
@@ -215,21 +182,14 @@ fn mapped_ranges() {
 	let seed2soil = &almanac.mappings[0];
 	assert_eq!(seed2soil.src_type, "seed", "test data changed");
 	assert_eq!(seed2soil.dst_type, "soil", "test data changed");
-	assert_eq!(seed2soil.ranges, vec![
-		Segment { dst: 52, src: 50, len: 48 },
-		Segment { dst: 50, src: 98, len: 2 }
-	], "test data changed");
-	assert_eq!(seed2soil.ranges[0].range(), 50..98, "test data changed");
-	assert_eq!(seed2soil.ranges[0].offset(), 2, "test data changed");
-	assert_eq!(seed2soil.ranges[1].range(), 98..100, "test data changed");
-	assert_eq!(seed2soil.ranges[1].offset(), -48, "test data changed");
+	assert_eq!(seed2soil.segments.segments().collect_vec(), [(50..98, 2), (98..100, -48)], "test data changed");
 
 	macro_rules! check_ranges {
 		($mapping:expr, $src:expr, $msg:literal, (), &[ $(($o:literal, $s:literal .. $e:literal),)* ]) => {
 			assert_matches!(
 				$mapping.search_segments($src)
 					.enumerate()
-					.inspect(|(i, r)| eprintln!("[{} / {:?}][{}] = {:?}", $msg, $src, i, r))
+					.inspect(|(i, r)| tracing::debug!("[{} / {:?}][{}] = {:?}", $msg, $src, i, r))
 					.map(|(_, r)| r)
 					.collect_vec()
 					.as_slice(),
@@ -264,10 +224,10 @@ fn mapped_ranges() {
 		(0, 100..200), // 100..200
 	]);
 
-	let with_hole = Layer { src_type: "a".into(), dst_type: "b".into(), ranges: vec![
-		Segment { src: 25, len: 5, dst: 100 },
-		Segment { src: 40, len: 10, dst: 200 },
-	] };
+	let with_hole = Layer { src_type: "a".into(), dst_type: "b".into(), segments: OffsetMap::new([
+		(25..30, 75),
+		(40..50, 160),
+	]).unwrap() };
 	check_ranges!(with_hole, 0..100, "search starts before segments, goes through fragmented segments, and terminates outside", (), &[
 		(0, 0..25), // 0..25
 		(75, 100..105), // 25..30
@@ -278,28 +238,6 @@ fn mapped_ranges() {
 
 }
 
-#[test]
-fn overlapping_maps() {
-	let mut overlapping = Layer { src_type: "src".into(), dst_type: "dst".into(), ranges: vec![
-		Segment { src: 0, len: 16, dst: 64 },
-		Segment { src: 8, len: 16, dst: 72 },
-	]};
-
-	let mut contiguous = Layer { src_type: "src".into(), dst_type: "dst".into(), ranges: vec![
-		Segment { src: 0, len: 16, dst: 64 },
-		Segment { src: 16, len: 16, dst: 64+16 },
-	]};
-
-	let mut separate = Layer { src_type: "src".into(), dst_type: "dst".into(), ranges: vec![
-		Segment { src: 0, len: 16, dst: 64 },
-		Segment { src: 32, len: 16, dst: 64+32 },
-	]};
-
-	assert_eq!(overlapping.check_overlapping(), true);
-	assert_eq!(contiguous.check_overlapping(), false);
-	assert_eq!(separate.check_overlapping(), false);
-}
-
 #[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
 struct Segment {
 	dst: usize,
@@ -307,45 +245,31 @@ struct Segment {
 	len: usize,
 }
 impl FromStr for Segment {
-	type Err = std::num::ParseIntError;
+	type Err = ParseError;
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let (dst, src, len) = s.trim()
-			.split(' ')
-			.map(|s| s.parse())
+		let (dst, src, len) = s.split_whitespace()
+			.map(|n| n.parse().map_err(|e| ParseError::new(s, n, ParseErrorKind::InvalidNumber(e))))
 			.collect_tuple()
-			.unwrap();
-		Ok(Segment { dst: dst?, src: src?, len: len? })
+			.ok_or_else(|| ParseError::new(s, s, ParseErrorKind::InvalidSegment))?;
+		let segment = Segment { dst: dst?, src: src?, len: len? };
+		if segment.src.checked_add(segment.len).is_none() || segment.dst.checked_add(segment.len).is_none() || segment.offset().is_none() {
+			return Err(ParseError::new(s, s, ParseErrorKind::SegmentTooLong));
+		}
+		Ok(segment)
 	}
 }
 impl Segment {
-	/// Attempts to convert a range using this map. If the range only partially overlaps, then
-	/// this returns None. If the range is outside then it is returned as is. If fully contained, then it is mapped
-	/// according to this range.
-	fn rangemap_opt(&self, orange: Range<usize>) -> Option<Range<usize>> {
-		let srange = self.src..self.src+self.len;
-
-		let overlaps_start = srange.start <= orange.start && orange.start < srange.end;
-		let overlaps_end = srange.start < orange.end && orange.end <= srange.end;
-
-		match (overlaps_start, overlaps_end) {
-			(true, true) => Some(Range {
-				start: orange.start + self.dst - self.src,
-				end: orange.end + self.dst - self.src,
-			}),
-			(false, false) => Some(orange),
-			_ => None
-		}
-	}
 	fn range(&self) -> Range<usize> {
 		self.src..self.src+self.len
 	}
-	fn offset(&self) -> isize {
-		(self.dst as isize) - (self.src as isize)
+	/// How far the segment moves its values, if that fits in an `isize`
+	fn offset(&self) -> Option<isize> {
+		isize::try_from(self.dst as i128 - self.src as i128).ok()
 	}
 }
 impl fmt::Display for Segment {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		write!(f, "{:?}:{:+}", self.range(), self.offset())
+		write!(f, "{:?}:{:+}", self.range(), self.dst as i128 - self.src as i128)
 	}
 }
 
@@ -381,49 +305,7 @@ impl AoCDay for Day05 {
 	fn day(&self) -> u8 { 5 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		let mut lines = input.lines().filter_map(aoch::parsing::trimmed);
-		let raw_seeds = lines.next().expect("no seed string");
-		assert!(raw_seeds.starts_with("seeds: "));
-		let seeds = raw_seeds[7..].split(' ')
-			.map(|s| s.parse::<usize>().unwrap())
-			.collect_vec();
-
-		// let mut mappings = HashMap::default();
-		let mut mappings = Vec::default();
-		let mut group: (String, String, Vec<Segment>) = Default::default();
-
-		for l in lines {
-			if l.ends_with("map:") {
-				if !group.2.is_empty() {
-					let (src, dst, maps) = group;
-					mappings.push(Layer { src_type: src, dst_type: dst, ranges: maps });
-					// mappings.insert((src, dst), maps);
-				}
-
-				let (raw_desc, _map) = l.split_once(' ').unwrap();
-				let (src, _, dst) = raw_desc.split('-').collect_tuple().unwrap();
-				group = (src.to_owned(), dst.to_owned(), Vec::new());
-			} else {
-				let (dst, src, len) = l.split(' ')
-					.map(|s| s.parse::<usize>().unwrap())
-					.collect_tuple().unwrap();
-
-				group.2.push(Segment { dst, src, len })
-			}
-		}
-		if !group.2.is_empty() {
-			let (src, dst, maps) = group;
-			mappings.push(Layer { src_type: src, dst_type: dst, ranges: maps });
-		}
-
-		mappings.iter_mut()
-			.for_each(|m| {
-				let overlaps = m.check_overlapping();
-				log::debug!("Range {}-{} overlaps: {} ({} ranges)", m.src_type, m.dst_type, overlaps, m.ranges.len());
-				assert!(!overlaps, "range {}-{} overlaps", m.src_type, m.dst_type);
-			});
-
-		Almanac { seeds, mappings, cached: BTreeMap::default() }
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
 		_data.seeds.iter()
@@ -442,11 +324,12 @@ impl AoCDay for Day05 {
 			.map(Result::unwrap)
 			.collect_vec();
 
-		let results = ranges.iter()
+		let seeds = ranges.iter()
+			.map(|&[start, len]| start..start+len)
+			.collect_vec();
+
+		let results = _data.derive_ranges(seeds).into_iter()
 			.enumerate()
-			.map_enum(|&[start, len]| start..start+len)
-			// .inspect(|(i, seed)| eprintln!("seed[{}, {:?}] starting search", i, seed))
-			.map_enum(|range| (range.clone(), _data.by_divisible_range(range)))
 			.flat_map(|(si, (seed, derivations))| {
 				derivations.into_iter()
 					.enumerate()
@@ -464,14 +347,164 @@ impl AoCDay for Day05 {
 			.min_by_key(|(_si, _sr, _li, lr)| lr.start)
 			.expect("no results found");
 
-		eprintln!("total of {} results found", results.len());
+		tracing::debug!(results = results.len(), "total of {} results found", results.len());
 
 		best.3.start
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+impl TrySolve for Day05 {}
+
+impl Explain for Day05 {
+	fn explain_part1(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		let mut explanation = Explanation::default();
+		let mut lowest = usize::MAX;
+		for &seed in &_data.seeds {
+			let chain = _data.process(seed).collect_vec();
+			lowest = lowest.min(chain.last().unwrap().1);
+			explanation.step(
+				format!("seed {}", seed),
+				chain.iter().map(|(t, v)| format!("{} {}", t, v)).join(" -> "),
+			);
+		}
+		explanation.step("lowest", format!("location {}", lowest));
+		Some(explanation)
+	}
+	fn explain_part2(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		let seeds = _data.seeds.chunks_exact(2)
+			.map(|pair| pair[0]..pair[0]+pair[1])
+			.collect_vec();
+
+		// only the path through the maps that reaches the lowest location is shown for each seed range
+		let mut explanation = Explanation::default();
+		let mut lowest = usize::MAX;
+		for (seed, paths) in _data.derive_ranges(seeds) {
+			let best = paths.iter()
+				.min_by_key(|path| path.last().unwrap().1.start)
+				.expect("every seed range reaches a location");
+			lowest = lowest.min(best.last().unwrap().1.start);
+			let chain = _data.mappings.iter()
+				.zip(&best[1..])
+				.map(|(layer, (_, range))| format!("{} {:?}", layer.dst_type, range))
+				.join(" -> ");
+			explanation.step(format!("seeds {:?}", seed), chain);
+		}
+		explanation.step("lowest", format!("location {}", lowest));
+		Some(explanation)
+	}
+}
+
+/// How many columns each layer's ranges are drawn across, scaled down from the largest value
+const BAR_WIDTH: usize = 100;
+
+impl Visualize for Day05 {
+	fn visualize_part2(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		let seeds = _data.seeds.chunks_exact(2)
+			.map(|pair| pair[0]..pair[0]+pair[1])
+			.collect_vec();
+
+		// the pieces that each layer splits the ranges into, along with the seed range each came from and how
+		// far the layer moved it
+		let mut stages = vec![seeds.into_iter().enumerate().map(|(i, r)| (i, 0, r)).collect_vec()];
+		for layer in &_data.mappings {
+			let pieces = stages.last().unwrap().iter()
+				.flat_map(|(i, _, r)| layer.search_segments(r.clone()).map(move |(offset, moved)| (*i, offset, moved)))
+				.collect_vec();
+			stages.push(pieces);
+		}
+
+		let names = std::iter::once("seed")
+			.chain(_data.mappings.iter().map(|layer| layer.dst_type.as_str()))
+			.collect_vec();
+		let label = names.iter().map(|name| name.len()).max().unwrap() + 1;
+		let end = stages.iter().flatten().map(|(_, _, r)| r.end).max()?;
+		let column = |value: usize| label + (value as u128 * BAR_WIDTH as u128 / end as u128) as usize;
+
+		let mut animation = Animation::blank("Day 05: splitting the seed ranges through each map", label + BAR_WIDTH, names.len());
+		for (y, pieces) in stages.iter().enumerate() {
+			let caption = match y {
+				0 => format!("{} seed ranges", pieces.len()),
+				_ => {
+					let moved = pieces.iter().filter(|(_, offset, _)| *offset != 0).count();
+					format!("{}-to-{}: split into {} pieces, {} of them moved", names[y-1], names[y], pieces.len(), moved)
+				},
+			};
+			let frame = animation.push(caption);
+			frame.text((0, y), names[y], Tone::Plain);
+			// moved pieces are drawn solid, keeping the colour of the seed range they came from
+			for (i, offset, r) in pieces {
+				let glyph = if *offset != 0 { '#' } else { '-' };
+				for x in column(r.start)..=column(r.end - 1) {
+					frame.set((x, y), glyph, Tone::Group(*i as u32)).focus((x, y));
+				}
+			}
+		}
+
+		let (_, _, lowest) = stages.last().unwrap().iter().min_by_key(|(_, _, r)| r.start)?;
+		let at = (column(lowest.start), names.len() - 1);
+		animation.push(format!("the lowest location is {}", lowest.start))
+			.set(at, '*', Tone::Highlight)
+			.focus(at);
+		Some(animation)
+	}
+}
+
+impl TryParse for Day05 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		/// Builds the layer for a finished map, blaming its header if its segments can't make one
+		fn finish(input: &str, (header, src, dst, segments): Group, mappings: &mut Vec<Layer>) -> Result<(), ParseError> {
+			let count = segments.len();
+			let segments = OffsetMap::new(segments.iter().map(|s| (s.range(), s.offset().expect("offsets are checked when parsed"))));
+			tracing::debug!(segments = count, overlaps = segments.is_err(), "Range {}-{} overlaps: {} ({} ranges)", src, dst, segments.is_err(), count);
+			let segments = segments.map_err(|e| ParseError::new(input, header, match e {
+				MapError::Overlap(..) => ParseErrorKind::OverlappingSegments,
+				MapError::OutOfBounds(..) => ParseErrorKind::SegmentTooLong,
+			}))?;
+			mappings.push(Layer { src_type: src.to_owned(), dst_type: dst.to_owned(), segments });
+			Ok(())
+		}
+		/// A map's header, its source and destination types, and its segments so far
+		type Group<'i> = (&'i str, &'i str, &'i str, Vec<Segment>);
+
+		let mut lines = input.lines().filter_map(aoch::parsing::trimmed);
+		let raw_seeds = lines.next().unwrap_or(input);
+		let seeds = raw_seeds.strip_prefix("seeds: ")
+			.ok_or_else(|| ParseError::new(input, raw_seeds, ParseErrorKind::MissingSeeds))?
+			.split_whitespace()
+			.map(|s| s.parse::<usize>().map_err(|e| ParseError::new(input, s, ParseErrorKind::InvalidNumber(e))))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut mappings = Vec::default();
+		let mut group: Option<Group> = None;
+
+		for l in lines {
+			if l.ends_with("map:") {
+				if let Some(group) = group.take() {
+					finish(input, group, &mut mappings)?;
+				}
+
+				let (src, _, dst) = l.split_once(' ')
+					.and_then(|(raw_desc, _map)| raw_desc.split('-').collect_tuple())
+					.ok_or_else(|| ParseError::new(input, l, ParseErrorKind::InvalidMapHeader))?;
+				group = Some((l, src, dst, Vec::new()));
+			} else {
+				let (_, _, _, segments) = group.as_mut()
+					.ok_or_else(|| ParseError::new(input, l, ParseErrorKind::SegmentWithoutMap))?;
+				let segment = l.parse::<Segment>().map_err(|e| e.rebase(input, l))?;
+				segments.push(segment);
+			}
+		}
+		if let Some(group) = group {
+			finish(input, group, &mut mappings)?;
+		}
+
+		Ok(Almanac { seeds, mappings, cached: BTreeMap::default() })
+	}
+}
+
+pub const TEST_INPUT: &'static str = "
 seeds: 79 14 55 13
 
 seed-to-soil map:
@@ -517,11 +550,32 @@ fn fuel_calc() {
 }
 */
 
+#[test]
+fn parse_errors() {
+	let cases = [
+		("seed: 1 2", Some((1, 1, "seed: 1 2".into()))),
+		("seeds: 1 2\n\nseed-to-soil map:\n50 98 2\n52 5O 48", Some((5, 4, "5O".into()))),
+		("seeds: 1 2\n50 98 2", Some((2, 1, "50 98 2".into()))),
+		("seeds: 1 2\n\nseed-soil map:\n50 98 2", Some((3, 1, "seed-soil map:".into()))),
+		("seeds: 1 2\n\nseed-to-soil map:\n50 98", Some((4, 1, "50 98".into()))),
+		("seeds: 1 2\n\nseed-to-soil map:\n0 0 16\n8 8 16", Some((3, 1, "seed-to-soil map:".into()))),
+		("seeds: 1 2\n\nseed-to-soil map:\n0 18446744073709551615 2", Some((4, 1, "0 18446744073709551615 2".into()))),
+		("seeds: 1 2\n\nseed-to-soil map:\n0 0 16\n16 16 16", None),
+		// moving values further than an isize can hold
+		("seeds: 1 2\nseed-to-location map:\n9223372036854775813 0 10", Some((3, 1, "9223372036854775813 0 10".into()))),
+		("seeds: 1 2\nseed-to-location map:\n0 9223372036854775813 10", Some((3, 1, "0 9223372036854775813 10".into()))),
+		("seeds: 1 2\nseed-to-location map:\n9223372036854775813 9223372036854775800 10", None),
+	];
+	run_test(|input| {
+		Day05.try_parse(input).err()
+			.map(|e| (e.span.line, e.span.col, e.span.text))
+	}, &cases);
+}
+
 #[test]
 fn part1() {
 	let cases = [
 		(TEST_INPUT, 35),
-		(daystr!("05"), 278755257),
 	];
 	test_runner::<Day05, _>(Day05, DayPart::Part1, &cases);
 }
@@ -530,7 +584,35 @@ fn part1() {
 fn part2() {
 	let cases = [
 		(TEST_INPUT, 46),
-		(daystr!("05"), 26829166),
 	];
 	test_runner::<Day05, _>(Day05, DayPart::Part2, &cases);
 }
+
+#[test]
+fn explain() {
+	let mut almanac = Day05.parse(TEST_INPUT);
+	let explanation = Day05.explain_part1(&mut almanac).unwrap();
+	assert_eq!(explanation.steps[0].detail, "soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78 -> location 82");
+	assert_eq!(explanation.steps.last().unwrap().detail, "location 35");
+
+	let explanation = Day05.explain_part2(&mut almanac).unwrap();
+	assert_eq!(explanation.steps.len(), 3);
+	assert_eq!(explanation.steps.last().unwrap().detail, "location 46");
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day05, daystr!("05"));
+}
+
+#[test]
+fn visualized() {
+	let mut almanac = Day05.parse(TEST_INPUT);
+	assert!(Day05.visualize_part1(&mut almanac).is_none());
+	let animation = Day05.visualize_part2(&mut almanac).unwrap();
+	// the seeds, a frame for each of the seven maps, and the answer
+	assert_eq!(animation.frames().len(), 9);
+	assert_eq!(animation.frames()[1].caption, "seed-to-soil: split into 2 pieces, 2 of them moved");
+	assert_eq!(animation.frames()[8].caption, "the lowest location is 46");
+	assert_eq!(animation.height(), 8);
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day05::Day05;
 
 fn main() {
-	run_day::<Day05>(Day05, daystr!("05"), None);
+	aocinput::source::for_each_input(daystr!("05"), |input| {
+		run_day::<Day05>(Day05, input, None);
+	});
 }
 
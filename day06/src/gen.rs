@@ -0,0 +1,96 @@
+use aocinput::gen::{rng, Generate, Generated, Reference, Rng};
+use crate::Day06;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub races: usize,
+	/// The longest a single race can last. Part 2 joins every race's time together, so this grows quickly.
+	pub time: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { races: 4, time: 99 }
+	}
+}
+
+/// How many ways there are to beat the record, found by searching for the shortest winning hold
+/// as the distance only grows until halfway through the race.
+fn ways(time: u128, record: u128) -> u128 {
+	let (mut lo, mut hi) = (0, time/2 + 1);
+	while lo < hi {
+		let mid = (lo + hi) / 2;
+		if mid * (time - mid) > record { hi = mid } else { lo = mid + 1 }
+	}
+	if lo > time/2 { 0 } else { time - 2*lo + 1 }
+}
+
+impl Generate for Day06 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let races: Vec<(usize, usize)> = (0..params.races.max(1))
+			.map(|_| {
+				let time = rng.gen_range(2..=params.time.max(2));
+				// set by holding the button for some time that isn't the best
+				let held = rng.gen_range(1..time);
+				(time, held * (time - held))
+			})
+			.collect();
+
+		let row = |nums: Vec<String>| nums.iter().map(|n| format!("{:>5}", n)).collect::<String>();
+		let times = races.iter().map(|r| r.0.to_string()).collect::<Vec<_>>();
+		let records = races.iter().map(|r| r.1.to_string()).collect::<Vec<_>>();
+		let input = format!("Time:    {}\nDistance:{}\n", row(times.clone()), row(records.clone()));
+
+		let part1 = races.iter()
+			.map(|&(t, r)| ways(t as u128, r as u128))
+			.product::<u128>();
+		let part2 = ways(times.concat().parse().unwrap(), records.concat().parse().unwrap());
+
+		Generated {
+			input,
+			part1: part1.try_into().ok(),
+			part2: part2.try_into().ok(),
+		}
+	}
+}
+
+/// Each race's time and record, read with the spaces between numbers kept (for part 1) or removed (for part 2)
+fn races(input: &str, kerning: bool) -> Vec<(usize, usize)> {
+	let row = |line: &str| -> Vec<usize> {
+		let nums = line.split_once(':').unwrap().1;
+		match kerning {
+			true => nums.split_whitespace().map(|n| n.parse().unwrap()).collect(),
+			false => vec![nums.replace(' ', "").parse().unwrap()],
+		}
+	};
+	let (times, records) = input.trim().split_once('\n').unwrap();
+	row(times).into_iter().zip(row(records)).collect()
+}
+
+/// Tries holding the button down for every possible time.
+fn tried(time: usize, record: usize) -> usize {
+	(0..=time).filter(|held| held * (time - held) > record).count()
+}
+
+impl Reference for Day06 {
+	fn reference_part1(&self, input: &str) -> Option<usize> {
+		Some(races(input, true).into_iter().map(|(t, r)| tried(t, r)).product())
+	}
+	fn reference_part2(&self, input: &str) -> Option<usize> {
+		races(input, false).into_iter().map(|(t, r)| tried(t, r)).next()
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day06, 0..20, &Params { races: 3, time: 60 });
+}
+
+#[test]
+fn reference() {
+	let compared = aocinput::gen::cross_check(Day06, 0..50, &Params { races: 2, time: 60 }).unwrap_or_else(|d| panic!("{}", d));
+	assert_eq!(compared, 100);
+}
@@ -1,36 +1,83 @@
+#![feature(iterator_try_reduce)]
+
 #![allow(unused_imports)]
 use std::str::FromStr;
 use std::fmt::Debug;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Explain, Located, Overflow, SolveError, TryParse, TrySolve, Visualize};
+use aocinput::arith;
+#[cfg(test)] #[allow(unused_imports)]
+use aoch::{DayPart, run_test, daystr};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug,Clone,Copy)]
 pub struct Day06;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a `{0}` line")]
+	MissingLine(&'static str),
+	#[error("expected the same number of times and distances")]
+	MismatchedRaces,
+	#[error("invalid number: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+	#[error("no races found")]
+	NoRaces,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 #[derive(Debug,Clone,Copy,PartialEq,Eq)]
 pub struct Race {
 	time: usize,
 	dist: usize,
 }
 impl Race {
-	/// Returns "button held down for", and "distance went"
-	fn best(&self) -> impl Iterator<Item = (usize, usize)> {
-		let Race { time, dist } = *self;
-		(0..self.time)
-			.map::<(usize, usize), _>(move |held| {
-				(held, held*(time - held))
-			})
-			.filter(move |&(_, d)| d > dist)
+	/// How far the boat goes with the button held down for `held`, widened as it can go well past the record
+	fn went(&self, held: u128) -> u128 {
+		held * (self.time as u128 - held)
+	}
+
+	/// How many ways there are to hold the button down and beat the record.
+	///
+	/// Holding it for `h` goes `h * (time - h)`, which is symmetric around `time / 2`, so the winning holds are
+	/// every one between the roots of `h^2 - time*h + dist = 0`. An integer square root gets the lower root to
+	/// within one, and checking its neighbours settles the rest.
+	fn ways(&self) -> usize {
+		let (time, dist) = (self.time as u128, self.dist as u128);
+		// the furthest it can go is from holding it for half the time
+		if self.went(time / 2) <= dist {
+			return 0;
+		}
+		let root = numtheory::isqrt(time * time - 4 * dist);
+		let mut lowest = (time - root) / 2;
+		while self.went(lowest) <= dist {
+			lowest += 1;
+		}
+		while lowest > 0 && self.went(lowest - 1) > dist {
+			lowest -= 1;
+		}
+		(time - 2 * lowest + 1) as usize
 	}
 }
 
+/// Appends the digits of `b` onto `a`
+fn concat_digits(a: usize, b: usize) -> Result<usize, Overflow> {
+	const WHAT: &str = "combining the races";
+	let shift = arith::pow(10usize, b.checked_ilog10().unwrap_or(0)+1, WHAT)?;
+	arith::add(arith::mul(a, &shift, WHAT)?, &b, WHAT)
+}
+
 #[derive(Debug)]
 pub struct RaceResults {
 	separate: Vec<Race>,
-	combined: Race,
+	/// The races read as one, which only part 2 needs, so failing to combine them only fails that part
+	combined: Result<Race, Overflow>,
 }
 
 impl AoCDay for Day06 {
@@ -40,46 +87,84 @@ impl AoCDay for Day06 {
 	fn day(&self) -> u8 { 6 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		let (times, dists) = input.trim().split_once('\n').expect("not two lines");
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
+	}
+	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		self.try_part1(_data).unwrap_or_else(|e| panic!("{}", e))
+	}
+	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		self.try_part2(_data).unwrap_or_else(|e| panic!("{}", e))
+	}
+}
 
-		let itimes = times.split(' ').filter_map(aoch::parsing::trimmed);
-		let idists = dists.split(' ').filter_map(aoch::parsing::trimmed);
+impl TrySolve for Day06 {
+	fn try_part1(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		Ok(_data.separate.iter()
+			.map(|r| r.ways())
+			.try_fold(1, |acc, ways| arith::mul(acc, &ways, "multiplying the ways to win"))?)
+	}
+	fn try_part2(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		Ok(_data.combined?.ways())
+	}
+}
 
-		let separate = itimes.zip_eq(idists)
-			.skip(1) // row title
+impl Explain for Day06 {}
+
+impl Visualize for Day06 {}
+
+impl TryParse for Day06 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		let raw = input.trim();
+		let (times, dists) = raw.split_once('\n')
+			.ok_or_else(|| ParseError::new(input, raw, ParseErrorKind::MissingLine("Distance:")))?;
+
+		// checks the row title, returning the row's numbers
+		let row = |line: &'i str, title: &'static str| {
+			let line = line.trim();
+			let nums = line.strip_prefix(title)
+				.ok_or_else(|| ParseError::new(input, line, ParseErrorKind::MissingLine(title)))?;
+			nums.split(' ')
+				.filter_map(aoch::parsing::trimmed)
+				.map(|n| n.parse::<usize>().map_err(|e| ParseError::new(input, n, ParseErrorKind::InvalidNumber(e))))
+				.collect::<Result<Vec<_>, _>>()
+				.map(|v| (line, v))
+		};
+		let (_, itimes) = row(times, "Time:")?;
+		let (dists, idists) = row(dists, "Distance:")?;
+		if itimes.len() != idists.len() {
+			return Err(ParseError::new(input, dists, ParseErrorKind::MismatchedRaces));
+		}
+
+		let separate = itimes.into_iter().zip(idists)
 			// .inspect(|d| eprintln!("{:?}", d))
-			.map(|(stime, sdist)| (stime.parse().unwrap(), sdist.parse().unwrap()))
 			.map(|(time, dist)| Race { time, dist })
 			.collect_vec();
 
+		if separate.is_empty() {
+			return Err(ParseError::new(input, raw, ParseErrorKind::NoRaces));
+		}
 		let combined = separate.iter()
 			.copied()
-			.reduce(|mut acc, race| {
+			.try_reduce(|mut acc, race| {
 				// eprintln!("reducing {:?} into {:?}", race, acc);
-				acc.time = acc.time*(10usize).pow(race.time.ilog10()+1) + race.time;
-				acc.dist = acc.dist*(10usize).pow(race.dist.ilog10()+1) + race.dist;
-				acc
-			}).unwrap();
+				acc.time = concat_digits(acc.time, race.time)?;
+				acc.dist = concat_digits(acc.dist, race.dist)?;
+				Ok::<_, Overflow>(acc)
+			})
+			.map(|combined| combined.expect("there is at least one race"));
 
 		// eprintln!("combined: {:?}", combined);
 
-		RaceResults {
+		Ok(RaceResults {
 			separate,
 			combined,
-		}
-	}
-	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		_data.separate.iter()
-			.map(|r| r.best().count())
-			.product()
-	}
-	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		_data.combined.best().count()
+		})
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+pub const TEST_INPUT: &'static str = "
 Time:      7  15   30
 Distance:  9  40  200
 ";
@@ -98,7 +183,6 @@ fn fuel_calc() {
 fn part1() {
 	let cases = [
 		(TEST_INPUT, 288),
-		(daystr!("06"), 771628),
 	];
 	test_runner::<Day06, _>(Day06, DayPart::Part1, &cases);
 }
@@ -106,7 +190,25 @@ fn part1() {
 fn part2() {
 	let cases = [
 		(TEST_INPUT, 71503),
-		(daystr!("06"), 27363861),
 	];
 	test_runner::<Day06, _>(Day06, DayPart::Part2, &cases);
 }
+
+#[test]
+fn ways() {
+	// against trying every hold
+	for time in 0..60 {
+		for dist in 0..time*time/4 + 3 {
+			let race = Race { time, dist };
+			let tried = (0..=time as u128).filter(|&held| race.went(held) > dist as u128).count();
+			assert_eq!(race.ways(), tried, "{:?}", race);
+		}
+	}
+	let race = Race { time: usize::MAX, dist: usize::MAX };
+	assert_eq!(race.ways(), usize::MAX - 3);
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day06, daystr!("06"));
+}
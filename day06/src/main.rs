@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day06::Day06;
 
 fn main() {
-	run_day::<Day06>(Day06, daystr!("06"), None);
+	aocinput::source::for_each_input(daystr!("06"), |input| {
+		run_day::<Day06>(Day06, input, None);
+	});
 }
 
@@ -0,0 +1,46 @@
+use aocinput::gen::{rng, Generate, Generated, Rng};
+use crate::Day07;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub hands: usize,
+	/// The largest bid on a hand
+	pub bid: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { hands: 1000, bid: 1000 }
+	}
+}
+
+impl Generate for Day07 {
+	type Params = Params;
+
+	/// Ranking the hands is the puzzle itself, so no answers are generated.
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let mut input = String::new();
+		for _ in 0..params.hands {
+			// favour a smaller set of cards now and then, so there are more pairs and jokers
+			let cards = if rng.gen_bool(0.3) { &CARDS[7..] } else { CARDS };
+			let hand: String = (0..5).map(|_| cards[rng.gen_range(0..cards.len())] as char).collect();
+			input += &format!("{} {}\n", hand, rng.gen_range(1..=params.bid.max(1)));
+		}
+		Generated { input, part1: None, part2: None }
+	}
+}
+
+#[test]
+fn generated() {
+	use aoch::AoCDay;
+	// nothing to compare against, but it should still be solvable
+	for seed in 0..5 {
+		let generated = Day07.generate(seed, &Params::default());
+		let mut data = Day07.parse(&generated.input);
+		Day07.part1(&mut data);
+		Day07.part2(&mut data);
+	}
+}
@@ -1,6 +1,6 @@
 #![allow(unused_imports)]
 use std::cmp::Reverse;
-use std::{str::FromStr, cmp::Ordering};
+use std::{fmt, str::FromStr, cmp::Ordering};
 use std::fmt::Debug;
 use itertools::Itertools;
 use num_traits::{FromPrimitive, ToPrimitive};
@@ -8,8 +8,14 @@ use num_derive::{FromPrimitive, ToPrimitive};
 use test_log::test;
 use arrayvec::ArrayVec;
 use aoch::AoCDay;
+use aocinput::{Explain, Explanation, Located, TryParse, TrySolve, Visualize};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug,Clone,PartialEq,Eq,PartialOrd,Ord,FromPrimitive,ToPrimitive)]
 enum Card {
@@ -17,6 +23,17 @@ enum Card {
 	T, J, Q, K, A,
 }
 impl Card {
+	fn to_char(&self) -> char {
+		use Card::*;
+		match self {
+			T => 'T',
+			J => 'J',
+			Q => 'Q',
+			K => 'K',
+			A => 'A',
+			n => char::from_digit(ToPrimitive::to_u32(n).unwrap(), 10).unwrap(),
+		}
+	}
 	fn rank(&self, wildcard_joker: bool) -> u32 {
 		match (wildcard_joker, self) {
 			(true, Card::J) => 1,
@@ -36,6 +53,19 @@ enum HandType {
 	HighCard,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a `<cards> <bid>` pair")]
+	MissingBid,
+	#[error("invalid bid: {0}")]
+	InvalidBid(std::num::ParseIntError),
+	#[error("unexpected card character")]
+	UnknownCard,
+	#[error("expected a hand of five cards, found {0}")]
+	WrongHandSize(usize),
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Play {
 	cards: [Card; 5],
@@ -43,26 +73,40 @@ pub struct Play {
 }
 
 impl FromStr for Play {
-	type Err = ();
+	type Err = ParseError;
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		use Card::*;
 
-		let (cards, bid) = s.trim().split_once(' ').unwrap();
-		let bid = bid.parse().unwrap();
+		let s = s.trim();
+		let (cards, bid) = s.split_once(' ')
+			.ok_or_else(|| ParseError::new(s, s, ParseErrorKind::MissingBid))?;
+		let bid = bid.parse()
+			.map_err(|e| ParseError::new(s, bid, ParseErrorKind::InvalidBid(e)))?;
 
-		let (c1, c2, c3, c4, c5) = cards.chars()
-			.map(|c| match c {
+		let parsed = cards.char_indices()
+			.map(|(i, c)| Ok(match c {
 				'2'..='9' => FromPrimitive::from_u32((c as u32) - ('0' as u32)).unwrap(),
 				'T' => T,
 				'J' => J,
 				'Q' => Q,
 				'K' => K,
 				'A' => A,
-				_ => panic!("unexpected card character: {:?}", c),
-			})
-			.collect_tuple().unwrap();
+				_ => return Err(ParseError::new(s, &cards[i..i+c.len_utf8()], ParseErrorKind::UnknownCard)),
+			}))
+			.collect::<Result<Vec<Card>, _>>()?;
+
+		let cards = <[Card; 5]>::try_from(parsed)
+			.map_err(|v| ParseError::new(s, cards, ParseErrorKind::WrongHandSize(v.len())))?;
 
-		Ok(Play { cards: [c1, c2, c3, c4, c5], bid })
+		Ok(Play { cards, bid })
+	}
+}
+
+/// Formats the play as it would appear in the input.
+impl fmt::Display for Play {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let cards: String = self.cards.iter().map(Card::to_char).collect();
+		write!(f, "{} {}", cards, self.bid)
 	}
 }
 
@@ -76,7 +120,7 @@ impl Play {
 			.collect();
 		partitions.sort_by_key(|p| Reverse(p.clone())); // sort by group length
 
-		log::trace!("hand_type[{:?}], partitions = {:?}", self, partitions);
+		tracing::trace!("hand_type[{:?}], partitions = {:?}", self, partitions);
 
 		if wildcard_joker {
 			let opt_joker = partitions.iter()
@@ -130,14 +174,14 @@ impl AoCDay for Day07 {
 	fn day(&self) -> u8 { 7 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		aoch::parsing::from_lines(input).unwrap()
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
 		_data.sort_by(|a, b| a.cmp(b).reverse());
 		_data.iter()
 			.enumerate()
 			.map(|(rank, play)| (rank+1, play))
-			.inspect(|(rank, play)| log::debug!("rank {}: {:?} w/ {:?}", rank, play, play.hand_type(false)))
+			.inspect(|(rank, play)| tracing::debug!("rank {}: {:?} w/ {:?}", rank, play, play.hand_type(false)))
 			.map(|(rank, play)| rank * play.bid)
 			.sum::<usize>()
 	}
@@ -146,14 +190,50 @@ impl AoCDay for Day07 {
 		_data.iter()
 			.enumerate()
 			.map(|(rank, play)| (rank+1, play))
-			.inspect(|(rank, play)| log::debug!("rank {}: {:?} w/ {:?}", rank, play, play.hand_type(true)))
+			.inspect(|(rank, play)| tracing::debug!("rank {}: {:?} w/ {:?}", rank, play, play.hand_type(true)))
 			.map(|(rank, play)| rank * play.bid)
 			.sum::<usize>()
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+impl TrySolve for Day07 {}
+
+impl Explain for Day07 {
+	fn explain_part1(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		_data.sort_by(|a, b| a.cmp(b).reverse());
+		Some(explain_ranks(_data, false))
+	}
+	fn explain_part2(&self, _data: &mut Self::Data<'_>) -> Option<Explanation> {
+		_data.sort_by(|a, b| a.cmp_joker(b).reverse());
+		Some(explain_ranks(_data, true))
+	}
+}
+
+impl Visualize for Day07 {}
+
+/// Explains the winnings of each play, which have already been sorted from the weakest to the strongest hand
+fn explain_ranks(plays: &[Play], wildcard_joker: bool) -> Explanation {
+	let mut explanation = Explanation::default();
+	let mut total = 0;
+	for (rank, play) in plays.iter().enumerate().map(|(rank, play)| (rank+1, play)) {
+		total += rank * play.bid;
+		explanation.step(
+			format!("rank {}", rank),
+			format!("{} is {:?}, wins {} * {} = {}, total {}", play, play.hand_type(wildcard_joker), rank, play.bid, rank * play.bid, total),
+		);
+	}
+	explanation
+}
+
+impl TryParse for Day07 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		aocinput::from_lines(input)
+	}
+}
+
+pub const TEST_INPUT: &'static str = "
 32T3K 765
 T55J5 684
 KK677 28
@@ -171,11 +251,24 @@ fn fuel_calc() {
 }
 */
 
+#[test]
+fn parse_errors() {
+	let cases = [
+		("32T3K 765\nT55X5 684", Some((2, 4, "X".into(), ParseErrorKind::UnknownCard))),
+		("32T3K 765\nT55J5", Some((2, 1, "T55J5".into(), ParseErrorKind::MissingBid))),
+		("32T3K7 765", Some((1, 1, "32T3K7".into(), ParseErrorKind::WrongHandSize(6)))),
+		("32T3K 765\nKK677 28", None),
+	];
+	run_test(|input| {
+		Day07.try_parse(input).err()
+			.map(|e| (e.span.line, e.span.col, e.span.text, e.kind))
+	}, &cases);
+}
+
 #[test]
 fn part1() {
 	let cases = [
 		(TEST_INPUT, 6440),
-		(daystr!("07"), 254024898),
 	];
 	test_runner::<Day07, _>(Day07, DayPart::Part1, &cases);
 }
@@ -183,7 +276,23 @@ fn part1() {
 fn part2() {
 	let cases = [
 		(TEST_INPUT, 5905),
-		(daystr!("07"), 254115617),
 	];
 	test_runner::<Day07, _>(Day07, DayPart::Part2, &cases);
 }
+
+#[test]
+fn explain() {
+	let mut plays = Day07.parse(TEST_INPUT);
+	let explanation = Day07.explain_part1(&mut plays).unwrap();
+	assert_eq!(explanation.steps[0].subject, "rank 1");
+	assert_eq!(explanation.steps[0].detail, "32T3K 765 is OnePair, wins 1 * 765 = 765, total 765");
+	assert!(explanation.steps[4].detail.ends_with("total 6440"));
+
+	let explanation = Day07.explain_part2(&mut plays).unwrap();
+	assert_eq!(explanation.steps[4].detail, "KTJJT 220 is FourOfAKind, wins 5 * 220 = 1100, total 5905");
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day07, daystr!("07"));
+}
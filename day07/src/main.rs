@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day07::Day07;
 
 fn main() {
-	run_day::<Day07>(Day07, daystr!("07"), None);
+	aocinput::source::for_each_input(daystr!("07"), |input| {
+		run_day::<Day07>(Day07, input, None);
+	});
 }
 
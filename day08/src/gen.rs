@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+use aocinput::arith::{answer, Answer};
+use aocinput::gen::{rng, Generate, Generated, Rng, SliceRandom};
+use crate::Day08;
+
+/// Characters for the rest of a node name, leaving out `A` and `Z` to keep them from being starts or ends
+const NAME: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY0123456789";
+const PRIMES: [usize; 16] = [7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	/// Length of the left/right instructions
+	pub directions: usize,
+	/// How many start nodes there are for part 2, including `AAA`
+	pub ghosts: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { directions: 31, ghosts: 6 }
+	}
+}
+
+impl Generate for Day08 {
+	type Params = Params;
+
+	/// Each ghost follows its own chain of nodes from its start to its end, and then from its end back around
+	/// to the second node of the chain. Every chain is a multiple of the directions long, so a ghost is always
+	/// told the same way at the same node, and reaches its end at a fixed interval.
+	fn generate(&self, seed: u64, params: &Params) -> Generated<Answer> {
+		let mut rng = rng(seed);
+		let directions: String = (0..params.directions.max(1))
+			.map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+			.collect();
+		let ghosts = params.ghosts.clamp(1, PRIMES.len());
+		assert!(ghosts * PRIMES[PRIMES.len()-1] * directions.len() < NAME.len().pow(3) / 2, "too many nodes to name");
+
+		let (mut used, mut prefixes) = (HashSet::new(), HashSet::new());
+		let mut fresh_name = |rng: &mut _| loop {
+			let name: String = (0..3).map(|_| *NAME.choose(rng).unwrap() as char).collect();
+			if used.insert(name.clone()) { break name; }
+		};
+
+		let mut laps: Vec<usize> = PRIMES.choose_multiple(&mut rng, ghosts).copied().collect();
+		laps.sort();
+		let mut nodes = Vec::new();
+		for (g, lap) in laps.iter().enumerate() {
+			let (start, end) = match g {
+				0 => ("AAA".to_owned(), "ZZZ".to_owned()),
+				_ => loop {
+					let prefix = fresh_name(&mut rng)[..2].to_owned();
+					if prefixes.insert(prefix.clone()) {
+						break (prefix.clone() + "A", prefix + "Z");
+					}
+				},
+			};
+
+			let len = lap * directions.len();
+			let mut chain = vec![start];
+			chain.extend((1..len).map(|_| fresh_name(&mut rng)));
+			chain.push(end);
+
+			for (i, node) in chain.iter().enumerate() {
+				// the end loops back around to the second node
+				let next = if i == len { &chain[1] } else { &chain[i+1] };
+				// the other way is never taken, so it can go anywhere
+				let other = chain.choose(&mut rng).unwrap();
+				let (left, right) = match directions.as_bytes()[i % directions.len()] {
+					b'L' => (next, other),
+					_ => (other, next),
+				};
+				nodes.push(format!("{} = ({}, {})", node, left, right));
+			}
+		}
+		nodes.shuffle(&mut rng);
+
+		let steps: Vec<usize> = laps.iter().map(|lap| lap * directions.len()).collect();
+		let part1 = steps[0];
+		let part2 = numtheory::lcm_all(&steps).expect("too many steps for the ghosts to line up");
+
+		Generated {
+			input: format!("{}\n\n{}\n", directions, nodes.join("\n")),
+			part1: Some(answer(part1)),
+			part2: Some(answer(part2)),
+		}
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day08, 0..10, &Params { directions: 11, ghosts: 4 });
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel() {
+	use aoch::AoCDay;
+	use crate::NodeType;
+	let start = |n| n == NodeType::Start || n == NodeType::FullStart;
+	let end = |n| n == NodeType::End || n == NodeType::FullEnd;
+	for seed in 0..10 {
+		let map = Day08.parse(&Day08.generate(seed, &Params::default()).input);
+		assert_eq!(map.run_nodes_serial(start, end), map.run_nodes_parallel(start, end), "seed {}", seed);
+	}
+}
@@ -5,8 +5,16 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Animation, Explain, Located, Overflow, SolveError, TryParse, TrySolve, Visualize};
+use aocinput::visual::Tone;
+use aocinput::arith::{self, answer, Answer};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 /// Each mapping is stored within a vector. During parsing, the string-based mapping
 /// is converted to use indicies into a vector, where the only source info remaining
@@ -16,6 +24,19 @@ use aoch::{DayPart, run_test, test_runner, daystr};
 #[derive(Debug,Clone,Copy)]
 pub struct Day08;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a line of L/R directions")]
+	MissingDirections,
+	#[error("unknown direction")]
+	InvalidDirection,
+	#[error("expected a `<node> = (<left>, <right>)` mapping")]
+	InvalidMapping,
+	#[error("node is never defined")]
+	UnknownNode,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 #[derive(Debug,Clone,Copy,PartialEq)]
 enum NodeType {
 	None,
@@ -45,10 +66,23 @@ impl NodeType {
 pub struct IndexedMap {
 	directions: String,
 	mapping: Vec<(NodeType, (usize, usize))>,
+	/// Each node's name, only kept to show where the ghosts are
+	names: Vec<String>,
 }
 
 impl IndexedMap {
-	fn run_nodes<FS: FnMut(NodeType) -> bool, FE: FnMut(NodeType) -> bool>(&self, mut start_node_pred: FS, mut end_node_pred: FE) -> usize {
+	/// Finds how many steps it takes for every ghost on a start node to be on an end node at the same time.
+	fn run_nodes(&self, start_node_pred: fn(NodeType) -> bool, end_node_pred: fn(NodeType) -> bool) -> Result<Answer, Overflow> {
+		#[cfg(feature = "parallel")]
+		return self.run_nodes_parallel(start_node_pred, end_node_pred);
+		#[cfg(not(feature = "parallel"))]
+		return self.run_nodes_serial(start_node_pred, end_node_pred);
+	}
+
+	/// Steps every ghost in lockstep, setting each aside once it reaches an end node.
+	// only checked against the parallel path in tests when that is enabled
+	#[cfg_attr(feature = "parallel", allow(dead_code))]
+	fn run_nodes_serial<FS: FnMut(NodeType) -> bool, FE: FnMut(NodeType) -> bool>(&self, mut start_node_pred: FS, mut end_node_pred: FE) -> Result<Answer, Overflow> {
 		// Vec<(start_idx, curr_idx)>
 		let mut current_nodes = self.mapping.iter()
 			.enumerate()
@@ -59,13 +93,13 @@ impl IndexedMap {
 		// Vec<(start_idx, Vec<(end_idx, step)>)>
 		let mut found_cycles: Vec<(usize, Vec<(usize, usize)>)> = Default::default();
 
-		log::info!("running with {} nodes for directions {}", current_nodes.len(), self.directions);
+		tracing::info!(alive = current_nodes.len(), "running with {} nodes for directions {}", current_nodes.len(), self.directions);
 
 		let mut steps = 1;
 		let dirs = std::iter::repeat(self.directions.as_str()).flat_map(str::chars);
 
 		for dir in dirs { // will never return None, but prevents an unwrap
-			log::trace!("step[{}] => current={:?}, found={:?}", steps, current_nodes, found_cycles);
+			tracing::trace!("step[{}] => current={:?}, found={:?}", steps, current_nodes, found_cycles);
 			current_nodes.retain_mut(|(start, curr)| {
 				// update current node
 				let next = self.mapping.get(*curr);
@@ -103,22 +137,60 @@ impl IndexedMap {
 
 			steps += 1;
 			if steps % 1_000_000 == 0 {
-				log::debug!("steps = {}M, found = {:?}, current = {:?}", steps/1_000_000, found_cycles, current_nodes);
+				tracing::debug!(steps, alive = current_nodes.len(), found = found_cycles.len(), "steps = {}M, found = {:?}, current = {:?}", steps/1_000_000, found_cycles, current_nodes);
 			}
 
 			if current_nodes.is_empty() {
-				log::debug!("found = {:?}, current = {:?}", found_cycles, current_nodes);
+				tracing::debug!("found = {:?}, current = {:?}", found_cycles, current_nodes);
 				break;
 			}
 		}
 
-		let lcm_steps: usize = found_cycles.iter()
+		found_cycles.iter()
 			.flat_map(|(start, ends)| ends.iter().map(move |(end, steps)| (*start, *end, *steps)))
-			.inspect(|(start, end, steps)| log::debug!("{:?} --{}--> {:?}", start, steps, end))
+			.inspect(|(start, end, steps)| tracing::debug!("{:?} --{}--> {:?}", start, steps, end))
 			.map(|(_start, _end, steps)| steps)
-			.fold(1, |acc, steps| num::Integer::lcm(&acc, &steps));
+			.try_fold(answer(1), |acc, steps| arith::lcm(acc, answer(steps), "lining up every ghost's steps"))
+	}
+
+	/// Walks each ghost to its first end node on its own thread, as the ghosts never affect each other.
+	#[cfg(feature = "parallel")]
+	fn run_nodes_parallel(&self, start_node_pred: fn(NodeType) -> bool, end_node_pred: fn(NodeType) -> bool) -> Result<Answer, Overflow> {
+		use rayon::prelude::*;
 
-		lcm_steps
+		let starts = self.mapping.iter()
+			.enumerate()
+			.filter(|(_i, (nt, _))| start_node_pred(*nt))
+			.map(|(i, _)| i)
+			.collect_vec();
+
+		tracing::info!(alive = starts.len(), "running with {} nodes for directions {}", starts.len(), self.directions);
+
+		starts.into_par_iter()
+			.map(|start| {
+				let steps = self.steps_to_end(start, end_node_pred);
+				tracing::debug!("{:?} --{}--> end", start, steps);
+				Ok(answer(steps))
+			})
+			.try_reduce(|| answer(1), |acc, steps| arith::lcm(acc, steps, "lining up every ghost's steps"))
+	}
+
+	/// Counts the steps from `start` until the first node that `end_node_pred` accepts.
+	#[cfg(feature = "parallel")]
+	fn steps_to_end(&self, start: usize, end_node_pred: fn(NodeType) -> bool) -> usize {
+		let mut curr = start;
+		for (steps, dir) in self.directions.chars().cycle().enumerate() {
+			let (_, (left, right)) = self.mapping[curr];
+			curr = match dir {
+				'L' => left,
+				'R' => right,
+				l => panic!("unknown direction: {}", l),
+			};
+			if end_node_pred(self.mapping[curr].0) {
+				return steps + 1;
+			}
+		}
+		unreachable!("directions repeat forever")
 	}
 }
 
@@ -128,63 +200,172 @@ pub struct Map<'s> {
 	mapping: Vec<(&'s str, (&'s str, &'s str))>,
 }
 impl<'s> Map<'s> {
-	fn compile(&mut self) -> IndexedMap {
+	/// Converts node names into indices. Names that are never defined are reported relative to `input`,
+	/// which the map's strings must be borrowed from.
+	fn compile(&mut self, input: &str) -> Result<IndexedMap, ParseError> {
 		// sort it so we can search it better for the new mapping
 		self.mapping.sort_by_key(|f| f.0);
 
+		let index = |name: &str| self.mapping.binary_search_by_key(&name, |n| n.0)
+			.map_err(|_| ParseError::new(input, name, ParseErrorKind::UnknownNode));
+
 		let mapping = self.mapping.iter()
 			.map(|(n, (l, r))| {
-				let li = self.mapping.binary_search_by_key(l, |n| n.0).unwrap();
-				let ri = self.mapping.binary_search_by_key(r, |n| n.0).unwrap();
-				(NodeType::classify(n), (li, ri))
+				Ok((NodeType::classify(n), (index(l)?, index(r)?)))
 			})
-			.collect_vec();
+			.collect::<Result<Vec<_>, _>>()?;
 
-		IndexedMap {
+		Ok(IndexedMap {
 			directions: self.directions.to_owned(),
-			mapping
-		}
+			mapping,
+			names: self.mapping.iter().map(|(n, _)| n.to_string()).collect(),
+		})
 	}
 }
 
 impl AoCDay for Day08 {
 	type Data<'i> = IndexedMap;
-	type Answer = usize;
+	type Answer = Answer;
 
 	fn day(&self) -> u8 { 8 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
+	}
+	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		self.try_part1(_data).unwrap_or_else(|e| panic!("{}", e))
+	}
+	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		self.try_part2(_data).unwrap_or_else(|e| panic!("{}", e))
+	}
+}
+
+impl TrySolve for Day08 {
+	fn try_part1(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		Ok(_data.run_nodes(|n| n == NodeType::FullStart, |n| n == NodeType::FullEnd)?)
+	}
+	fn try_part2(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		Ok(_data.run_nodes(
+			|n| n == NodeType::Start || n == NodeType::FullStart,
+			|n| n == NodeType::End || n == NodeType::FullEnd,
+		)?)
+	}
+}
+
+impl Explain for Day08 {}
+
+/// The most steps the ghosts are animated for, in case some of them never reach an end node
+const MAX_STEPS: usize = 100_000;
+
+impl IndexedMap {
+	/// Animates every ghost on a start node stepping along together, until each has reached an end node.
+	fn ghost_walk(&self, title: &str, start_node_pred: fn(NodeType) -> bool, end_node_pred: fn(NodeType) -> bool) -> Option<Animation> {
+		// (row, current node, steps taken to reach an end node)
+		let mut ghosts = self.mapping.iter()
+			.enumerate()
+			.filter(|(_i, (nt, _))| start_node_pred(*nt))
+			.enumerate()
+			.map(|(row, (i, _))| (row + 3, i, None))
+			.collect_vec();
+		if ghosts.is_empty() {
+			return None;
+		}
+
+		let name_width = self.names.iter().map(String::len).max().unwrap_or(0);
+		let reached = name_width * 2 + 4;
+		let width = self.directions.len().max(reached + " reached an end after 1000000 steps".len());
+		let mut animation = Animation::blank(title, width, ghosts.len() + 3);
+
+		let frame = animation.push(format!("{} ghosts start out", ghosts.len()));
+		frame.text((0, 0), &self.directions, Tone::Dim).text((0, 1), "step 0", Tone::Plain);
+		for &(row, node, _) in &ghosts {
+			frame.text((0, row), &format!("{:w$} -> {:w$}", self.names[node], self.names[node], w = name_width), Tone::Plain);
+		}
+
+		let dirs = self.directions.chars().cycle().enumerate().take(MAX_STEPS);
+		for (i, dir) in dirs {
+			let steps = i + 1;
+			let frame = animation.push(format!("step {}: going {}", steps, dir));
+			frame.text((0, 1), &format!("step {}", steps), Tone::Plain)
+				.focus((i % self.directions.len(), 0));
+			for (row, node, ended) in ghosts.iter_mut().filter(|(_, _, ended)| ended.is_none()) {
+				let (_, (left, right)) = self.mapping[*node];
+				*node = if dir == 'L' { left } else { right };
+				let at = (name_width + 4, *row);
+				if end_node_pred(self.mapping[*node].0) {
+					*ended = Some(steps);
+					frame.text(at, &self.names[*node], Tone::Highlight)
+						.text((reached, *row), &format!(" reached an end after {} steps", steps), Tone::Highlight);
+				} else {
+					frame.text(at, &self.names[*node], Tone::Path);
+				}
+			}
+			if ghosts.iter().all(|(_, _, ended)| ended.is_some()) {
+				break;
+			}
+		}
+
+		let steps = ghosts.iter().filter_map(|(_, _, ended)| ended.map(|s| s as u128)).collect_vec();
+		let caption = if steps.len() < ghosts.len() {
+			format!("stopped after {} steps, with {} ghosts yet to reach an end", MAX_STEPS, ghosts.len() - steps.len())
+		} else {
+			match numtheory::lcm_all(&steps) {
+				Some(lined_up) => format!("every ghost is on an end node together after {} steps", lined_up),
+				None => "the ghosts line up too many steps away to count".to_string(),
+			}
+		};
+		animation.push(caption);
+		Some(animation)
+	}
+}
+
+impl Visualize for Day08 {
+	fn visualize_part1(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		_data.ghost_walk("Day 08: walking from AAA to ZZZ", |n| n == NodeType::FullStart, |n| n == NodeType::FullEnd)
+	}
+	fn visualize_part2(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		_data.ghost_walk(
+			"Day 08: walking every ghost at once",
+			|n| n == NodeType::Start || n == NodeType::FullStart,
+			|n| n == NodeType::End || n == NodeType::FullEnd,
+		)
+	}
+}
+
+impl TryParse for Day08 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
 		let mut lines = input.lines()
 			.filter_map(aoch::parsing::trimmed);
-		let directions = lines.next().unwrap();
-		let mut mapping = lines.map(|line| {
-			let (key, dests) = line.split_once(" = ").unwrap();
-			let (left, right) = dests.split_once(", ").unwrap();
+		let directions = lines.next()
+			.ok_or_else(|| ParseError::new(input, input, ParseErrorKind::MissingDirections))?;
+		if let Some((i, c)) = directions.char_indices().find(|&(_, c)| c != 'L' && c != 'R') {
+			return Err(ParseError::new(input, &directions[i..i+c.len_utf8()], ParseErrorKind::InvalidDirection));
+		}
 
-			(key, (&left[1..], &right[..right.len()-1]))
+		let mut mapping = lines.map(|line| {
+			let (key, left, right) = line.split_once(" = ")
+				.and_then(|(key, dests)| {
+					let (left, right) = dests.split_once(", ")?;
+					Some((key, left.strip_prefix('(')?, right.strip_suffix(')')?))
+				})
+				.ok_or_else(|| ParseError::new(input, line, ParseErrorKind::InvalidMapping))?;
+
+			Ok((key, (left, right)))
 		})
-		.collect_vec();
+		.collect::<Result<Vec<_>, _>>()?;
 
 		mapping.sort();
 
 		Map {
 			directions,
 			mapping,
-		}.compile()
-	}
-	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		_data.run_nodes(|n| n == NodeType::FullStart, |n| n == NodeType::FullEnd)
-	}
-	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		_data.run_nodes(
-			|n| n == NodeType::Start || n == NodeType::FullStart,
-			|n| n == NodeType::End || n == NodeType::FullEnd,
-		)
+		}.compile(input)
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT_RL: &'static str = "
+pub const TEST_INPUT_RL: &'static str = "
 RL
 
 AAA = (BBB, CCC)
@@ -196,8 +377,7 @@ GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 ";
 
-#[cfg(test)]
-const TEST_INPUT_LLR: &'static str = "
+pub const TEST_INPUT_LLR: &'static str = "
 LLR
 
 AAA = (BBB, BBB)
@@ -205,8 +385,7 @@ BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 ";
 
-#[cfg(test)]
-const TEST_INPUT_LR_PAR: &'static str = "
+pub const TEST_INPUT_LR_PAR: &'static str = "
 LR
 
 11A = (11B, XXX)
@@ -232,17 +411,38 @@ fn fuel_calc() {
 #[test]
 fn part1() {
 	let cases = [
-		(TEST_INPUT_RL, 2),
-		(TEST_INPUT_LLR, 6),
-		(daystr!("08"), 22411),
+		(TEST_INPUT_RL, answer(2)),
+		(TEST_INPUT_LLR, answer(6)),
 	];
 	test_runner::<Day08, _>(Day08, DayPart::Part1, &cases);
 }
 #[test]
 fn part2() {
 	let cases = [
-		(TEST_INPUT_LR_PAR, 6),
-		(daystr!("08"), 11188774513823),
+		(TEST_INPUT_LR_PAR, answer(6)),
 	];
 	test_runner::<Day08, _>(Day08, DayPart::Part2, &cases);
 }
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day08, daystr!("08"));
+}
+
+#[test]
+fn visualized() {
+	let mut data = Day08.parse(TEST_INPUT_LLR);
+	let animation = Day08.visualize_part1(&mut data).unwrap();
+	// the start, six steps, and the answer
+	assert_eq!(animation.frames().len(), 8);
+	assert_eq!(animation.frames()[7].caption, "every ghost is on an end node together after 6 steps");
+
+	let mut data = Day08.parse(TEST_INPUT_LR_PAR);
+	assert!(Day08.visualize_part1(&mut data).is_none());
+	let animation = Day08.visualize_part2(&mut data).unwrap();
+	let last = animation.canvas(usize::MAX);
+	let row = |y| (0..last.width()).map(|x| last.get((x, y)).unwrap().glyph).collect::<String>();
+	assert_eq!(row(3).trim_end(), "11A -> 11Z reached an end after 2 steps");
+	assert_eq!(row(4).trim_end(), "22A -> 22Z reached an end after 3 steps");
+	assert_eq!(animation.frames().last().unwrap().caption, "every ghost is on an end node together after 6 steps");
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day08::Day08;
 
 fn main() {
-	run_day::<Day08>(Day08, daystr!("08"), None);
+	aocinput::source::for_each_input(daystr!("08"), |input| {
+		run_day::<Day08>(Day08, input, None);
+	});
 }
 
@@ -0,0 +1,51 @@
+use aocinput::gen::{rng, Generate, Generated, Rng};
+use crate::Day09;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub histories: usize,
+	/// Values in each history
+	pub length: usize,
+	/// Each history follows a polynomial of up to this degree, and so takes as many rounds of differences to settle
+	pub degree: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { histories: 200, length: 21, degree: 5 }
+	}
+}
+
+impl Generate for Day09 {
+	type Params = Params;
+
+	/// Each history is a polynomial evaluated at `0..length`, so the answers are just its values at `length` and `-1`.
+	fn generate(&self, seed: u64, params: &Params) -> Generated<isize> {
+		let mut rng = rng(seed);
+		let mut input = String::new();
+		let (mut part1, mut part2) = (0, 0);
+
+		for _ in 0..params.histories {
+			let coefficients: Vec<isize> = (0..=rng.gen_range(0..=params.degree))
+				.map(|_| rng.gen_range(-5..=5))
+				.collect();
+			let at = |x: isize| coefficients.iter().rev().fold(0, |acc, c| acc*x + c);
+
+			// enough values for the differences to settle to zero
+			let length = params.length.max(params.degree+2) as isize;
+			let values: Vec<String> = (0..length).map(|x| at(x).to_string()).collect();
+			input += &values.join(" ");
+			input.push('\n');
+
+			part1 += at(length);
+			part2 += at(-1);
+		}
+
+		Generated { input, part1: Some(part1), part2: Some(part2) }
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day09, 0..20, &Params::default());
+}
@@ -7,16 +7,35 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Explain, Located, TryParse, TrySolve, Visualize};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 #[derive(Debug,Clone,Copy)]
 pub struct Day09;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("invalid number: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+	#[error("a history needs at least two values")]
+	TooShort,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 #[derive(Debug,Clone)]
 struct Layers<T>(Vec<VecDeque<T>>, T);
 impl fmt::Display for Layers<isize> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		// a constant history has no layers to draw the base under
+		if self.0.is_empty() {
+			return write!(f, "{}", self.1);
+		}
 		let mut layers = self.clone();
 		layers.add_base_layer(self.1);
 		layers.add_base_layer(0);
@@ -94,6 +113,47 @@ impl<T: Clone + Copy + PartialEq> Sequence<T> {
 		Layers(layers, base)
 	}
 }
+impl Sequence<isize> {
+	/// Sums the history with a weight for each value, which is `None` if it overflows.
+	fn weighted_sum(&self, weight: impl Fn(usize) -> Option<i128>) -> Option<isize> {
+		self.0.iter().enumerate()
+			.try_fold(0i128, |acc, (i, &v)| acc.checked_add(weight(i)?.checked_mul(v as i128)?))
+			.and_then(|sum| sum.try_into().ok())
+	}
+
+	/// A history of `n` values always settles within `n` rounds of differences, so its `n`th differences are
+	/// zero. Expanding the `n`th difference that ends at the next value, or starts at the previous one, into the
+	/// history gives that value as an alternating sum of the others weighted by binomial coefficients.
+	///
+	/// The coefficients overflow for histories of 68 or more values, even when the value itself is small, so
+	/// those are extrapolated through their layers of differences instead.
+	fn next(&self) -> isize {
+		let n = self.0.len() as u64;
+		self.weighted_sum(|i| {
+			let sign = if (n - 1 - i as u64).is_multiple_of(2) { 1 } else { -1 };
+			numtheory::binomial(n, i as u64).map(|c| sign * c as i128)
+		}).unwrap_or_else(|| self.next_by_layers())
+	}
+	fn previous(&self) -> isize {
+		let n = self.0.len() as u64;
+		self.weighted_sum(|i| {
+			let sign = if i.is_multiple_of(2) { 1 } else { -1 };
+			numtheory::binomial(n, i as u64 + 1).map(|c| sign * c as i128)
+		}).unwrap_or_else(|| self.previous_by_layers())
+	}
+
+	fn next_by_layers(&self) -> isize {
+		let mut layers = self.find_differences();
+		layers.extrapolate_back();
+		// a constant history has no layers of differences, only its base
+		layers.0.first().map_or(layers.1, |l| *l.back().unwrap())
+	}
+	fn previous_by_layers(&self) -> isize {
+		let mut layers = self.find_differences();
+		layers.extrapolate_front();
+		layers.0.first().map_or(layers.1, |l| *l.front().unwrap())
+	}
+}
 
 impl AoCDay for Day09 {
 	type Data<'i> = Vec<Sequence<isize>>;
@@ -102,51 +162,75 @@ impl AoCDay for Day09 {
 	fn day(&self) -> u8 { 9 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		input.lines()
-			.filter_map(aoch::parsing::trimmed)
-			.map(|l| {
-				let mut seq = Sequence(l.split(' ').map(|ns| ns.parse().unwrap()).collect_vec());
-				seq.0.reserve(2); // preallocate for the actual puzzle
-				seq
-			})
-			.collect_vec()
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
 		_data.iter()
 			.map(|hist| {
-				let mut layers = hist.find_differences();
-				layers.extrapolate_back();
-				log::debug!("extrapolated history ({:?}):\n{}", hist, layers);
-				let n = *layers.0.first().as_ref().unwrap().back().unwrap();
-				n
+				if tracing::enabled!(tracing::Level::DEBUG) {
+					let mut layers = hist.find_differences();
+					layers.extrapolate_back();
+					tracing::debug!("extrapolated history ({:?}):\n{}", hist, layers);
+				}
+				hist.next()
 			})
 			.sum()
 	}
 	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
 		_data.iter()
 			.map(|hist| {
-				let mut layers = hist.find_differences();
-				layers.extrapolate_front();
-				log::debug!("extrapolated history ({:?}):\n{}", hist, layers);
-				let n = *layers.0.first().as_ref().unwrap().front().unwrap();
-				n
+				if tracing::enabled!(tracing::Level::DEBUG) {
+					let mut layers = hist.find_differences();
+					layers.extrapolate_front();
+					tracing::debug!("extrapolated history ({:?}):\n{}", hist, layers);
+				}
+				hist.previous()
 			})
 			.sum()
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+impl TrySolve for Day09 {}
+
+impl Explain for Day09 {}
+
+impl Visualize for Day09 {}
+
+impl TryParse for Day09 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		input.lines()
+			.filter_map(aoch::parsing::trimmed)
+			.map(|l| {
+				let mut seq = Sequence(l.split(' ')
+					.map(|ns| ns.parse().map_err(|e| ParseError::new(input, ns, ParseErrorKind::InvalidNumber(e))))
+					.collect::<Result<Vec<_>, _>>()?);
+				if seq.0.len() < 2 {
+					return Err(ParseError::new(input, l, ParseErrorKind::TooShort));
+				}
+				seq.0.reserve(2); // preallocate for the actual puzzle
+				Ok(seq)
+			})
+			.collect()
+	}
+}
+
+pub const TEST_INPUT: &'static str = "
 0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45
 ";
 
-#[cfg(test)]
-const TEST_INPUT_3: &'static str = "
+pub const TEST_INPUT_3: &'static str = "
 10 13 16 21 30 45
 ";
 
+pub const TEST_INPUT_CONSTANT: &'static str = "
+7 7 7
+-2 -2
+";
+
 /*
 #[test]
 fn fuel_calc() {
@@ -161,7 +245,7 @@ fn fuel_calc() {
 fn part1() {
 	let cases = [
 		(TEST_INPUT, 114),
-		(daystr!("09"), 1853145119),
+		(TEST_INPUT_CONSTANT, 5),
 	];
 	test_runner::<Day09, _>(Day09, DayPart::Part1, &cases);
 }
@@ -170,7 +254,34 @@ fn part2() {
 	let cases = [
 		(TEST_INPUT_3, 5),
 		(TEST_INPUT, 2),
-		(daystr!("09"), 923),
+		(TEST_INPUT_CONSTANT, 5),
 	];
 	test_runner::<Day09, _>(Day09, DayPart::Part2, &cases);
 }
+
+#[test]
+fn binomial_sums() {
+	// extrapolating with binomial sums agrees with working through the layers of differences
+	let mut histories = Day09.parse(TEST_INPUT);
+	histories.extend(Day09.parse(TEST_INPUT_CONSTANT));
+	histories.push(Sequence((0..20).map(|x| x*x*x - 7*x*x + 2).collect()));
+	histories.push(Sequence(vec![-3, 8]));
+	for hist in histories {
+		assert_eq!((hist.next(), hist.previous()), (hist.next_by_layers(), hist.previous_by_layers()), "{:?}", hist);
+	}
+	assert_eq!(Sequence(vec![isize::MAX; 3]).weighted_sum(|_| Some(2)), None);
+}
+
+#[test]
+fn long_history() {
+	// too long for the binomial coefficients, though the values either side are small
+	let squares = (0..70isize).map(|x| x*x).join(" ");
+	let input = format!("{}\n{}\n", squares, ["3"; 68].join(" "));
+	test_runner::<Day09, _>(Day09, DayPart::Part1, &[(&input, 70*70 + 3)]);
+	test_runner::<Day09, _>(Day09, DayPart::Part2, &[(&input, 1 + 3)]);
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day09, daystr!("09"));
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day09::Day09;
 
 fn main() {
-	run_day::<Day09>(Day09, daystr!("09"), None);
+	aocinput::source::for_each_input(daystr!("09"), |input| {
+		run_day::<Day09>(Day09, input, None);
+	});
 }
 
@@ -0,0 +1,123 @@
+use aocinput::gen::{rng, Generate, Generated, Rng};
+use grid::{Coord, Direction};
+use crate::Day10;
+
+const PIPES: &[u8] = b"|-LJ7F";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub width: usize,
+	pub height: usize,
+	/// The chance that a tile off the loop is a stray pipe, rather than ground
+	pub junk: f64,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { width: 140, height: 140, junk: 0.6 }
+	}
+}
+
+/// The pipe connecting two directions
+fn pipe(a: Direction, b: Direction) -> u8 {
+	use Direction::*;
+	match (a.min(b), a.max(b)) {
+		(North, South) => b'|',
+		(East, West) => b'-',
+		(North, East) => b'L',
+		(North, West) => b'J',
+		(South, West) => b'7',
+		(East, South) => b'F',
+		pair => unreachable!("loop doubled back on itself: {:?}", pair),
+	}
+}
+
+/// The direction from one tile to a neighbouring one
+fn towards(from: Coord, to: Coord) -> Direction {
+	Direction::ALL.into_iter()
+		.find(|d| from + *d == Some(to))
+		.expect("loop tiles are not neighbours")
+}
+
+/// Walks from `from` to `to` in a straight line, not including `from`.
+fn line(path: &mut Vec<Coord>, to: Coord) {
+	let &(mut x, mut y) = path.last().unwrap();
+	while (x, y) != to {
+		x = if x < to.0 { x+1 } else if x > to.0 { x-1 } else { x };
+		y = if y < to.1 { y+1 } else if y > to.1 { y-1 } else { y };
+		path.push((x, y));
+	}
+}
+
+impl Generate for Day10 {
+	type Params = Params;
+
+	/// The loop goes around a region that is unbroken from top to bottom in every column. It runs along the top
+	/// edge from left to right, then back along the bottom edge. Then the enclosed area is found from the
+	/// shoelace formula, and the tiles inside it from Pick's theorem.
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let (width, height) = (params.width.max(5), params.height.max(6));
+		let (left, right) = (1, width-2);
+
+		// wander the top edge around, keeping space below it for the bottom edge
+		let mut top = vec![rng.gen_range(1..=height/2); width];
+		for x in left+1..=right {
+			top[x] = top[x-1].saturating_add_signed(rng.gen_range(-2..=2)).clamp(1, height-4);
+		}
+		// the bottom edge clears the top edge over the neighbouring columns, so the two never meet
+		let mut bottom = vec![0; width];
+		for x in left..=right {
+			let clear = top[x.saturating_sub(2).max(left)..=(x+2).min(right)].iter().max().unwrap() + 1;
+			bottom[x] = rng.gen_range(clear..=height-2);
+		}
+		// the ends of the loop run straight up and down
+		top[right] = top[right-1];
+		bottom[left] = bottom[left+1];
+
+		let mut path = vec![(left, top[left])];
+		for x in left+1..=right {
+			line(&mut path, (x, top[x-1]));
+			line(&mut path, (x, top[x]));
+		}
+		line(&mut path, (right, bottom[right]));
+		for x in (left..right).rev() {
+			line(&mut path, (x, bottom[x+1]));
+			line(&mut path, (x, bottom[x]));
+		}
+		line(&mut path, (left, top[left]));
+		path.pop(); // back at the start
+
+		let mut tiles: Vec<Vec<u8>> = (0..height)
+			.map(|_| (0..width).map(|_| {
+				if rng.gen_bool(params.junk) { PIPES[rng.gen_range(0..PIPES.len())] } else { b'.' }
+			}).collect())
+			.collect();
+		for (i, &(x, y)) in path.iter().enumerate() {
+			let prev = path[(i + path.len() - 1) % path.len()];
+			let next = path[(i + 1) % path.len()];
+			tiles[y][x] = pipe(towards((x, y), prev), towards((x, y), next));
+		}
+		// the start has to be found by what connects to it, so keep stray pipes away from it
+		let (sx, sy) = path[0];
+		tiles[sy][sx] = b'S';
+		tiles[sy][sx-1] = b'.';
+		tiles[sy-1][sx] = b'.';
+
+		let twice_area = path.iter().zip(path.iter().cycle().skip(1))
+			.map(|(&(x1, y1), &(x2, y2))| (x1*y2) as isize - (x2*y1) as isize)
+			.sum::<isize>()
+			.unsigned_abs();
+		let inside = twice_area/2 + 1 - path.len()/2;
+
+		let input = tiles.into_iter()
+			.map(|row| String::from_utf8(row).unwrap() + "\n")
+			.collect();
+		Generated { input, part1: Some(path.len()/2), part2: Some(inside) }
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day10, 0..10, &Params { width: 30, height: 20, ..Params::default() });
+}
@@ -1,5 +1,3 @@
-#![feature(ascii_char)]
-
 #![allow(unused_imports)]
 use std::collections::hash_map::Entry;
 use std::str::FromStr;
@@ -8,8 +6,16 @@ use arrayvec::ArrayVec;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Animation, Explain, Located, TryParse, TrySolve, Visualize};
+use aocinput::visual::Tone;
+use grid::{Bounds, Coord, Direction, Grid, GridErrorKind};
+#[cfg(test)] #[allow(unused_imports)]
+use aoch::{DayPart, run_test, daystr};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 use std::io::Write;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
@@ -17,12 +23,16 @@ use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 // BTreeMap does not make it significantly better
 type HashMap<K, V> = std::collections::HashMap<K, V>;
 
-type UCOORD = usize;
-type SCOORD = isize;
-
 #[derive(Debug,Clone,Copy)]
 pub struct Day10;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error(transparent)]
+	Grid(#[from] GridErrorKind),
+}
+pub type ParseError = Located<ParseErrorKind>;
+
 #[derive(Debug,Clone)]
 struct LineSeeker<'s> {
 	raw: &'s str,
@@ -56,7 +66,7 @@ impl<'s> LineSeeker<'s> {
 		let raw_previous = &self.raw[..self.byte_idx];
 		if raw_previous.is_empty() { return None; }
 		let line = raw_previous.lines().next_back()?;
-		log::debug!("{:?}.back().line = {:?} (raw_previous = {:?})", self, line, raw_previous);
+		tracing::debug!("{:?}.back().line = {:?} (raw_previous = {:?})", self, line, raw_previous);
 		let line_idx = self.line_idx;
 		self.line_idx -= 1;
 		self.byte_idx -= line.as_bytes().len(); // newline handling?
@@ -87,141 +97,162 @@ enum LineSide {
 	Right
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Direction {
-	North,
-	East,
-	South,
-	West,
+/// Returns a tuple of connected directions.
+fn symbol_connections(c: char) -> Option<[Direction; 2]> {
+	match c {
+		'|' => Some([Direction::North, Direction::South]),
+		'-' => Some([Direction::East, Direction::West]),
+		'L' => Some([Direction::North, Direction::East]),
+		'J' => Some([Direction::North, Direction::West]),
+		'7' => Some([Direction::South, Direction::West]),
+		'F' => Some([Direction::South, Direction::East]),
+		_ => None,
+	}
 }
 
-impl Direction {
-	const ALL: [Direction; 4] = [
-		Direction::North,
-		Direction::East,
-		Direction::South,
-		Direction::West,
-	];
+#[derive(Debug,Clone)]
+pub struct Maze<'s>(Grid<'s>);
 
-	#[inline(always)]
-	const fn offset(&self) -> (SCOORD, SCOORD) {
-		use Direction::*;
-		match self {
-			North => (0, -1),
-			East => (1, 0),
-			South => (0, 1),
-			West => (-1, 0),
-		}
+impl<'s> Maze<'s> {
+	fn get(&self, x: usize, y: usize) -> Option<char> {
+		self.0.get((x, y))
 	}
 
-	#[inline(always)]
-	fn forward(&self) -> Direction {
-		*self
-	}
-	#[inline(always)]
-	fn behind(&self) -> Direction {
-		use Direction::*;
-		match self {
-			North => South,
-			East => West,
-			South => North,
-			West => East,
-		}
-	}
-	#[inline(always)]
-	fn left(&self) -> Direction {
-		use Direction::*;
-		match self {
-			North => West,
-			East => North,
-			South => East,
-			West => South,
-		}
+	fn get_around(&self, x: usize, y: usize, dir: Direction) -> Option<(Coord, char)> {
+		let (ox, oy) = self.0.step((x, y), dir)?;
+		self.get(ox, oy).map(|c| ((ox, oy), c))
 	}
-	#[inline(always)]
-	fn right(&self) -> Direction {
-		use Direction::*;
-		match self {
-			North => East,
-			East => South,
-			South => West,
-			West => North,
+
+	/// Labels each tile within the path's bounds that isn't on the path with the group of tiles it's connected
+	/// to, calling `labelled` whenever a tile's label is set, including when two groups are found to be one
+	fn label_groups(&self, path: &HashMap<Coord, (Direction, char)>, mut labelled: impl FnMut(Coord, u32)) -> HashMap<Coord, u32> {
+		let Bounds { min: (minx, miny), max: (maxx, maxy) } = Bounds::enclosing(path.keys().copied()).unwrap();
+
+		let mut next_group_id: u32 = 0;
+		let mut others: HashMap<Coord, u32> = HashMap::with_capacity(((maxy-miny)*(maxx-minx)) as usize);
+
+		// walk each coordinate, categorizing each non-path tile into contiguous groups
+		for y in miny..=maxy {
+			for x in minx..=maxx {
+				if path.get(&(x, y)).is_some() { continue; }
+
+				let mut known_neighbors: ArrayVec<_, 4> = self.around(x, y)
+					.filter(|&((ax, ay), d, c)| !path.contains_key(&(ax,ay)))
+					.filter_map(|((ax,ay), d, c)| others.get(&(ax,ay)).map(|gid| {
+						((ax,ay),d,c,*gid)
+					}))
+					.collect();
+
+				let same_gid = known_neighbors.iter()
+					.map(|(_, _, _, gid)| *gid)
+					.all_equal();
+
+				if known_neighbors.is_empty() {
+					others.insert((x,y), next_group_id);
+					labelled((x,y), next_group_id);
+					next_group_id += 1;
+				} else if same_gid {
+					others.insert((x,y), known_neighbors[0].3);
+					labelled((x,y), known_neighbors[0].3);
+				} else {
+					// multiple neighbors with differing group IDs
+					// make smaller group ID, consume the larger group IDs
+					let smallest_group_id = known_neighbors.iter()
+						.map(|(_,_,_,gid)| *gid)
+						.min().unwrap();
+					for ((ax,ay),d,c,lgid) in known_neighbors.iter_mut() {
+						others.iter_mut()
+							.filter(|(_,oid)| *oid == lgid)
+							.for_each(|(coord,oid)| {
+								*oid = smallest_group_id;
+								labelled(*coord, smallest_group_id);
+							});
+						*lgid = smallest_group_id;
+					}
+
+					others.insert((x,y), smallest_group_id);
+					labelled((x,y), smallest_group_id);
+				}
+			}
 		}
+
+		others
 	}
 
-	/// Returns a tuple of connected directions.
-	fn symbol_connections(c: char) -> Option<[Direction; 2]> {
-		match c {
-			'|' => Some([Direction::North, Direction::South]),
-			'-' => Some([Direction::East, Direction::West]),
-			'L' => Some([Direction::North, Direction::East]),
-			'J' => Some([Direction::North, Direction::West]),
-			'7' => Some([Direction::South, Direction::West]),
-			'F' => Some([Direction::South, Direction::East]),
-			_ => None,
+	/// Walks the path, finding which side of it each group that touches it is on
+	fn group_sides(&self, path_ordered: &[(Coord, Direction, char)], path: &HashMap<Coord, (Direction, char)>, others: &HashMap<Coord, u32>) -> HashMap<u32, LineSide> {
+		let mut group_affinities = HashMap::new();
+
+		macro_rules! check_lineside {
+			($sx:expr, $sy:expr, $traveled_dir:expr, $side:expr) => {
+				if let Some(((ox, oy), _c)) = self.get_around($sx, $sy, $traveled_dir) {
+					if let Some(gid) = others.get(&(ox, oy)) {
+						assert!(!path.contains_key(&(ox, oy)), "tried to assign line affinity to pathful tile");
+						let og_side = group_affinities.entry(*gid)
+							.or_insert($side);
+						if *og_side != $side {
+							panic!("line group moved sides!");
+						}
+					}
+				}
+			};
+			($sx:expr, $sy:expr, $traveled_dir:expr, $side:expr, { $($direction:ident),+ $(,)? }) => {{
+				$(
+					check_lineside!($sx, $sy, $traveled_dir.$direction(), $side);
+				)+
+			}};
 		}
-	}
-}
 
-impl std::ops::Add<Direction> for (SCOORD, SCOORD) {
-    type Output = (SCOORD, SCOORD);
+		// walk the path again, categorizing each group
+		let mut path_ordered_iter = path_ordered.iter();
+		let mut dir = path_ordered_iter.next().unwrap();
+		// walk through, tracking left/right of the path
+		for &((sx, sy), traveled_dir, s) in path_ordered_iter {
+			match (s, traveled_dir) {
 
-    fn add(self, rhs: Direction) -> Self::Output {
-        let (ox, oy) = rhs.offset();
-		(self.0 + ox, self.1 + oy)
-    }
-}
+				('J', Direction::North) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
+				('L', Direction::North) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
 
-impl std::ops::Add<Direction> for (UCOORD, UCOORD) {
-    type Output = Option<(UCOORD, UCOORD)>;
-
-    fn add(self, rhs: Direction) -> Self::Output {
-        let (x, y) = (self.0 as SCOORD, self.1 as SCOORD);
-		let (ox, oy) = rhs.offset();
-		Some((
-			(x + ox).try_into().ok()?,
-			(y + oy).try_into().ok()?,
-		))
-    }
-}
+				('L', Direction::East) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
+				('F', Direction::East) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
 
-#[derive(Debug,Clone)]
-pub struct Maze<'s>(&'s [std::ascii::Char], usize);
+				('F', Direction::South) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
+				('7', Direction::South) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
 
-impl<'s> Maze<'s> {
-	fn get(&self, x: UCOORD, y: UCOORD) -> Option<char> {
-		self.0.get((y as usize)*(self.1) + (x as usize)).map(|c| c.to_char())
-	}
+				('7', Direction::West) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
+				('J', Direction::West) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
 
-	fn get_around(&self, x: UCOORD, y: UCOORD, dir: Direction) -> Option<((UCOORD, UCOORD), char)> {
-		let (ox, oy) = ((x, y) + dir)?;
-		self.get(ox, oy).map(|c| ((ox, oy), c))
+				('|' | '-', _) => {
+					check_lineside!(sx, sy, traveled_dir.left(), LineSide::Left);
+					check_lineside!(sx, sy, traveled_dir.right(), LineSide::Right);
+				},
+
+				(_, _) => {
+					// ignore
+				}
+			}
+		}
+
+		group_affinities
 	}
 
 	/// Returns a walk around a tile in NESW order, yielding items of ((x, y), walked direction, dest symbol)
-	fn around(&self, x: UCOORD, y: UCOORD) -> impl Iterator<Item = ((UCOORD, UCOORD), Direction, char)> + '_ {
-		Direction::ALL.into_iter()
-			.flat_map(move |dir| {
-				// apply offset w/ lower bounds (type) checking
-				let (x, y) = ((x, y) + dir)?;
-
-				// query character w/ upper bounds (index) checking
-				self.get(x, y).map(|c| ((x, y), dir, c))
-			})
+	fn around(&self, x: usize, y: usize) -> impl Iterator<Item = (Coord, Direction, char)> + '_ {
+		self.0.neighbours4((x, y))
 	}
 
 	/// Returns an iterator of valid neighboring tiles that connect back to the provided coordinates
 	/// Yields ((neighbor_x, neighbor_y), in_direction, leads_towards_direction)
-	fn tile_connections(&self, x: UCOORD, y: UCOORD) -> impl Iterator<Item = ((UCOORD, UCOORD), Direction, Direction)> + '_ {
+	fn tile_connections(&self, x: usize, y: usize) -> impl Iterator<Item = (Coord, Direction, Direction)> + '_ {
 		self.around(x, y)
 			.filter(|(_, _, nc)| *nc != '.')
 			.flat_map(move |((nx, ny), ndir, nc)| {
 				// what direction does this tile need to connect with start, relative to itself?
 				// let towards_self = ndir.opposite();
-				log::debug!("Maze::tile_connections({}, {}) visiting neighbor ({}, {}, char: {:?}, dir: {:?})", x, y, nx, ny, nc, ndir);
+				tracing::debug!("Maze::tile_connections({}, {}) visiting neighbor ({}, {}, char: {:?}, dir: {:?})", x, y, nx, ny, nc, ndir);
 
 				let to_center = ndir.behind();
-				Direction::symbol_connections(nc).into_iter()
+				symbol_connections(nc).into_iter()
 					// Goes back towards origin
 					.filter(|arr| arr.contains(&to_center))
 					.flatten()
@@ -231,38 +262,36 @@ impl<'s> Maze<'s> {
 					.next()
 			})
 	}
-	fn find_start(&self) -> Option<(UCOORD, UCOORD)> {
-		self.0.iter()
-			.position(|c| c.to_char() == 'S')
-			.map(|i| ((i % self.1) as UCOORD, (i/self.1) as UCOORD))
+	fn find_start(&self) -> Option<Coord> {
+		self.0.position(|c| c == 'S')
 	}
-	fn walk_path(&self) -> impl Iterator<Item = ((UCOORD, UCOORD), Direction, char)> + '_ {
+	fn walk_path(&self) -> impl Iterator<Item = (Coord, Direction, char)> + '_ {
 		let start = self.find_start().expect("cannot walk a path with no start");
 
 		let (dir1, dir2) = self.tile_connections(start.0, start.1).collect_tuple()
 			.expect("Expected two connections to starting tile");
 
-		log::debug!("Found tiles connecting to start at {:?}, in {:?} and {:?} directions", start, dir1, dir2);
+		tracing::debug!("Found tiles connecting to start at {:?}, in {:?} and {:?} directions", start, dir1, dir2);
 
 		#[derive(Debug)]
 		struct PathWalker<'m> {
 			maze: &'m Maze<'m>,
-			start: (UCOORD, UCOORD),
-			position: (UCOORD, UCOORD),
+			start: Coord,
+			position: Coord,
 			next_direction: Option<Direction>,
 		}
 		impl<'m> Iterator for PathWalker<'m> {
-			type Item = ((UCOORD, UCOORD), Direction, char);
+			type Item = (Coord, Direction, char);
 			fn next(&mut self) -> Option<Self::Item> {
 				// eprintln!("PathWalker::next({:?})", self);
 				let last_pos = self.position;
 				let move_dir = self.next_direction?;
-				let Some((next_x, next_y)) = self.position + move_dir else {
-					panic!("next_direction targeted tile beyond lower limits of map ({:?})", self);
+				let Some((next_x, next_y)) = self.maze.0.step(self.position, move_dir) else {
+					panic!("next_direction targeted tile beyond the limits of map ({:?})", self);
 				};
 
 				if (next_x, next_y) == self.start {
-					log::trace!("reached end of walk {:?}", self);
+					tracing::trace!("reached end of walk {:?}", self);
 					self.next_direction = None;
 					// let rtn = (last_pos, self.maze.get(last_pos.0, last_pos.1).unwrap());
 					// eprintln!("\treturnning {:?}", rtn);
@@ -270,7 +299,7 @@ impl<'s> Maze<'s> {
 				} else {
 					// Get the next symbols other direction
 					let (next_dir,) = self.maze.get(next_x, next_y)
-						.map(Direction::symbol_connections)
+						.map(symbol_connections)
 						.iter().flatten().flatten().copied()
 						.filter(|d| d.behind() != move_dir)
 						.collect_tuple()
@@ -278,7 +307,7 @@ impl<'s> Maze<'s> {
 							panic!("found zero or over one targets for next direction from {:?} ({:?})", (next_x, next_y), self);
 						});
 
-					log::trace!("\tnext state: {:?} going {:?}", (next_x, next_y), next_dir);
+					tracing::trace!("\tnext state: {:?} going {:?}", (next_x, next_y), next_dir);
 					self.position = (next_x, next_y);
 					self.next_direction = Some(next_dir);
 				}
@@ -298,6 +327,15 @@ impl<'s> Maze<'s> {
 	}
 }
 
+/// Which side of the path is inside the loop. The inside is on the right of the path if it winds clockwise,
+/// which the shoelace sum is positive for when y runs downwards.
+fn inside_side(path_ordered: &[(Coord, Direction, char)]) -> LineSide {
+	let winding: isize = path_ordered.iter().zip(path_ordered.iter().cycle().skip(1))
+		.map(|(&((x1, y1), _, _), &((x2, y2), _, _))| (x1*y2) as isize - (x2*y1) as isize)
+		.sum();
+	if winding > 0 { LineSide::Right } else { LineSide::Left }
+}
+
 impl AoCDay for Day10 {
 	type Data<'i> = Maze<'i>;
 	type Answer = usize;
@@ -305,22 +343,7 @@ impl AoCDay for Day10 {
 	fn day(&self) -> u8 { 10 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		let raw = input.trim();
-		// eprintln!("Debug:\n{:?}", raw);
-		// eprintln!("Display:\n{}", raw);
-		let ascii = raw.as_ascii().expect("input not ascii");
-		let width_eqs = raw.lines()
-			.map(|l| l.len())
-			// .enumerate()
-			// .inspect(|(i, len)| eprintln!("Line {}: {}", i, len))
-			// .map(|(_i, len)| len)
-			.all_equal();
-		if ! width_eqs {
-			panic!("not all input lines are of equal length");
-		}
-		let width = raw.lines().map(|l| l.len()+1).next();
-
-		Maze(ascii, width.expect("no input lines"))
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
 		use Direction::{North, East, South, West};
@@ -332,7 +355,7 @@ impl AoCDay for Day10 {
 		let path_ordered = _data.walk_path()
 			// .inspect(|t| eprintln!("path node: {:?}", t))
 			.collect_vec();
-		let path: HashMap<(UCOORD, UCOORD), (Direction, char)> = path_ordered.iter()
+		let path: HashMap<Coord, (Direction, char)> = path_ordered.iter()
 			.map(|&(xy, d, c)| (xy, (d, c)))
 			.collect();
 
@@ -341,60 +364,7 @@ impl AoCDay for Day10 {
 		// 	eprintln!("\t{i}: {p:?}");
 		// }
 
-		let bounds_x_raw = path_ordered.iter().minmax_by_key(|p| p.0.0).into_option().unwrap();
-		let bounds_y_raw = path_ordered.iter().minmax_by_key(|p| p.0.1).into_option().unwrap();
-		let (minx, maxx) = (bounds_x_raw.0.0.0, bounds_x_raw.1.0.0);
-		let (miny, maxy) = (bounds_y_raw.0.0.1, bounds_y_raw.1.0.1);
-		// path.sort();
-
-		let mut next_group_id: u32 = 0;
-		let mut others: HashMap<(UCOORD, UCOORD), u32> = HashMap::with_capacity(((maxy-miny)*(maxx-minx)) as usize);
-
-		// walk each coordinate, categorizing each non-path tile into contiguous groups
-		for y in miny..=maxy {
-			for x in minx..=maxx {
-				if path.get(&(x, y)).is_some() { continue; }
-
-				let mut known_neighbors: ArrayVec<_, 4> = _data.around(x, y)
-					.filter(|&((ax, ay), d, c)| !path.contains_key(&(ax,ay)))
-					.filter_map(|((ax,ay), d, c)| others.get(&(ax,ay)).map(|gid| {
-						((ax,ay),d,c,*gid)
-					}))
-					.collect();
-
-				let same_gid = known_neighbors.iter()
-					.map(|(_, _, _, gid)| *gid)
-					.all_equal();
-
-				if known_neighbors.is_empty() {
-					others.insert((x,y), next_group_id);
-					next_group_id += 1;
-				} else if same_gid {
-					others.insert((x,y), known_neighbors[0].3);
-				} else {
-					// multiple neighbors with differing group IDs
-					// make smaller group ID, consume the larger group IDs
-					let smallest_group_id = known_neighbors.iter()
-						.map(|(_,_,_,gid)| *gid)
-						.min().unwrap();
-					for ((ax,ay),d,c,lgid) in known_neighbors.iter_mut() {
-						others.iter_mut()
-							.filter(|(_,oid)| *oid == lgid)
-							.for_each(|(_,oid)| {
-								// deallocate greater group ID for reuse
-								if next_group_id == *oid+1 {
-									next_group_id -= 1;
-								}
-
-								*oid = smallest_group_id;
-							});
-						*lgid = smallest_group_id;
-					}
-
-					others.insert((x,y), smallest_group_id);
-				}
-			}
-		}
+		let others = _data.label_groups(&path, |_, _| {});
 
 		// put each group's coordinates into a Vec
 		let mut groups = HashMap::new();
@@ -404,72 +374,24 @@ impl AoCDay for Day10 {
 				.push(*coord);
 		}
 
-		let mut group_affinities = HashMap::new();
-
-		macro_rules! check_lineside {
-			($sx:expr, $sy:expr, $traveled_dir:expr, $side:expr) => {
-				if let Some(((ox, oy), _c)) = _data.get_around($sx, $sy, $traveled_dir) {
-					if let Some(gid) = others.get(&(ox, oy)) {
-						assert!(!path.contains_key(&(ox, oy)), "tried to assign line affinity to pathful tile");
-						let og_side = group_affinities.entry(*gid)
-							.or_insert($side);
-						if *og_side != $side {
-							panic!("line group moved sides!");
-						}
-					}
-				}
-			};
-			($sx:expr, $sy:expr, $traveled_dir:expr, $side:expr, { $($direction:ident),+ $(,)? }) => {{
-				$(
-					check_lineside!($sx, $sy, $traveled_dir.$direction(), $side);
-				)+
-			}};
-		}
-
-		// walk the path again, categorizing each group
-		let mut path_ordered_iter = path_ordered.iter();
-		let mut dir = path_ordered_iter.next().unwrap();
-		// walk through, tracking left/right of the path
-		for &((sx, sy), traveled_dir, s) in path_ordered_iter {
-			match (s, traveled_dir) {
-
-				('J', Direction::North) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
-				('L', Direction::North) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
-
-				('L', Direction::East) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
-				('F', Direction::East) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
-
-				('F', Direction::South) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
-				('7', Direction::South) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
-
-				('7', Direction::West) => check_lineside!(sx, sy, traveled_dir, LineSide::Right, { right, behind, }),
-				('J', Direction::West) => check_lineside!(sx, sy, traveled_dir, LineSide::Left, { left, behind, }),
-
-				('|' | '-', _) => {
-					check_lineside!(sx, sy, traveled_dir.left(), LineSide::Left);
-					check_lineside!(sx, sy, traveled_dir.right(), LineSide::Right);
-				},
-
-				(_, _) => {
-					// ignore
-				}
-			}
-		}
+		let group_affinities = _data.group_sides(&path_ordered, &path, &others);
 
 		let mut affinities = HashMap::<LineSide, usize>::new();
 		for (gid, side) in group_affinities.iter() {
-			log::debug!("Group {}: {:?}", gid, side);
+			tracing::debug!("Group {}: {:?}", gid, side);
 			*affinities.entry(*side)
 				.or_default() += groups.get(&gid).unwrap().len();
 		}
 
-		log::debug!("groups affinities: {:?}", group_affinities);
-		log::debug!("affinities: {:?}", affinities);
+		tracing::debug!(groups = groups.len(), sided = group_affinities.len(), "groups affinities: {:?}", group_affinities);
+		tracing::debug!("affinities: {:?}", affinities);
 
 		// walk each tile, to print it to the console
 
-		if log::log_enabled!(log::Level::Info) {
-			let mut stdout = StandardStream::stdout(ColorChoice::Always);
+		if tracing::enabled!(tracing::Level::DEBUG) {
+			let Bounds { min: (minx, miny), max: (maxx, maxy) } = Bounds::enclosing(path.keys().copied()).unwrap();
+			// drawn to stderr alongside the rest of the log, keeping stdout to the answers
+			let mut out = StandardStream::stderr(ColorChoice::Always);
 			const UNICODE: bool = false;
 			for y in miny..=maxy {
 				for x in minx..=maxx {
@@ -524,22 +446,102 @@ impl AoCDay for Day10 {
 						_ => panic!("tile not categorized as path nor group"),
 					};
 
-					stdout.set_color(&spec).unwrap();
-					write!(&mut stdout, "{}", c).unwrap();
-					stdout.set_color(ColorSpec::new().set_reset(true)).unwrap();
+					out.set_color(&spec).unwrap();
+					write!(&mut out, "{}", c).unwrap();
+					out.set_color(ColorSpec::new().set_reset(true)).unwrap();
 					// print!("{}", c);
 				}
-				writeln!(&mut stdout, "").unwrap();
+				writeln!(&mut out, "").unwrap();
+			}
+		}
+
+		affinities.get(&inside_side(&path_ordered)).copied().unwrap_or(0)
+	}
+}
+
+impl TrySolve for Day10 {}
+
+impl Explain for Day10 {}
+
+/// Adds a frame for each step along the path, which stays marked behind the walk.
+fn walk_frames(animation: &mut Animation, path_ordered: &[(Coord, Direction, char)]) {
+	for (i, &(at, dir, c)) in path_ordered.iter().enumerate() {
+		animation.push(format!("step {}: {} at {:?}, heading {:?}", i, c, at, dir))
+			.tone(at, Tone::Path)
+			.focus(at);
+	}
+}
+
+impl Visualize for Day10 {
+	fn visualize_part1(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		let path_ordered = _data.walk_path().collect_vec();
+		let mut animation = Animation::new("Day 10: walking the loop", _data.0.rows());
+		walk_frames(&mut animation, &path_ordered);
+
+		let (furthest, _, _) = path_ordered[path_ordered.len() / 2];
+		animation.push(format!("the loop is {} tiles long, so the furthest tile is {} steps away", path_ordered.len(), path_ordered.len().div_ceil(2)))
+			.set(furthest, '*', Tone::Highlight)
+			.focus(furthest);
+		Some(animation)
+	}
+
+	fn visualize_part2(&self, _data: &mut Self::Data<'_>) -> Option<Animation> {
+		let path_ordered = _data.walk_path().collect_vec();
+		let path: HashMap<Coord, (Direction, char)> = path_ordered.iter()
+			.map(|&(xy, d, c)| (xy, (d, c)))
+			.collect();
+		let mut animation = Animation::new("Day 10: flooding the tiles inside the loop", _data.0.rows());
+		walk_frames(&mut animation, &path_ordered);
+
+		// a row's labels start with its first tile, and merges only ever relabel tiles above or before it
+		let mut rows: Vec<Vec<(Coord, u32)>> = Vec::new();
+		let others = _data.label_groups(&path, |at, gid| match rows.last_mut() {
+			Some(row) if at.1 <= row[0].0.1 => row.push((at, gid)),
+			_ => rows.push(vec![(at, gid)]),
+		});
+		for row in &rows {
+			let y = row[0].0.1;
+			let merged = row.iter().any(|((_, ly), _)| *ly < y);
+			let frame = animation.push(format!("grouping row {}{}", y, if merged { ", merging groups that meet" } else { "" }));
+			for &(at, gid) in row {
+				frame.tone(at, Tone::Group(gid));
 			}
 		}
 
-		*affinities.values()
-			.min().unwrap()
+		let sides = _data.group_sides(&path_ordered, &path, &others);
+		let inside = inside_side(&path_ordered);
+		let count = others.values().filter(|gid| sides.get(gid) == Some(&inside)).count();
+		let winding = match inside {
+			LineSide::Right => "clockwise",
+			LineSide::Left => "anticlockwise",
+		};
+		let frame = animation.push(format!("the loop winds {}, so the {} tiles of the groups on its {:?} are inside", winding, count, inside));
+		for (&at, gid) in others.iter().sorted() {
+			match sides.get(gid) {
+				Some(side) if *side == inside => frame.tone(at, Tone::Inside),
+				Some(_) => frame.tone(at, Tone::Outside),
+				None => continue,
+			};
+		}
+		Some(animation)
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT_P1_SIMPLE: &'static str = "
+impl TryParse for Day10 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		let raw = input.trim();
+		// eprintln!("Debug:\n{:?}", raw);
+		// eprintln!("Display:\n{}", raw);
+		let grid = Grid::new(raw)
+			.map_err(|e| ParseError::new(input, e.text, e.kind.into()))?;
+
+		Ok(Maze(grid))
+	}
+}
+
+pub const TEST_INPUT_P1_SIMPLE: &'static str = "
 .....
 .S-7.
 .|.|.
@@ -548,8 +550,7 @@ const TEST_INPUT_P1_SIMPLE: &'static str = "
 ";
 
 
-#[cfg(test)]
-const TEST_INPUT_P1_COMPLEX: &'static str = "
+pub const TEST_INPUT_P1_COMPLEX: &'static str = "
 ..F7.
 .FJ|.
 SJ.L7
@@ -557,8 +558,7 @@ SJ.L7
 LJ...
 ";
 
-#[cfg(test)]
-const TEST_INPUT_P2_SIMPLE: &'static str = "
+pub const TEST_INPUT_P2_SIMPLE: &'static str = "
 ...........
 .S-------7.
 .|F-----7|.
@@ -570,8 +570,7 @@ const TEST_INPUT_P2_SIMPLE: &'static str = "
 ...........
 ";
 
-#[cfg(test)]
-const TEST_INPUT_P2_LARGE: &'static str = "
+pub const TEST_INPUT_P2_LARGE: &'static str = "
 .F----7F7F7F7F-7....
 .|F--7||||||||FJ....
 .||.FJ||||||||L7....
@@ -584,8 +583,7 @@ L--J.L7...LJS7F-7L7.
 ....L---J.LJ.LJLJ...
 ";
 
-#[cfg(test)]
-const TEST_INPUT_P2_WITHTRASH: &'static str = "
+pub const TEST_INPUT_P2_WITHTRASH: &'static str = "
 FF7FSF7F7F7F7F7F---7
 L|LJ||||||||||||F--J
 FL-7LJLJ||||||LJL-77
@@ -598,6 +596,15 @@ L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L
 ";
 
+// most of the loop's bounds are inside it, with only the notch at the bottom outside
+pub const TEST_INPUT_P2_MOSTLY_INSIDE: &'static str = "
+S------7
+|......|
+|......|
+|..F-7.|
+L--J.L-J
+";
+
 /*
 #[test]
 fn fuel_calc() {
@@ -608,12 +615,25 @@ fn fuel_calc() {
 }
 */
 
+#[test]
+fn parse_errors() {
+	let cases = [
+		(".....\n.S-7.\n.|.|\n.L-J.", Some((3, 1, ".|.|".into(), GridErrorKind::Ragged { found: 4, expected: 5 }.into()))),
+		(".....\n.S─7.", Some((2, 3, "─".into(), GridErrorKind::NotAscii.into()))),
+		("\n\n", Some((1, 1, "".into(), GridErrorKind::Empty.into()))),
+		(TEST_INPUT_P1_SIMPLE, None),
+	];
+	run_test(|input| {
+		Day10.try_parse(input).err()
+			.map(|e| (e.span.line, e.span.col, e.span.text, e.kind))
+	}, &cases);
+}
+
 #[test]
 fn part1() {
 	let cases = [
 		(TEST_INPUT_P1_SIMPLE, 4),
 		(TEST_INPUT_P1_COMPLEX, 8),
-		(daystr!("10"), 6864),
 	];
 	test_runner::<Day10, _>(Day10, DayPart::Part1, &cases);
 }
@@ -623,7 +643,30 @@ fn part2() {
 		(TEST_INPUT_P2_SIMPLE, 4),
 		(TEST_INPUT_P2_LARGE, 8),
 		(TEST_INPUT_P2_WITHTRASH, 10),
-		(daystr!("10"), 349),
+		(TEST_INPUT_P2_MOSTLY_INSIDE, 15),
 	];
 	test_runner::<Day10, _>(Day10, DayPart::Part2, &cases);
 }
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day10, daystr!("10"));
+}
+
+#[test]
+fn visualized() {
+	let mut data = Day10.parse(TEST_INPUT_P1_COMPLEX);
+	let animation = Day10.visualize_part1(&mut data).unwrap();
+	// a frame for each of the 16 tiles of the loop, and one for the answer
+	assert_eq!(animation.frames().len(), 17);
+	let last = animation.canvas(16);
+	assert_eq!(last.get((4, 2)).unwrap(), aocinput::visual::Cell { glyph: '*', tone: Tone::Highlight, focused: true });
+
+	for (input, inside) in [(TEST_INPUT_P2_SIMPLE, 4), (TEST_INPUT_P2_LARGE, 8), (TEST_INPUT_P2_WITHTRASH, 10)] {
+		let mut data = Day10.parse(input);
+		let animation = Day10.visualize_part2(&mut data).unwrap();
+		let last = animation.canvas(usize::MAX);
+		let tiles = (0..last.height()).flat_map(|y| (0..last.width()).map(move |x| (x, y)));
+		assert_eq!(tiles.filter(|&at| last.get(at).unwrap().tone == Tone::Inside).count(), inside);
+	}
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day10::Day10;
 
 fn main() {
-	run_day::<Day10>(Day10, daystr!("10"), None);
+	aocinput::source::for_each_input(daystr!("10"), |input| {
+		run_day::<Day10>(Day10, input, None);
+	});
 }
 
@@ -0,0 +1,122 @@
+use aocinput::arith::{answer, Answer};
+use aocinput::gen::{rng, Generate, Generated, Reference, SliceRandom};
+use crate::Day11;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub width: usize,
+	pub height: usize,
+	pub galaxies: usize,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { width: 140, height: 140, galaxies: 440 }
+	}
+}
+
+/// The sum of distances between every pair of positions along one axis, once every empty line is
+/// widened to `scale` lines.
+fn axis_distances(mut positions: Vec<usize>, scale: usize) -> usize {
+	positions.sort();
+	// move each position over by the empty lines before it
+	let mut empty = 0;
+	let expanded: Vec<usize> = positions.iter().enumerate()
+		.map(|(i, &p)| {
+			if i > 0 {
+				empty += p.saturating_sub(positions[i-1] + 1);
+			}
+			p + empty*(scale-1)
+		})
+		.collect();
+	// each position is the far end of a pair with every position before it
+	let mut before = 0;
+	expanded.iter().enumerate()
+		.map(|(i, &p)| {
+			let sum = p*i - before;
+			before += p;
+			sum
+		})
+		.sum()
+}
+
+impl Generate for Day11 {
+	type Params = Params;
+
+	fn generate(&self, seed: u64, params: &Params) -> Generated<Answer> {
+		let mut rng = rng(seed);
+		let (width, height) = (params.width.max(1), params.height.max(1));
+		let cells: Vec<usize> = (0..width*height).collect();
+		let galaxies: Vec<(usize, usize)> = cells.choose_multiple(&mut rng, params.galaxies.clamp(2, width*height))
+			.map(|i| (i % width, i / width))
+			.collect();
+
+		let mut tiles = vec![vec![b'.'; width]; height];
+		for &(x, y) in &galaxies {
+			tiles[y][x] = b'#';
+		}
+		let input = tiles.into_iter()
+			.map(|row| String::from_utf8(row).unwrap() + "\n")
+			.collect();
+
+		let distances = |scale| {
+			axis_distances(galaxies.iter().map(|g| g.0).collect(), scale)
+				+ axis_distances(galaxies.iter().map(|g| g.1).collect(), scale)
+		};
+		Generated { input, part1: Some(answer(distances(2))), part2: Some(answer(distances(1_000_000))) }
+	}
+}
+
+/// Copies every row that has no galaxies
+fn doubled_rows(rows: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+	rows.into_iter()
+		.flat_map(|row| {
+			let copies = if row.contains(&b'#') { 1 } else { 2 };
+			std::iter::repeat_n(row, copies)
+		})
+		.collect()
+}
+
+fn transposed(rows: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+	(0..rows[0].len()).map(|x| rows.iter().map(|row| row[x]).collect()).collect()
+}
+
+impl Reference for Day11 {
+	/// Literally doubles the empty rows and columns, then measures between every pair of galaxies.
+	fn reference_part1(&self, input: &str) -> Option<Answer> {
+		let rows = input.trim().lines().map(|l| l.as_bytes().to_vec()).collect();
+		let expanded = transposed(doubled_rows(transposed(doubled_rows(rows))));
+		let galaxies: Vec<(usize, usize)> = expanded.iter().enumerate()
+			.flat_map(|(y, row)| row.iter().enumerate().filter(|(_, &c)| c == b'#').map(move |(x, _)| (x, y)))
+			.collect();
+		let mut sum = 0;
+		for (i, a) in galaxies.iter().enumerate() {
+			for b in &galaxies[i+1..] {
+				sum += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+			}
+		}
+		Some(answer(sum))
+	}
+}
+
+#[test]
+fn generated() {
+	aocinput::gen::verify(Day11, 0..10, &Params { width: 40, height: 40, galaxies: 60 });
+}
+
+#[test]
+fn reference() {
+	let compared = aocinput::gen::cross_check(Day11, 0..50, &Params { width: 12, height: 10, galaxies: 8 }).unwrap_or_else(|d| panic!("{}", d));
+	assert_eq!(compared, 50);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel() {
+	use aoch::AoCDay;
+	for seed in 0..10 {
+		let mut coords = Day11.parse(&Day11.generate(seed, &Params::default()).input);
+		coords.expand(2).unwrap();
+		assert_eq!(coords.pair_dist_sum_serial(), coords.pair_dist_sum_parallel(), "seed {}", seed);
+	}
+}
@@ -4,112 +4,145 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use test_log::test;
 use aoch::AoCDay;
+use aocinput::{Explain, Located, Overflow, SolveError, TryParse, TrySolve, Visualize};
+use aocinput::arith::{self, answer, Answer};
+use grid::{Bounds, Grid, GridErrorKind};
 #[cfg(test)] #[allow(unused_imports)]
-use aoch::{DayPart, run_test, test_runner, daystr};
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
 
 type Num = usize;
 
 #[derive(Debug,Clone,Copy)]
 pub struct Day11;
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error(transparent)]
+	Grid(#[from] GridErrorKind),
+	#[error("expected only `.` and `#` tiles")]
+	UnexpectedTile,
+}
+pub type ParseError = Located<ParseErrorKind>;
+
+const SUMMING: &str = "summing the distances between galaxies";
+
+/// The manhattan distance between two galaxies
+fn distance((ax, ay): (Num, Num), (bx, by): (Num, Num)) -> Result<Answer, Overflow> {
+	arith::add(answer(ax.abs_diff(bx)), &answer(ay.abs_diff(by)), SUMMING)
+}
+
 #[derive(Clone)]
 pub struct Coordinates {
 	raw: Vec<(Num, Num)>,
 }
 
 impl Coordinates {
-	pub fn bounds_with_min(&self) -> ((Num, Num), (Num, Num)) {
-		let mut iter = self.raw.iter().copied();
-		let mut min = iter.next().expect("no elements in raw map");
-		let mut max = min;
-		for (x, y) in iter {
-			if x < min.0 { min.0 = x; }
-			if x > max.0 { max.0 = x; }
-			if y < min.1 { min.1 = y; }
-			if y > max.1 { max.1 = y; }
-		}
-		(min, max)
+	pub fn bounds(&self) -> Bounds {
+		Bounds::enclosing(self.raw.iter().copied()).expect("no elements in raw map")
 	}
-	pub fn bounds_max(&self) -> (Num, Num) {
-		self.raw.iter().copied()
-			.reduce(|(ax, ay), (ex, ey)| (ax.max(ex), ay.max(ey)))
-			.unwrap()
-	}
-	pub fn expand(&mut self, gap_scale: usize) {
+	pub fn expand(&mut self, gap_scale: usize) -> Result<(), Overflow> {
 		let gap_add = gap_scale - 1;
-		let ((nx, ny), (mut xx, mut xy)) = self.bounds_with_min();
-		eprintln!("expanding {} nodes with scale {} -- x", self.raw.len(), gap_add);
+		let grow = |n: Num| arith::add(n, &gap_add, "expanding empty space");
+		let Bounds { min: (nx, ny), max: (mut xx, mut xy) } = self.bounds();
+		tracing::debug!("expanding {} nodes with scale {} -- x", self.raw.len(), gap_add);
 
 		let mut x = nx+1;
 		while x <= xx {
 			let empty = ! self.raw.iter().any(|(ix, _)| *ix == x);
 			if empty {
 				// col is empty, expand it
-				self.raw.iter_mut()
-					.for_each(|(ix, _)| {
-						if *ix > x {
-							*ix += gap_add;
-						}
-					});
-				xx += gap_add;
+				for (ix, _) in self.raw.iter_mut() {
+					if *ix > x {
+						*ix = grow(*ix)?;
+					}
+				}
+				xx = grow(xx)?;
 			}
 
 			x += 1;
 			if empty {
-				x += gap_add;
+				x = grow(x)?;
 			}
 
 			// eprintln!("[{}] x = {} (nx, xx) = {:?} (was empty = {:?})", self.raw.len(), x, (nx, xx), empty);
 		}
 
-		eprintln!("expanding {} nodes with scale {} -- y", self.raw.len(), gap_add);
+		tracing::debug!("expanding {} nodes with scale {} -- y", self.raw.len(), gap_add);
 
 		let mut y = ny+1;
 		while y <= xy {
 			let empty = ! self.raw.iter().any(|(_, iy)| *iy == y);
 			if empty {
 				// col is empty, expand it
-				self.raw.iter_mut()
-					.for_each(|(_, iy)| {
-						if *iy > y {
-							*iy += gap_add;
-						}
-					});
-				xy += gap_add;
+				for (_, iy) in self.raw.iter_mut() {
+					if *iy > y {
+						*iy = grow(*iy)?;
+					}
+				}
+				xy = grow(xy)?;
 			}
 
 			y += 1;
 			if empty {
-				y += gap_add;
+				y = grow(y)?;
 			}
 
 			// eprintln!("[{}] y = {} (ny, xy) = {:?} (was empty = {:?})", self.raw.len(), y, (ny, xy), empty);
 		}
 
-		eprintln!("expanding {} nodes with scale {} -- done", self.raw.len(), gap_add);
+		tracing::debug!("expanding {} nodes with scale {} -- done", self.raw.len(), gap_add);
+		Ok(())
 	}
 
-	pub fn pair_dist_sum(&self) -> usize {
-		let mut sum = 0;
+	/// Sums the manhattan distance between every pair of galaxies.
+	pub fn pair_dist_sum(&self) -> Result<Answer, Overflow> {
 		let count = (self.raw.len()*(self.raw.len()-1))/2;
-		eprintln!("Pairs: {count:}");
+		tracing::debug!(galaxies = self.raw.len(), pairs = count, "Pairs: {count:}");
+		#[cfg(feature = "parallel")]
+		return self.pair_dist_sum_parallel();
+		#[cfg(not(feature = "parallel"))]
+		return self.pair_dist_sum_serial();
+	}
+
+	// only checked against the parallel path in tests when that is enabled
+	#[cfg_attr(feature = "parallel", allow(dead_code))]
+	fn pair_dist_sum_serial(&self) -> Result<Answer, Overflow> {
+		let mut sum = answer(0);
 		for i in 0..self.raw.len()-1 {
 			for j in i+1..self.raw.len() {
 				let (ax, ay) = self.raw[i];
 				let (bx, by) = self.raw[j];
 				// eprintln!("[{}, {}] = ({:?}, {:?})", i, j, (ax, ay), (bx, by));
-				let len = ax.abs_diff(bx) + ay.abs_diff(by);
+				let len = distance((ax, ay), (bx, by))?;
 				// eprintln!("[{}, {}] = ({:?}, {:?}) = {}", i, j, (ax, ay), (bx, by), len);
-				sum += len;
+				sum = arith::add(sum, &len, SUMMING)?;
 			}
 		}
-		sum
+		Ok(sum)
+	}
+
+	/// Sums the distances from each galaxy to the ones after it on separate threads.
+	#[cfg(feature = "parallel")]
+	fn pair_dist_sum_parallel(&self) -> Result<Answer, Overflow> {
+		use rayon::prelude::*;
+
+		(0..self.raw.len().saturating_sub(1)).into_par_iter()
+			.map(|i| {
+				self.raw[i+1..].iter()
+					.try_fold(answer(0), |sum, &b| arith::add(sum, &distance(self.raw[i], b)?, SUMMING))
+			})
+			.try_reduce(|| answer(0), |a, b| arith::add(a, &b, SUMMING))
 	}
 }
 
 impl fmt::Debug for Coordinates {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let (xx, xy) = self.bounds_max();
+		let (xx, xy) = self.bounds().max;
 		write!(f, "\n")?;
 		for y in 0..=xy {
 			for x in 0..=xx {
@@ -128,37 +161,58 @@ impl fmt::Debug for Coordinates {
 
 impl AoCDay for Day11 {
 	type Data<'i> = Coordinates;
-	type Answer = usize;
+	type Answer = Answer;
 
 	fn day(&self) -> u8 { 11 }
 
 	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
-		let coords = input.lines()
-			.filter_map(aoch::parsing::trimmed)
-			.enumerate()
-			.flat_map(|(y, l)| {
-				l.chars().enumerate()
-					.filter(|(_, c)| *c == '#')
-					.map(move |(x, _)| (x, y))
-			})
-			.collect_vec();
-		eprintln!("Coordinates: {} total", coords.len());
-		Coordinates { raw: coords }
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
-		let mut p1 = _data.clone();
-		p1.expand(2);
-		p1.pair_dist_sum()
+		self.try_part1(_data).unwrap_or_else(|e| panic!("{}", e))
 	}
 	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		self.try_part2(_data).unwrap_or_else(|e| panic!("{}", e))
+	}
+}
+
+impl TrySolve for Day11 {
+	fn try_part1(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
 		let mut p1 = _data.clone();
-		p1.expand(1000000);
-		p1.pair_dist_sum()
+		p1.expand(2)?;
+		Ok(p1.pair_dist_sum()?)
+	}
+	fn try_part2(&self, _data: &mut Self::Data<'_>) -> Result<Self::Answer, SolveError> {
+		let mut p1 = _data.clone();
+		p1.expand(1000000)?;
+		Ok(p1.pair_dist_sum()?)
 	}
 }
 
-#[cfg(test)]
-const TEST_INPUT: &'static str = "
+impl Explain for Day11 {}
+
+impl Visualize for Day11 {}
+
+impl TryParse for Day11 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		let grid = Grid::new(input.trim())
+			.map_err(|e| ParseError::new(input, e.text, e.kind.into()))?;
+		if let Some((pos, _)) = grid.iter().find(|(_, c)| *c != '.' && *c != '#') {
+			return Err(ParseError::new(input, grid.cell(pos).unwrap(), ParseErrorKind::UnexpectedTile));
+		}
+
+		let coords = grid.iter()
+			.filter(|(_, c)| *c == '#')
+			.map(|(pos, _)| pos)
+			.collect_vec();
+		tracing::debug!("Coordinates: {} total", coords.len());
+		Ok(Coordinates { raw: coords })
+	}
+}
+
+pub const TEST_INPUT: &'static str = "
 ...#......
 .......#..
 #.........
@@ -174,13 +228,13 @@ const TEST_INPUT: &'static str = "
 #[test]
 fn scaled_stars() {
 	let cases = [
-		(2, 374),
-		(10, 1030),
-		(100, 8410),
+		(2, Ok(answer(374))),
+		(10, Ok(answer(1030))),
+		(100, Ok(answer(8410))),
 	];
 	run_test(|scale| {
 		let mut d11 = Day11.parse(TEST_INPUT);
-		d11.expand(*scale);
+		d11.expand(*scale)?;
 		d11.pair_dist_sum()
 	}, &cases);
 }
@@ -188,8 +242,7 @@ fn scaled_stars() {
 #[test]
 fn part1() {
 	let cases = [
-		(TEST_INPUT, 374),
-		(daystr!("11"), 9545480),
+		(TEST_INPUT, answer(374)),
 	];
 	test_runner::<Day11, _>(Day11, DayPart::Part1, &cases);
 }
@@ -197,8 +250,12 @@ fn part1() {
 #[test]
 fn part2() {
 	let cases = [
-		// (TEST_INPUT, 0),
-		(daystr!("11"), 0),
+		(TEST_INPUT, answer(82000210)),
 	];
 	test_runner::<Day11, _>(Day11, DayPart::Part2, &cases);
 }
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day11, daystr!("11"));
+}
@@ -2,6 +2,8 @@ use aoch::{run_day, daystr};
 use day11::Day11;
 
 fn main() {
-	run_day::<Day11>(Day11, daystr!("11"), None);
+	aocinput::source::for_each_input(daystr!("11"), |input| {
+		run_day::<Day11>(Day11, input, None);
+	});
 }
 
@@ -0,0 +1,59 @@
+use aocinput::gen::{rng, Generate, Generated, Rng};
+use crate::Day12;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+	pub rows: usize,
+	/// Springs in each row
+	pub length: usize,
+	/// The chance that a spring's condition is unknown
+	pub unknown: f64,
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Params { rows: 1000, length: 20, unknown: 0.4 }
+	}
+}
+
+impl Generate for Day12 {
+	type Params = Params;
+
+	/// Each row starts from a known arrangement with some springs hidden, so there's always at least one way to
+	/// arrange it. Counting them is the puzzle itself, so no answers are generated.
+	fn generate(&self, seed: u64, params: &Params) -> Generated<usize> {
+		let mut rng = rng(seed);
+		let mut input = String::new();
+		for _ in 0..params.rows {
+			let mut damaged: Vec<bool> = (0..params.length.max(1)).map(|_| rng.gen_bool(0.5)).collect();
+			let at = rng.gen_range(0..damaged.len());
+			damaged[at] = true;
+
+			let groups: Vec<String> = damaged.split(|d| !d)
+				.filter(|g| !g.is_empty())
+				.map(|g| g.len().to_string())
+				.collect();
+			let springs: String = damaged.iter()
+				.map(|&d| match (rng.gen_bool(params.unknown), d) {
+					(true, _) => '?',
+					(false, true) => '#',
+					(false, false) => '.',
+				})
+				.collect();
+			input += &format!("{} {}\n", springs, groups.join(","));
+		}
+		Generated { input, part1: None, part2: None }
+	}
+}
+
+#[test]
+fn generated() {
+	use aoch::AoCDay;
+	// there is always at least one arrangement of each row
+	let params = Params { rows: 50, ..Params::default() };
+	for seed in 0..5 {
+		let generated = Day12.generate(seed, &params);
+		let mut data = Day12.parse(&generated.input);
+		assert!(Day12.part1(&mut data) >= params.rows);
+	}
+}
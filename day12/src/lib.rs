@@ -0,0 +1,233 @@
+#![allow(unused_imports)]
+use std::{fmt, str::FromStr};
+use std::fmt::Debug;
+use itertools::Itertools;
+use test_log::test;
+use aoch::AoCDay;
+use aocinput::{Explain, Located, TryParse, TrySolve, Visualize};
+#[cfg(test)] #[allow(unused_imports)]
+use aoch::{DayPart, run_test, daystr};
+#[cfg(test)] #[allow(unused_imports)]
+use aocinput::answers::test_runner;
+
+#[cfg(feature = "gen")]
+pub mod gen;
+
+#[derive(Debug,Clone,Copy)]
+pub struct Day12;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("expected a `<springs> <groups>` pair")]
+	MissingGroups,
+	#[error("unexpected spring character")]
+	UnknownSpring,
+	#[error("invalid group size: {0}")]
+	InvalidNumber(std::num::ParseIntError),
+}
+pub type ParseError = Located<ParseErrorKind>;
+
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+enum Spring {
+	Operational,
+	Damaged,
+	Unknown,
+}
+
+impl Spring {
+	fn from_char(c: char) -> Option<Spring> {
+		match c {
+			'.' => Some(Spring::Operational),
+			'#' => Some(Spring::Damaged),
+			'?' => Some(Spring::Unknown),
+			_ => None,
+		}
+	}
+	fn to_char(self) -> char {
+		match self {
+			Spring::Operational => '.',
+			Spring::Damaged => '#',
+			Spring::Unknown => '?',
+		}
+	}
+}
+
+/// A row of springs, alongside the contiguous damaged group sizes recorded for it.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Row {
+	springs: Vec<Spring>,
+	groups: Vec<usize>,
+}
+
+impl FromStr for Row {
+	type Err = ParseError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (raw_springs, raw_groups) = s.split_once(' ')
+			.ok_or_else(|| ParseError::new(s, s, ParseErrorKind::MissingGroups))?;
+		let springs = raw_springs.char_indices()
+			.map(|(i, c)| {
+				Spring::from_char(c)
+					.ok_or_else(|| ParseError::new(s, &raw_springs[i..i+c.len_utf8()], ParseErrorKind::UnknownSpring))
+			})
+			.collect::<Result<_, _>>()?;
+		let groups = raw_groups.split(',')
+			.map(|g| g.parse().map_err(|e| ParseError::new(s, g, ParseErrorKind::InvalidNumber(e))))
+			.collect::<Result<_, _>>()?;
+		Ok(Row { springs, groups })
+	}
+}
+
+/// Formats the row as it would appear in the input.
+impl fmt::Display for Row {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let springs: String = self.springs.iter().copied().map(Spring::to_char).collect();
+		write!(f, "{} {}", springs, self.groups.iter().join(","))
+	}
+}
+
+impl Row {
+	/// Repeats the row `times` times, joining the springs with an unknown spring in between.
+	fn unfold(&self, times: usize) -> Row {
+		let springs = std::iter::repeat_n(self.springs.as_slice(), times)
+			.collect_vec()
+			.join(&Spring::Unknown);
+		let groups = self.groups.repeat(times);
+		Row { springs, groups }
+	}
+
+	/// Counts the ways the unknown springs can be filled in to match the damaged groups.
+	///
+	/// Each (spring index, group index) pair has a fixed number of ways to place the remaining groups in the
+	/// remaining springs, so those are memoized into a flat table instead of re-walked for every branch.
+	fn arrangements(&self) -> usize {
+		struct Counter<'r> {
+			springs: &'r [Spring],
+			groups: &'r [usize],
+			memo: Vec<Option<usize>>,
+		}
+		impl Counter<'_> {
+			fn count(&mut self, si: usize, gi: usize) -> usize {
+				let key = si*(self.groups.len()+1) + gi;
+				if let Some(ways) = self.memo[key] {
+					return ways;
+				}
+
+				let rest = &self.springs[si..];
+				let ways = match (rest.first(), self.groups.get(gi)) {
+					// all groups placed, the remainder must be able to be operational
+					(_, None) => rest.iter().all(|s| *s != Spring::Damaged) as usize,
+					// ran out of springs with groups left
+					(None, Some(_)) => 0,
+					(Some(&spring), Some(&group)) => {
+						let mut ways = 0;
+						if spring != Spring::Damaged {
+							// treat this one as operational and move on
+							ways += self.count(si+1, gi);
+						}
+						if spring != Spring::Operational {
+							// start the group here, if it fits and isn't immediately followed by another damaged spring
+							let fits = rest.len() >= group
+								&& rest[..group].iter().all(|s| *s != Spring::Operational)
+								&& rest.get(group) != Some(&Spring::Damaged);
+							if fits {
+								let next = (si + group + 1).min(self.springs.len());
+								ways += self.count(next, gi+1);
+							}
+						}
+						ways
+					}
+				};
+
+				self.memo[key] = Some(ways);
+				ways
+			}
+		}
+
+		let mut counter = Counter {
+			springs: &self.springs,
+			groups: &self.groups,
+			memo: vec![None; (self.springs.len()+1)*(self.groups.len()+1)],
+		};
+		counter.count(0, 0)
+	}
+}
+
+impl AoCDay for Day12 {
+	type Data<'i> = Vec<Row>;
+	type Answer = usize;
+
+	fn day(&self) -> u8 { 12 }
+
+	fn parse<'i>(&self, input: &'i str) -> Self::Data<'i> {
+		self.try_parse(input).unwrap_or_else(|e| panic!("{}", e))
+	}
+	fn part1(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		_data.iter()
+			.map(Row::arrangements)
+			.sum()
+	}
+	fn part2(&self, _data: &mut Self::Data<'_>) -> Self::Answer {
+		_data.iter()
+			.map(|row| row.unfold(5).arrangements())
+			.sum()
+	}
+}
+
+impl TrySolve for Day12 {}
+
+impl Explain for Day12 {}
+
+impl Visualize for Day12 {}
+
+impl TryParse for Day12 {
+	type Error = ParseError;
+
+	fn try_parse<'i>(&self, input: &'i str) -> Result<Self::Data<'i>, Self::Error> {
+		aocinput::from_lines(input)
+	}
+}
+
+pub const TEST_INPUT: &str = "
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+#[test]
+fn row_arrangements() {
+	let cases = [
+		("???.### 1,1,3", (1, 1)),
+		(".??..??...?##. 1,1,3", (4, 16384)),
+		("?#?#?#?#?#?#?#? 1,3,1,6", (1, 1)),
+		("????.#...#... 4,1,1", (1, 16)),
+		("????.######..#####. 1,6,5", (4, 2500)),
+		("?###???????? 3,2,1", (10, 506250)),
+	];
+	run_test(|line| {
+		let row: Row = line.parse().unwrap();
+		(row.arrangements(), row.unfold(5).arrangements())
+	}, &cases);
+}
+
+#[test]
+fn part1() {
+	let cases = [
+		(TEST_INPUT, 21),
+	];
+	test_runner::<Day12, _>(Day12, DayPart::Part1, &cases);
+}
+#[test]
+fn part2() {
+	let cases = [
+		(TEST_INPUT, 525152),
+	];
+	test_runner::<Day12, _>(Day12, DayPart::Part2, &cases);
+}
+
+#[test]
+fn answers() {
+	aocinput::answers::test_answers(Day12, daystr!("12"));
+}
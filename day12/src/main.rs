@@ -0,0 +1,9 @@
+use aoch::{run_day, daystr};
+use day12::Day12;
+
+fn main() {
+	aocinput::source::for_each_input(daystr!("12"), |input| {
+		run_day::<Day12>(Day12, input, None);
+	});
+}
+
@@ -0,0 +1,191 @@
+//! Every implemented day behind one object-safe trait, so they can be kept in one collection and picked by
+//! number at runtime instead of each being monomorphised separately.
+//!
+//! ```
+//! let day = days::get(9).unwrap();
+//! assert_eq!(day.solve("0 3 6 9 12 15", 1).unwrap().to_string(), "18");
+//! ```
+use std::fmt::Display;
+use aoch::{daystr, AoCDay};
+use aocinput::{Animation, Answer, Explain, Explanation, SolveError, TryParse, TrySolve, Visualize};
+use aocinput::normalize::normalize;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DayError {
+	#[error("parse error: {0}")]
+	Parse(String),
+	#[error(transparent)]
+	Solve(#[from] SolveError),
+	#[error("there is no part {0}")]
+	NoSuchPart(u8),
+}
+
+/// A day, taking its input as a string and giving its answers as an [`Answer`] whatever the day's own types are
+pub trait DynDay: Send + Sync {
+	fn day(&self) -> u8;
+
+	/// The day's embedded puzzle input, which is empty if it wasn't available when this was built
+	fn embedded(&self) -> &'static str;
+
+	/// Parses an input that has already been normalised, like one read from a [`Source`](aocinput::Source), so
+	/// that each of its parts can be solved separately.
+	fn parse<'a>(&'a self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, DayError>;
+
+	/// Normalises and parses the input, then solves a part of it.
+	fn solve(&self, input: &str, part: u8) -> Result<Answer, DayError> {
+		check_part(part)?;
+		let input = normalize(input).text;
+		let mut parsed = self.parse(&input)?;
+		parsed.solve(part)
+	}
+
+	/// Normalises and parses the input, then explains how a part of it is solved, if the day can.
+	fn explain(&self, input: &str, part: u8) -> Result<Option<Explanation>, DayError> {
+		check_part(part)?;
+		let input = normalize(input).text;
+		let mut parsed = self.parse(&input)?;
+		parsed.explain(part)
+	}
+
+	/// Normalises and parses the input, then animates how a part of it is solved, if the day can.
+	fn visualize(&self, input: &str, part: u8) -> Result<Option<Animation>, DayError> {
+		check_part(part)?;
+		let input = normalize(input).text;
+		let mut parsed = self.parse(&input)?;
+		parsed.visualize(part)
+	}
+}
+
+/// A day's parsed input. Parts can change the data as they solve it, so each should get a fresh parse.
+pub trait Parsed {
+	fn solve(&mut self, part: u8) -> Result<Answer, DayError>;
+	fn explain(&mut self, part: u8) -> Result<Option<Explanation>, DayError>;
+	fn visualize(&mut self, part: u8) -> Result<Option<Animation>, DayError>;
+}
+
+fn check_part(part: u8) -> Result<(), DayError> {
+	match part {
+		1 | 2 => Ok(()),
+		_ => Err(DayError::NoSuchPart(part)),
+	}
+}
+
+/// Puts a day behind [`DynDay`], along with its embedded input
+pub struct Registered<D> {
+	day: D,
+	embedded: &'static str,
+}
+
+impl<D> Registered<D> {
+	pub const fn new(day: D, embedded: &'static str) -> Registered<D> {
+		Registered { day, embedded }
+	}
+}
+
+impl<D> DynDay for Registered<D>
+where
+	D: TryParse + TrySolve + Explain + Visualize + Send + Sync,
+	D::Answer: Display,
+{
+	fn day(&self) -> u8 {
+		self.day.day()
+	}
+
+	fn embedded(&self) -> &'static str {
+		self.embedded
+	}
+
+	fn parse<'a>(&'a self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, DayError> {
+		let data = self.day.try_parse(input).map_err(|e| DayError::Parse(e.to_string()))?;
+		Ok(Box::new(ParsedDay { day: &self.day, data }))
+	}
+}
+
+struct ParsedDay<'a, D: AoCDay> {
+	day: &'a D,
+	data: D::Data<'a>,
+}
+
+impl<D> Parsed for ParsedDay<'_, D>
+where
+	D: TrySolve + Explain + Visualize,
+	D::Answer: Display,
+{
+	fn solve(&mut self, part: u8) -> Result<Answer, DayError> {
+		let answer = match check_part(part).map(|()| part)? {
+			1 => self.day.try_part1(&mut self.data)?,
+			_ => self.day.try_part2(&mut self.data)?,
+		};
+		Ok(Answer::of(&answer))
+	}
+
+	fn explain(&mut self, part: u8) -> Result<Option<Explanation>, DayError> {
+		Ok(match check_part(part).map(|()| part)? {
+			1 => self.day.explain_part1(&mut self.data),
+			_ => self.day.explain_part2(&mut self.data),
+		})
+	}
+
+	fn visualize(&mut self, part: u8) -> Result<Option<Animation>, DayError> {
+		Ok(match check_part(part).map(|()| part)? {
+			1 => self.day.visualize_part1(&mut self.data),
+			_ => self.day.visualize_part2(&mut self.data),
+		})
+	}
+}
+
+static REGISTRY: [&dyn DynDay; 12] = [
+	&Registered::new(day01::Day01, daystr!("01")),
+	&Registered::new(day02::Day02, daystr!("02")),
+	&Registered::new(day03::Day03, daystr!("03")),
+	&Registered::new(day04::Day04, daystr!("04")),
+	&Registered::new(day05::Day05, daystr!("05")),
+	&Registered::new(day06::Day06, daystr!("06")),
+	&Registered::new(day07::Day07, daystr!("07")),
+	&Registered::new(day08::Day08, daystr!("08")),
+	&Registered::new(day09::Day09, daystr!("09")),
+	&Registered::new(day10::Day10, daystr!("10")),
+	&Registered::new(day11::Day11, daystr!("11")),
+	&Registered::new(day12::Day12, daystr!("12")),
+];
+
+/// Every implemented day, in order
+pub fn registry() -> &'static [&'static dyn DynDay] {
+	&REGISTRY
+}
+
+/// The day with the given number, if it is implemented.
+pub fn get(day: u8) -> Option<&'static dyn DynDay> {
+	registry().iter().copied().find(|d| d.day() == day)
+}
+
+#[test]
+fn registered() {
+	let days = registry().iter().map(|d| d.day()).collect::<Vec<_>>();
+	assert_eq!(days, (1..=12).collect::<Vec<_>>());
+	assert!(get(13).is_none());
+}
+
+#[test]
+fn dispatched() {
+	let cases: [(u8, &str, [Answer; 2]); 4] = [
+		(2, day02::TEST_INPUT, [8usize.into(), 2286usize.into()]),
+		(5, day05::TEST_INPUT, [35usize.into(), 46usize.into()]),
+		(7, day07::TEST_INPUT, [6440usize.into(), 5905usize.into()]),
+		(9, day09::TEST_INPUT, [114isize.into(), 2isize.into()]),
+	];
+	for (n, input, answers) in cases {
+		let day = get(n).unwrap();
+		for (part, expected) in [1, 2].into_iter().zip(answers) {
+			assert_eq!(day.solve(input, part), Ok(expected), "day {} part {}", n, part);
+		}
+	}
+
+	let day02 = get(2).unwrap();
+	assert!(day02.explain(day02::TEST_INPUT, 1).unwrap().is_some());
+	assert_eq!(get(9).unwrap().explain(day09::TEST_INPUT, 2), Ok(None));
+	assert!(get(5).unwrap().visualize(day05::TEST_INPUT, 2).unwrap().is_some());
+	assert_eq!(get(9).unwrap().visualize(day09::TEST_INPUT, 1), Ok(None));
+	assert_eq!(day02.solve(day02::TEST_INPUT, 3), Err(DayError::NoSuchPart(3)));
+	assert!(matches!(day02.solve("Game one", 1), Err(DayError::Parse(_))));
+}
@@ -0,0 +1,34 @@
+//! Feeds arbitrary text to a day's parser, which must either succeed or return an error, never panic.
+//!
+//! The first byte picks the day (modulo 12, with 0 being day 1), so a single corpus covers all of them.
+//! Run with `cargo +nightly fuzz run parse` from the repository root.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use aocinput::TryParse;
+
+fn check<D: TryParse>(day: D, input: &str) {
+	if let Err(e) = day.try_parse(input) {
+		// the error's span has to be displayable too
+		let _ = e.to_string();
+	}
+}
+
+fuzz_target!(|data: &[u8]| {
+	let Some((&day, rest)) = data.split_first() else { return };
+	let Ok(input) = std::str::from_utf8(rest) else { return };
+	match day % 12 + 1 {
+		1 => check(day01::Day01, input),
+		2 => check(day02::Day02, input),
+		3 => check(day03::Day03, input),
+		4 => check(day04::Day04, input),
+		5 => check(day05::Day05, input),
+		6 => check(day06::Day06, input),
+		7 => check(day07::Day07, input),
+		8 => check(day08::Day08, input),
+		9 => check(day09::Day09, input),
+		10 => check(day10::Day10, input),
+		11 => check(day11::Day11, input),
+		12 => check(day12::Day12, input),
+		_ => unreachable!(),
+	}
+});
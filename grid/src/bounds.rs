@@ -0,0 +1,50 @@
+use crate::Coord;
+
+/// An inclusive rectangle of coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+	pub min: Coord,
+	pub max: Coord,
+}
+
+impl Bounds {
+	/// The smallest bounds that contain every coordinate, or None if there are none.
+	pub fn enclosing<I: IntoIterator<Item = Coord>>(coords: I) -> Option<Bounds> {
+		let mut iter = coords.into_iter();
+		let first = iter.next()?;
+		Some(iter.fold(Bounds { min: first, max: first }, |mut b, (x, y)| {
+			b.min = (b.min.0.min(x), b.min.1.min(y));
+			b.max = (b.max.0.max(x), b.max.1.max(y));
+			b
+		}))
+	}
+
+	pub fn width(&self) -> usize {
+		self.max.0 - self.min.0 + 1
+	}
+	pub fn height(&self) -> usize {
+		self.max.1 - self.min.1 + 1
+	}
+	pub fn contains(&self, (x, y): Coord) -> bool {
+		(self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+	}
+
+	/// Every coordinate within the bounds, row by row
+	pub fn coords(&self) -> impl Iterator<Item = Coord> {
+		let Bounds { min, max } = *self;
+		(min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+	}
+}
+
+#[test]
+fn enclosing() {
+	assert_eq!(Bounds::enclosing([]), None);
+
+	let b = Bounds::enclosing([(4, 1), (2, 7), (3, 3)]).unwrap();
+	assert_eq!(b, Bounds { min: (2, 1), max: (4, 7) });
+	assert_eq!((b.width(), b.height()), (3, 7));
+	assert!(b.contains((2, 1)) && b.contains((4, 7)));
+	assert!(!b.contains((1, 1)) && !b.contains((4, 8)));
+	assert_eq!(b.coords().count(), 21);
+	assert_eq!(b.coords().take(4).collect::<Vec<_>>(), [(2, 1), (3, 1), (4, 1), (2, 2)]);
+}
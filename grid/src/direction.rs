@@ -0,0 +1,108 @@
+use crate::Coord;
+
+/// A cardinal direction on a grid, where north is towards the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+	North,
+	East,
+	South,
+	West,
+}
+
+impl Direction {
+	/// Every direction, clockwise from north
+	pub const ALL: [Direction; 4] = [
+		Direction::North,
+		Direction::East,
+		Direction::South,
+		Direction::West,
+	];
+
+	/// The (x, y) change from moving a single tile in this direction
+	#[inline(always)]
+	pub const fn offset(&self) -> (isize, isize) {
+		use Direction::*;
+		match self {
+			North => (0, -1),
+			East => (1, 0),
+			South => (0, 1),
+			West => (-1, 0),
+		}
+	}
+
+	#[inline(always)]
+	pub fn forward(&self) -> Direction {
+		*self
+	}
+	#[inline(always)]
+	pub fn behind(&self) -> Direction {
+		use Direction::*;
+		match self {
+			North => South,
+			East => West,
+			South => North,
+			West => East,
+		}
+	}
+	#[inline(always)]
+	pub fn left(&self) -> Direction {
+		use Direction::*;
+		match self {
+			North => West,
+			East => North,
+			South => East,
+			West => South,
+		}
+	}
+	#[inline(always)]
+	pub fn right(&self) -> Direction {
+		use Direction::*;
+		match self {
+			North => East,
+			East => South,
+			South => West,
+			West => North,
+		}
+	}
+}
+
+impl std::ops::Add<Direction> for (isize, isize) {
+	type Output = (isize, isize);
+
+	fn add(self, rhs: Direction) -> Self::Output {
+		let (ox, oy) = rhs.offset();
+		(self.0 + ox, self.1 + oy)
+	}
+}
+
+/// Moves one tile over, returning None if that would go below zero on either axis.
+/// Upper bounds are left to the grid, see [`Grid::step`](crate::Grid::step).
+impl std::ops::Add<Direction> for Coord {
+	type Output = Option<Coord>;
+
+	fn add(self, rhs: Direction) -> Self::Output {
+		let (ox, oy) = rhs.offset();
+		Some((
+			self.0.checked_add_signed(ox)?,
+			self.1.checked_add_signed(oy)?,
+		))
+	}
+}
+
+#[test]
+fn turning() {
+	for d in Direction::ALL {
+		assert_eq!(d.left().right(), d);
+		assert_eq!(d.right().right(), d.behind());
+		assert_eq!(d.left().left().left(), d.right());
+		assert_eq!(d.behind().behind(), d.forward());
+
+		// turning right is clockwise
+		let (x, y) = d.offset();
+		assert_eq!(d.right().offset(), (-y, x));
+	}
+
+	assert_eq!((0usize, 0usize) + Direction::North, None);
+	assert_eq!((0usize, 0usize) + Direction::East, Some((1, 0)));
+	assert_eq!((3isize, 0isize) + Direction::North, (3, -1));
+}
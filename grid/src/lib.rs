@@ -0,0 +1,236 @@
+//! A 2D grid of ascii tiles borrowed straight from puzzle input, with the coordinate and direction
+//! helpers that the grid puzzles keep needing.
+use std::ops::Range;
+
+mod bounds;
+mod direction;
+
+pub use bounds::Bounds;
+pub use direction::Direction;
+
+/// An (x, y) position, where (0, 0) is the first tile of the first row
+pub type Coord = (usize, usize);
+
+/// Offsets to every tile surrounding another, in row order
+const AROUND: [(isize, isize); 8] = [
+	(-1, -1), (0, -1), (1, -1),
+	(-1, 0), /* tile */ (1, 0),
+	(-1, 1), (0, 1), (1, 1),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum GridErrorKind {
+	#[error("grid is empty")]
+	Empty,
+	#[error("grid is not ascii")]
+	NotAscii,
+	#[error("line is {found} tiles long, while the first line is {expected}")]
+	Ragged { found: usize, expected: usize },
+	#[error("line endings are not consistent")]
+	MixedLineEndings,
+}
+
+/// A malformed grid, along with the text that was wrong with it so the caller can say where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{kind}")]
+pub struct GridError<'s> {
+	/// The offending text, or all of the source if there wasn't anything more specific.
+	pub text: &'s str,
+	pub kind: GridErrorKind,
+}
+
+/// A rectangular grid of ascii tiles, stored as the raw input text with its line endings still included.
+///
+/// A grid can also be a view of a smaller region of a larger grid, in which case coordinates are relative
+/// to the top-left of the view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grid<'s> {
+	src: &'s str,
+	/// bytes from the start of one row to the next, including the line ending
+	stride: usize,
+	origin: Coord,
+	width: usize,
+	height: usize,
+}
+
+impl<'s> Grid<'s> {
+	/// Creates a grid from lines of equal length. Either LF or CRLF line endings are accepted, but not both.
+	pub fn new(src: &'s str) -> Result<Grid<'s>, GridError<'s>> {
+		if let Some((i, c)) = src.char_indices().find(|(_, c)| !c.is_ascii()) {
+			return Err(GridError { text: &src[i..i+c.len_utf8()], kind: GridErrorKind::NotAscii });
+		}
+
+		let mut lines = src.lines();
+		let first = lines.next().filter(|l| !l.is_empty())
+			.ok_or(GridError { text: src, kind: GridErrorKind::Empty })?;
+		// distance between the starts of the first two lines
+		let stride = lines.next()
+			.map(|second| second.as_ptr() as usize - src.as_ptr() as usize)
+			.unwrap_or(first.len());
+
+		let mut height = 0;
+		for (y, line) in src.lines().enumerate() {
+			if line.len() != first.len() {
+				let kind = GridErrorKind::Ragged { found: line.len(), expected: first.len() };
+				return Err(GridError { text: line, kind });
+			}
+			if line.as_ptr() as usize - src.as_ptr() as usize != y*stride {
+				return Err(GridError { text: line, kind: GridErrorKind::MixedLineEndings });
+			}
+			height += 1;
+		}
+
+		Ok(Grid { src, stride, origin: (0, 0), width: first.len(), height })
+	}
+
+	pub fn width(&self) -> usize {
+		self.width
+	}
+	pub fn height(&self) -> usize {
+		self.height
+	}
+	pub fn bounds(&self) -> Bounds {
+		Bounds { min: (0, 0), max: (self.width-1, self.height-1) }
+	}
+	pub fn contains(&self, (x, y): Coord) -> bool {
+		x < self.width && y < self.height
+	}
+
+	fn index(&self, (x, y): Coord) -> Option<usize> {
+		self.contains((x, y))
+			.then(|| (self.origin.1 + y)*self.stride + self.origin.0 + x)
+	}
+
+	pub fn get(&self, coord: Coord) -> Option<char> {
+		self.index(coord).map(|i| self.src.as_bytes()[i] as char)
+	}
+
+	/// The tile as a slice of the source text, for pointing at it in errors.
+	pub fn cell(&self, coord: Coord) -> Option<&'s str> {
+		self.index(coord).map(|i| &self.src[i..i+1])
+	}
+
+	pub fn row(&self, y: usize) -> Option<&'s str> {
+		let start = self.index((0, y))?;
+		Some(&self.src[start..start+self.width])
+	}
+	pub fn rows(&self) -> impl Iterator<Item = &'s str> + '_ {
+		(0..self.height).map(|y| self.row(y).unwrap())
+	}
+
+	/// Every tile, row by row
+	pub fn iter(&self) -> impl Iterator<Item = (Coord, char)> + '_ {
+		self.bounds().coords().map(|c| (c, self.get(c).unwrap()))
+	}
+
+	/// Finds the first tile, row by row, that matches the predicate.
+	pub fn position<F: FnMut(char) -> bool>(&self, mut pred: F) -> Option<Coord> {
+		self.iter().find(|(_, t)| pred(*t)).map(|(c, _)| c)
+	}
+
+	/// Moves `(dx, dy)` tiles away, if that is still within the grid.
+	pub fn offset(&self, (x, y): Coord, (dx, dy): (isize, isize)) -> Option<Coord> {
+		let moved = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+		self.contains(moved).then_some(moved)
+	}
+
+	/// Moves one tile over, if that is still within the grid.
+	pub fn step(&self, coord: Coord, dir: Direction) -> Option<Coord> {
+		self.offset(coord, dir.offset())
+	}
+
+	/// The up to four tiles sharing an edge with a tile, in NESW order.
+	pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = (Coord, Direction, char)> + '_ {
+		Direction::ALL.into_iter()
+			.filter_map(move |dir| {
+				let n = self.step(coord, dir)?;
+				Some((n, dir, self.get(n).unwrap()))
+			})
+	}
+
+	/// The up to eight tiles surrounding a tile, including diagonals, in row order.
+	pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = (Coord, char)> + '_ {
+		AROUND.into_iter()
+			.filter_map(move |off| {
+				let n = self.offset(coord, off)?;
+				Some((n, self.get(n).unwrap()))
+			})
+	}
+
+	/// A view of a smaller region of this grid, or None if the region is empty or reaches outside it.
+	pub fn view(&self, xs: Range<usize>, ys: Range<usize>) -> Option<Grid<'s>> {
+		if xs.is_empty() || ys.is_empty() || xs.end > self.width || ys.end > self.height {
+			return None;
+		}
+		Some(Grid {
+			src: self.src,
+			stride: self.stride,
+			origin: (self.origin.0 + xs.start, self.origin.1 + ys.start),
+			width: xs.len(),
+			height: ys.len(),
+		})
+	}
+}
+
+#[cfg(test)]
+const SAMPLE: &str = "\
+abc
+def
+ghi
+jkl";
+
+#[test]
+fn construction() {
+	let grid = Grid::new(SAMPLE).unwrap();
+	assert_eq!((grid.width(), grid.height()), (3, 4));
+	assert_eq!(grid.get((1, 2)), Some('h'));
+	assert_eq!(grid.get((3, 0)), None);
+	assert_eq!(grid.rows().collect::<Vec<_>>(), ["abc", "def", "ghi", "jkl"]);
+
+	let crlf = SAMPLE.replace('\n', "\r\n");
+	let grid = Grid::new(&crlf).unwrap();
+	assert_eq!(grid.get((1, 2)), Some('h'));
+	assert_eq!(grid.row(3), Some("jkl"));
+	assert_eq!(grid.position(|c| c == 'k'), Some((1, 3)));
+
+	let err = |src| Grid::new(src).map_err(|e| (e.text, e.kind));
+	assert_eq!(err(""), Err(("", GridErrorKind::Empty)));
+	assert_eq!(err("ab\nc"), Err(("c", GridErrorKind::Ragged { found: 1, expected: 2 })));
+	assert_eq!(err("ab\r\ncd\nef"), Err(("ef", GridErrorKind::MixedLineEndings)));
+	assert_eq!(err("ab\nc─"), Err(("─", GridErrorKind::NotAscii)));
+}
+
+#[test]
+fn neighbourhoods() {
+	let grid = Grid::new(SAMPLE).unwrap();
+
+	let n4 = grid.neighbours4((0, 1)).collect::<Vec<_>>();
+	assert_eq!(n4, [((0, 0), Direction::North, 'a'), ((1, 1), Direction::East, 'e'), ((0, 2), Direction::South, 'g')]);
+
+	let n8 = grid.neighbours8((1, 1)).map(|(_, c)| c).collect::<String>();
+	assert_eq!(n8, "abcdfghi");
+	let n8 = grid.neighbours8((2, 3)).map(|(_, c)| c).collect::<String>();
+	assert_eq!(n8, "hik");
+
+	assert_eq!(grid.step((2, 0), Direction::East), None);
+	assert_eq!(grid.step((2, 0), Direction::South), Some((2, 1)));
+	assert_eq!(grid.offset((2, 3), (-2, -3)), Some((0, 0)));
+	assert_eq!(grid.offset((2, 3), (-3, 0)), None);
+}
+
+#[test]
+fn views() {
+	let grid = Grid::new(SAMPLE).unwrap();
+	let view = grid.view(1..3, 1..3).unwrap();
+	assert_eq!(view.rows().collect::<Vec<_>>(), ["ef", "hi"]);
+	assert_eq!(view.get((0, 0)), Some('e'));
+	assert_eq!(view.get((2, 0)), None);
+	assert_eq!(view.neighbours8((0, 0)).map(|(_, c)| c).collect::<String>(), "fhi");
+
+	let nested = view.view(1..2, 0..2).unwrap();
+	assert_eq!(nested.rows().collect::<Vec<_>>(), ["f", "i"]);
+	assert_eq!(nested.cell((0, 1)).map(|s| s.as_ptr()), grid.cell((2, 2)).map(|s| s.as_ptr()));
+
+	assert_eq!(grid.view(1..4, 0..1), None);
+	assert_eq!(grid.view(1..1, 0..1), None);
+}
@@ -0,0 +1,288 @@
+//! The bits of number theory that keep coming up: the cycles that line up at a least common multiple, the
+//! quadratics that want an exact square root, and the finite differences that are really binomial sums.
+//!
+//! Anything that can overflow returns `None` when it does, rather than a wrong answer.
+
+/// The unsigned integers, with just enough arithmetic for the generic functions here
+pub trait Unsigned: Copy + Ord + std::fmt::Debug {
+	const ZERO: Self;
+	const ONE: Self;
+	fn checked_add(self, rhs: Self) -> Option<Self>;
+	fn checked_mul(self, rhs: Self) -> Option<Self>;
+	fn div(self, rhs: Self) -> Self;
+	fn rem(self, rhs: Self) -> Self;
+	fn bits(self) -> u32;
+	fn shr(self, bits: u32) -> Self;
+	fn shl(self, bits: u32) -> Self;
+}
+
+macro_rules! unsigned {
+	($($t:ty),*) => {$(
+		impl Unsigned for $t {
+			const ZERO: Self = 0;
+			const ONE: Self = 1;
+			fn checked_add(self, rhs: Self) -> Option<Self> { <$t>::checked_add(self, rhs) }
+			fn checked_mul(self, rhs: Self) -> Option<Self> { <$t>::checked_mul(self, rhs) }
+			fn div(self, rhs: Self) -> Self { self / rhs }
+			fn rem(self, rhs: Self) -> Self { self % rhs }
+			fn bits(self) -> u32 { <$t>::BITS - self.leading_zeros() }
+			fn shr(self, bits: u32) -> Self { self >> bits }
+			fn shl(self, bits: u32) -> Self { self << bits }
+		}
+	)*};
+}
+unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// The greatest common divisor, where `gcd(0, n)` is `n`.
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+	while b != T::ZERO {
+		(a, b) = (b, a.rem(b));
+	}
+	a
+}
+
+/// The least common multiple, where `lcm(0, n)` is `0`.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+	if a == T::ZERO || b == T::ZERO {
+		return Some(T::ZERO);
+	}
+	a.div(gcd(a, b)).checked_mul(b)
+}
+
+/// The greatest common divisor of every number, which is `0` if there are none.
+pub fn gcd_all<T: Unsigned>(nums: &[T]) -> T {
+	nums.iter().fold(T::ZERO, |acc, n| gcd(acc, *n))
+}
+
+/// The least common multiple of every number, which is `1` if there are none.
+pub fn lcm_all<T: Unsigned>(nums: &[T]) -> Option<T> {
+	nums.iter().try_fold(T::ONE, |acc, n| lcm(acc, *n))
+}
+
+/// The largest integer whose square is at most `n`, found exactly without going through floating point.
+pub fn isqrt<T: Unsigned>(n: T) -> T {
+	if n <= T::ONE {
+		return n;
+	}
+	// start above the root, at a power of two, then Newton's method only ever steps down towards it
+	let mut x = T::ONE.shl(n.bits().div_ceil(2));
+	loop {
+		let next = x.checked_add(n.div(x)).expect("x and n/x are both at most the root's starting point").shr(1);
+		if next >= x {
+			return x;
+		}
+		x = next;
+	}
+}
+
+/// The number of ways to choose `k` things from `n`.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+	if k > n {
+		return Some(0);
+	}
+	let k = k.min(n - k);
+	let mut c: u64 = 1;
+	for i in 0..k {
+		// `c` is C(n, i), so this is exactly divisible, and the product fits as both sides are 64 bit
+		let next = c as u128 * (n - i) as u128 / (i + 1) as u128;
+		c = next.try_into().ok()?;
+	}
+	Some(c)
+}
+
+/// `base` to the power of `exp`, modulo `m`.
+///
+/// Panics if `m` is zero.
+pub fn modpow(base: u64, mut exp: u64, m: u64) -> u64 {
+	assert!(m != 0, "modulo zero");
+	let m = m as u128;
+	let mut base = base as u128 % m;
+	let mut result = 1 % m;
+	while exp > 0 {
+		if exp & 1 == 1 {
+			result = result * base % m;
+		}
+		base = base * base % m;
+		exp >>= 1;
+	}
+	result as u64
+}
+
+/// The `(g, x, y)` where `g = gcd(a, b)` and `a*x + b*y = g`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+	if b == 0 {
+		(a, 1, 0)
+	} else {
+		let (g, x, y) = extended_gcd(b, a % b);
+		(g, y, x - a / b * y)
+	}
+}
+
+/// Solves a system of congruences `x ≡ a (mod m)` given as `(a, m)` pairs, returning the smallest `x` along with
+/// the modulus that every solution repeats with, which is the least common multiple of the moduli.
+///
+/// The moduli don't need to be coprime. `None` is returned if the congruences contradict each other, or if the
+/// combined modulus doesn't fit in a `u64`. No congruences at all are solved by every number, as `(0, 1)`.
+///
+/// Panics if a modulus is zero.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+	congruences.iter().try_fold((0, 1), |(a1, m1), &(a2, m2)| {
+		assert!(m2 != 0, "modulo zero");
+		let a2 = a2 % m2;
+		let (g, p, _) = extended_gcd(m1 as i128, m2 as i128);
+		let diff = a2 as i128 - a1 as i128;
+		if diff % g != 0 {
+			return None;
+		}
+		// step from a1 in multiples of m1 until a2 (mod m2) is reached
+		let step = m2 as i128 / g;
+		let m = u64::try_from(m1 as u128 * step as u128).ok()?;
+		// both factors are below step, which fits in a u64, so their product fits in a u128
+		let k = (diff / g).rem_euclid(step) as u128 * p.rem_euclid(step) as u128 % step as u128;
+		// a1 < m1 and k < step, so this stays below m
+		let x = a1 as u128 + m1 as u128 * k;
+		Some((x as u64, m))
+	})
+}
+
+#[test]
+fn gcd_lcm() {
+	assert_eq!(gcd(0u64, 0), 0);
+	assert_eq!(gcd(0u64, 7), 7);
+	assert_eq!(gcd(7u64, 0), 7);
+	assert_eq!(gcd(12u32, 18), 6);
+	assert_eq!(gcd(17u8, 5), 1);
+	assert_eq!(lcm(4usize, 6), Some(12));
+	assert_eq!(lcm(0usize, 6), Some(0));
+	assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+	assert_eq!(lcm(1u128 << 100, 1 << 90), Some(1 << 100));
+
+	// against a brute force search
+	for a in 1..40u32 {
+		for b in 1..40u32 {
+			let g = (1..=a.min(b)).rev().find(|d| a % d == 0 && b % d == 0).unwrap();
+			let l = (1..).map(|n| n * a).find(|m| m % b == 0).unwrap();
+			assert_eq!((gcd(a, b), lcm(a, b)), (g, Some(l)), "{} {}", a, b);
+		}
+	}
+
+	assert_eq!(gcd_all::<u64>(&[]), 0);
+	assert_eq!(gcd_all(&[12u64, 18, 27]), 3);
+	assert_eq!(lcm_all::<u64>(&[]), Some(1));
+	assert_eq!(lcm_all(&[2u64, 3, 4, 5, 6]), Some(60));
+	assert_eq!(lcm_all(&[u64::MAX, 2]), None);
+}
+
+#[test]
+fn square_roots() {
+	for n in 0..10_000u64 {
+		let r = isqrt(n);
+		assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({}) = {}", n, r);
+	}
+	for r in [1u64, 2, 3, 1 << 16, (1 << 32) - 1, 3_037_000_499] {
+		assert_eq!(isqrt(r * r), r);
+		assert_eq!(isqrt(r * r - 1), r - 1);
+		assert_eq!(isqrt(r * r + 1), r);
+	}
+	assert_eq!(isqrt(u64::MAX), (1 << 32) - 1);
+	assert_eq!(isqrt(u128::MAX), (1 << 64) - 1);
+	assert_eq!(isqrt(u128::MAX - 1), (1 << 64) - 1);
+	let big = 1u128 << 63 | 12345;
+	assert_eq!(isqrt(big * big), big);
+	assert_eq!(isqrt(big * big - 1), big - 1);
+	assert_eq!(isqrt(u8::MAX), 15);
+}
+
+#[test]
+fn binomials() {
+	// against pascal's triangle
+	let mut row = vec![1u128];
+	for n in 0..70 {
+		for (k, c) in row.iter().enumerate() {
+			assert_eq!(binomial(n, k as u64), u64::try_from(*c).ok(), "C({}, {})", n, k);
+		}
+		assert_eq!(binomial(n, n + 1), Some(0));
+		row = std::iter::once(1).chain(row.windows(2).map(|w| w[0] + w[1])).chain([1]).collect();
+	}
+	assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX));
+	assert_eq!(binomial(u64::MAX, u64::MAX - 1), Some(u64::MAX));
+	assert_eq!(binomial(u64::MAX, 2), None);
+	assert_eq!(binomial(68, 34), None);
+}
+
+#[test]
+fn modular_powers() {
+	assert_eq!(modpow(2, 10, 1000), 24);
+	assert_eq!(modpow(0, 0, 7), 1);
+	assert_eq!(modpow(5, 0, 1), 0);
+	assert_eq!(modpow(3, 200, 13), 9);
+	// fermat's little theorem, with a modulus that needs 128 bit products
+	let p = 18_446_744_073_709_551_557;
+	assert_eq!(modpow(123_456_789, p - 1, p), 1);
+	for base in 0..20u64 {
+		for exp in 0..20u32 {
+			assert_eq!(modpow(base, exp as u64, 1009), ((base as u128).pow(exp) % 1009) as u64);
+		}
+	}
+}
+
+#[test]
+fn chinese_remainders() {
+	assert_eq!(crt(&[]), Some((0, 1)));
+	assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+	// non-coprime moduli, which only agree when the residues do
+	assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+	assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+	assert_eq!(crt(&[(7, 5)]), Some((2, 5)));
+	assert_eq!(crt(&[(0, u64::MAX), (1, 2)]), None);
+	assert_eq!(crt(&[(0, 2), (u64::MAX - 1, u64::MAX)]), None);
+	// moduli near u64::MAX, whose products need 128 bits
+	let p = 18_446_744_073_709_551_557;
+	assert_eq!(crt(&[(u64::MAX, p)]), Some((58, p)));
+	assert_eq!(crt(&[(1, u64::MAX), (2, u64::MAX - 1)]), None);
+	assert_eq!(crt(&[(u64::MAX - 1, u64::MAX), (3, u64::MAX)]), None);
+	assert_eq!(crt(&[(u64::MAX - 1, u64::MAX), (u64::MAX - 1, u64::MAX)]), Some((u64::MAX - 1, u64::MAX)));
+	let n = 1u64 << 32;
+	assert_eq!(crt(&[(1, n), (0, n - 1)]), Some(((n - 1) * (n - 1), n * (n - 1))));
+
+	// against a brute force search
+	for m1 in 1..13 {
+		for m2 in 1..13 {
+			for a1 in 0..m1 {
+				for a2 in 0..m2 {
+					let l = lcm(m1, m2).unwrap();
+					let expected = (0..l).find(|x| x % m1 == a1 && x % m2 == a2).map(|x| (x, l));
+					assert_eq!(crt(&[(a1, m1), (a2, m2)]), expected, "x = {} (mod {}), x = {} (mod {})", a1, m1, a2, m2);
+				}
+			}
+		}
+	}
+
+	// against the congruences themselves, for large moduli drawn from a xorshift generator
+	let mut state = 0x2545_f491_4f6c_dd1du64;
+	let mut next = move || {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		state
+	};
+	for _ in 0..10_000 {
+		let m1 = next() >> (next() % 64) | 1;
+		// usually small enough for the moduli to share a u64, but not always
+		let m2 = match next() % 4 {
+			0 => next().max(1),
+			_ => (next() / m1).saturating_add(next() % 3).max(1),
+		};
+		let (a1, a2) = (next(), next());
+		match crt(&[(a1, m1), (a2, m2)]) {
+			Some((x, m)) => {
+				assert_eq!((x % m1, x % m2, Some(m)), (a1 % m1, a2 % m2, lcm(m1, m2)), "{} {} {} {}", a1, m1, a2, m2);
+				assert!(x < m);
+			},
+			None => assert!(lcm(m1, m2).is_none() || (a1 % m1) % gcd(m1, m2) != (a2 % m2) % gcd(m1, m2), "{} {} {} {}", a1, m1, a2, m2),
+		}
+	}
+
+	// the ghosts of day 8 line up at the lcm of their cycles, when each cycle starts back at zero
+	assert_eq!(crt(&[(0, 12_643), (0, 14_257), (0, 15_871)]), lcm_all(&[12_643u64, 14_257, 15_871]).map(|l| (0, l)));
+}
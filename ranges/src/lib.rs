@@ -0,0 +1,207 @@
+//! Sets of `usize` ranges, and maps that move some of those ranges by an offset, for the puzzles that push whole
+//! ranges of values through a series of mappings instead of one value at a time.
+use std::ops::Range;
+
+mod map;
+
+pub use map::{MapError, OffsetMap};
+
+/// Splits `range` at the edges of the ranges in `partition`, which have to be sorted and must not overlap.
+///
+/// Each piece is given in order, along with the index of the partition range it is within, or `None` for the
+/// pieces in the gaps between them.
+pub fn split<'p>(range: Range<usize>, partition: &'p [Range<usize>]) -> impl Iterator<Item = (Option<usize>, Range<usize>)> + 'p {
+	debug_assert!(partition.windows(2).all(|w| w[0].end <= w[1].start), "partition is not sorted, or overlaps");
+	// skip the partition ranges that end before the range starts
+	let mut i = partition.partition_point(|p| p.end <= range.start);
+	let mut rest = range;
+	std::iter::from_fn(move || {
+		if rest.is_empty() {
+			return None;
+		}
+		let piece = match partition.get(i) {
+			// all that is left is in the gap after the last range
+			None => (None, rest.clone()),
+			// in the gap before the next range
+			Some(p) if rest.start < p.start => (None, rest.start..rest.end.min(p.start)),
+			Some(p) => {
+				let piece = (Some(i), rest.start..rest.end.min(p.end));
+				i += 1;
+				piece
+			},
+		};
+		rest.start = piece.1.end;
+		Some(piece)
+	})
+}
+
+/// A set of values, kept as sorted ranges that neither overlap nor touch each other
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+	ranges: Vec<Range<usize>>,
+}
+
+impl RangeSet {
+	pub fn new() -> RangeSet {
+		RangeSet::default()
+	}
+
+	pub fn ranges(&self) -> &[Range<usize>] {
+		&self.ranges
+	}
+	pub fn is_empty(&self) -> bool {
+		self.ranges.is_empty()
+	}
+	/// How many values are in the set
+	pub fn len(&self) -> usize {
+		self.ranges.iter().map(|r| r.len()).sum()
+	}
+	pub fn min(&self) -> Option<usize> {
+		self.ranges.first().map(|r| r.start)
+	}
+	pub fn contains(&self, value: usize) -> bool {
+		let i = self.ranges.partition_point(|r| r.end <= value);
+		self.ranges.get(i).is_some_and(|r| r.contains(&value))
+	}
+
+	/// Adds every value in `range`, merging it with the ranges it overlaps or touches.
+	pub fn insert(&mut self, range: Range<usize>) {
+		if range.is_empty() {
+			return;
+		}
+		// the ranges from `first` up to `last` overlap or touch the new one
+		let first = self.ranges.partition_point(|r| r.end < range.start);
+		let last = self.ranges.partition_point(|r| r.start <= range.end);
+		let merged = match self.ranges[first..last] {
+			[] => range,
+			ref touching => touching[0].start.min(range.start)..touching[touching.len()-1].end.max(range.end),
+		};
+		self.ranges.splice(first..last, [merged]);
+	}
+
+	pub fn union(&self, other: &RangeSet) -> RangeSet {
+		let mut union = self.clone();
+		for r in &other.ranges {
+			union.insert(r.clone());
+		}
+		union
+	}
+
+	pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+		let (mut a, mut b) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+		let mut ranges = Vec::new();
+		while let (Some(ra), Some(rb)) = (a.peek(), b.peek()) {
+			let overlap = ra.start.max(rb.start)..ra.end.min(rb.end);
+			if !overlap.is_empty() {
+				ranges.push(overlap);
+			}
+			// whichever ends first can't overlap anything else
+			if ra.end <= rb.end { a.next(); } else { b.next(); }
+		}
+		RangeSet { ranges }
+	}
+
+	/// The values in this set that aren't in `other`
+	pub fn difference(&self, other: &RangeSet) -> RangeSet {
+		let ranges = self.ranges.iter()
+			.flat_map(|r| split(r.clone(), &other.ranges))
+			.filter(|(within, _)| within.is_none())
+			.map(|(_, piece)| piece)
+			.collect();
+		RangeSet { ranges }
+	}
+
+	/// Splits each range in the set against `partition`, like [`split`].
+	pub fn split<'a>(&'a self, partition: &'a [Range<usize>]) -> impl Iterator<Item = (Option<usize>, Range<usize>)> + 'a {
+		self.ranges.iter().flat_map(|r| split(r.clone(), partition))
+	}
+}
+
+impl From<Range<usize>> for RangeSet {
+	fn from(range: Range<usize>) -> RangeSet {
+		RangeSet::from_iter([range])
+	}
+}
+
+impl FromIterator<Range<usize>> for RangeSet {
+	fn from_iter<T: IntoIterator<Item = Range<usize>>>(iter: T) -> Self {
+		let mut set = RangeSet::new();
+		for r in iter {
+			set.insert(r);
+		}
+		set
+	}
+}
+
+/// Every value of an 8 bit mask, as a set of single values
+#[cfg(test)]
+fn from_mask(mask: u8) -> RangeSet {
+	(0..8).filter(|b| mask & 1 << b != 0).map(|b| b..b+1).collect()
+}
+
+#[cfg(test)]
+fn to_mask(set: &RangeSet) -> u8 {
+	set.ranges().iter().flat_map(|r| r.clone()).fold(0, |mask, b| mask | 1 << b)
+}
+
+#[test]
+fn set_operations() {
+	for a in 0..=u8::MAX {
+		let sa = from_mask(a);
+		// ranges are merged with their neighbours as soon as they touch
+		assert!(sa.ranges().windows(2).all(|w| w[0].end < w[1].start), "{:?}", sa);
+		assert_eq!(to_mask(&sa), a);
+		assert_eq!(sa.len(), a.count_ones() as usize);
+		assert_eq!(sa.min(), (a != 0).then(|| a.trailing_zeros() as usize));
+		assert!((0..10).all(|v| sa.contains(v) == (v < 8 && a & 1 << v != 0)));
+
+		for b in 0..=u8::MAX {
+			let sb = from_mask(b);
+			assert_eq!(to_mask(&sa.union(&sb)), a | b, "{:08b} | {:08b}", a, b);
+			assert_eq!(to_mask(&sa.intersection(&sb)), a & b, "{:08b} & {:08b}", a, b);
+			assert_eq!(to_mask(&sa.difference(&sb)), a & !b, "{:08b} - {:08b}", a, b);
+			assert_eq!(sa.union(&sb), from_mask(a | b));
+			assert_eq!(sa.intersection(&sb), from_mask(a & b));
+			assert_eq!(sa.difference(&sb), from_mask(a & !b));
+		}
+	}
+}
+
+#[test]
+fn insert() {
+	let mut set = RangeSet::from_iter([10..20, 30..40]);
+	set.insert(0..0);
+	assert_eq!(set.ranges(), [10..20, 30..40]);
+	set.insert(20..25);
+	assert_eq!(set.ranges(), [10..25, 30..40]);
+	set.insert(0..5);
+	assert_eq!(set.ranges(), [0..5, 10..25, 30..40]);
+	set.insert(4..35);
+	assert_eq!(set, RangeSet::from(0..40));
+	set.insert(usize::MAX-1..usize::MAX);
+	assert_eq!(set.ranges(), [0..40, usize::MAX-1..usize::MAX]);
+}
+
+#[test]
+fn splits() {
+	let partition = [2..4, 4..5, 7..9];
+	for start in 0..11 {
+		for end in start..11 {
+			let pieces = split(start..end, &partition).collect::<Vec<_>>();
+			// the pieces cover the range in order
+			assert_eq!(pieces.iter().flat_map(|(_, p)| p.clone()).collect::<Vec<_>>(), (start..end).collect::<Vec<_>>());
+			assert!(pieces.iter().all(|(_, p)| !p.is_empty()));
+			// and each piece is entirely within the partition it says, or outside all of them
+			for (within, piece) in pieces {
+				for v in piece {
+					assert_eq!(partition.iter().position(|p| p.contains(&v)), within, "{} in {:?}", v, start..end);
+				}
+			}
+		}
+	}
+
+	let set = RangeSet::from_iter([0..3, 8..10]);
+	assert_eq!(set.split(&partition).collect::<Vec<_>>(), [
+		(None, 0..2), (Some(0), 2..3), (Some(2), 8..9), (None, 9..10),
+	]);
+}
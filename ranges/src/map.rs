@@ -0,0 +1,129 @@
+use std::ops::Range;
+use crate::{split, RangeSet};
+
+/// Why an [`OffsetMap`] can't be made from some source ranges and offsets
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MapError {
+	/// Two of the source ranges share some values, so it can't tell which to use for them.
+	#[error("source ranges {0:?} and {1:?} overlap")]
+	Overlap(Range<usize>, Range<usize>),
+	/// The offset would move part of its range below zero or past `usize::MAX`.
+	#[error("offset {1:+} moves part of {0:?} outside of usize")]
+	OutOfBounds(Range<usize>, isize),
+}
+
+/// Moves the values within some source ranges by an offset for each range. Values outside all of them are
+/// left where they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OffsetMap {
+	/// sorted, and never overlapping
+	sources: Vec<Range<usize>>,
+	offsets: Vec<isize>,
+}
+
+fn shift(value: usize, offset: isize) -> usize {
+	value.checked_add_signed(offset).expect("offset moved a value outside of usize")
+}
+
+impl OffsetMap {
+	/// Creates a map from source ranges and the offset to move each one by. Empty ranges are ignored.
+	pub fn new(segments: impl IntoIterator<Item = (Range<usize>, isize)>) -> Result<OffsetMap, MapError> {
+		let mut segments = segments.into_iter()
+			.filter(|(r, _)| !r.is_empty())
+			.collect::<Vec<_>>();
+		segments.sort_by_key(|(r, _)| r.start);
+		if let Some(w) = segments.windows(2).find(|w| w[0].0.end > w[1].0.start) {
+			return Err(MapError::Overlap(w[0].0.clone(), w[1].0.clone()));
+		}
+		if let Some((r, offset)) = segments.iter()
+			.find(|(r, offset)| r.start.checked_add_signed(*offset).is_none() || (r.end - 1).checked_add_signed(*offset).is_none())
+		{
+			return Err(MapError::OutOfBounds(r.clone(), *offset));
+		}
+		let (sources, offsets) = segments.into_iter().unzip();
+		Ok(OffsetMap { sources, offsets })
+	}
+
+	/// Each source range with its offset, in order
+	pub fn segments(&self) -> impl Iterator<Item = (Range<usize>, isize)> + '_ {
+		self.sources.iter().cloned().zip(self.offsets.iter().copied())
+	}
+
+	/// Where a value is moved to, if it is within one of the source ranges.
+	pub fn get(&self, value: usize) -> Option<usize> {
+		let i = self.sources.partition_point(|r| r.end <= value);
+		self.sources.get(i)
+			.filter(|r| r.contains(&value))
+			.map(|_| shift(value, self.offsets[i]))
+	}
+	/// Where a value is moved to, which is where it already is if it isn't in any source range.
+	pub fn map(&self, value: usize) -> usize {
+		self.get(value).unwrap_or(value)
+	}
+
+	/// Splits `range` into the pieces that are each moved by the same offset, and moves them. The pieces are
+	/// given in the order they were in before being moved, along with the offset they were moved by.
+	pub fn split(&self, range: Range<usize>) -> impl Iterator<Item = (isize, Range<usize>)> + '_ {
+		split(range, &self.sources).map(|(within, piece)| {
+			let offset = within.map_or(0, |i| self.offsets[i]);
+			(offset, shift(piece.start, offset)..shift(piece.end - 1, offset) + 1)
+		})
+	}
+
+	/// Moves every value in a set.
+	pub fn map_set(&self, set: &RangeSet) -> RangeSet {
+		set.ranges().iter()
+			.flat_map(|r| self.split(r.clone()))
+			.map(|(_, moved)| moved)
+			.collect()
+	}
+}
+
+#[test]
+fn overlaps() {
+	assert_eq!(OffsetMap::new([(0..16, 64), (8..24, 64)]), Err(MapError::Overlap(0..16, 8..24)));
+	assert!(OffsetMap::new([(16..32, 64), (0..16, 64)]).is_ok());
+	assert!(OffsetMap::new([(0..16, 64), (32..48, 64), (40..40, 0)]).is_ok());
+}
+
+#[test]
+fn out_of_bounds() {
+	assert_eq!(OffsetMap::new([(0..16, 64), (24..32, -32)]), Err(MapError::OutOfBounds(24..32, -32)));
+	assert_eq!(OffsetMap::new([(usize::MAX - 4..usize::MAX, 8)]), Err(MapError::OutOfBounds(usize::MAX - 4..usize::MAX, 8)));
+	assert!(OffsetMap::new([(8..24, -8), (usize::MAX - 4..usize::MAX, 1)]).is_ok());
+}
+
+#[test]
+fn mapping() {
+	// the example's seed-to-soil map
+	let map = OffsetMap::new([(98..100, -48), (50..98, 2)]).unwrap();
+	assert_eq!(map.segments().collect::<Vec<_>>(), [(50..98, 2), (98..100, -48)]);
+	assert_eq!([0, 49, 50, 97, 98, 99, 100].map(|v| map.map(v)), [0, 49, 52, 99, 50, 51, 100]);
+	assert_eq!(map.get(49), None);
+
+	let splits = |r: Range<usize>| map.split(r).collect::<Vec<_>>();
+	assert_eq!(splits(0..40), [(0, 0..40)]);
+	assert_eq!(splits(25..99), [(0, 25..50), (2, 52..100), (-48, 50..51)]);
+	assert_eq!(splits(50..200), [(2, 52..100), (-48, 50..52), (0, 100..200)]);
+
+	let with_hole = OffsetMap::new([(25..30, 75), (40..50, 160)]).unwrap();
+	assert_eq!(with_hole.split(0..100).collect::<Vec<_>>(), [
+		(0, 0..25), (75, 100..105), (0, 30..40), (160, 200..210), (0, 50..100),
+	]);
+}
+
+#[test]
+fn map_sets() {
+	let maps = [
+		OffsetMap::new([(2..5, 3), (5..6, -5), (8..10, -2)]).unwrap(),
+		OffsetMap::new([(0..12, 0)]).unwrap(),
+		OffsetMap::default(),
+	];
+	for map in maps {
+		for mask in 0..1u16 << 12 {
+			let set = (0..12).filter(|b| mask & 1 << b != 0).map(|b| b..b+1).collect::<RangeSet>();
+			let expected = (0..12).filter(|b| mask & 1 << b != 0).map(|b| map.map(b)).map(|v| v..v+1).collect::<RangeSet>();
+			assert_eq!(map.map_set(&set), expected, "{:?} through {:?}", set, map);
+		}
+	}
+}